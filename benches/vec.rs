@@ -0,0 +1,122 @@
+//! Compares `MiniVec` against `Vec` on the operations that write many elements at once.
+//!
+//! Run with `cargo bench`. Each case reports the fastest of several rounds, in nanoseconds per
+//! call, for both containers side by side.
+
+use minivec::{mini_vec, MiniVec};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROUNDS: usize = 10;
+const ITERS: u32 = 200;
+
+fn fastest<R>(mut f: impl FnMut() -> R) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..ITERS {
+                black_box(f());
+            }
+            start.elapsed() / ITERS
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, vec: Duration, mini: Duration) {
+    println!(
+        "{:<32} Vec {:>10} ns   MiniVec {:>10} ns   ({:.2}x)",
+        name,
+        vec.as_nanos(),
+        mini.as_nanos(),
+        mini.as_secs_f64() / vec.as_secs_f64()
+    );
+}
+
+fn main() {
+    let bytes: Vec<u8> = (0..64 * 1024).map(|x| x as u8).collect();
+    let words: Vec<u64> = (0..16 * 1024).collect();
+    let strings: Vec<String> = (0..1024).map(|x| x.to_string()).collect();
+
+    report(
+        "extend_from_slice u8",
+        fastest(|| Vec::new().extend_from_slice(black_box(&bytes))),
+        fastest(|| MiniVec::new().extend_from_slice(black_box(&bytes))),
+    );
+
+    report(
+        "extend_from_slice u64",
+        fastest(|| Vec::new().extend_from_slice(black_box(&words))),
+        fastest(|| MiniVec::new().extend_from_slice(black_box(&words))),
+    );
+
+    report(
+        "extend_from_slice String",
+        fastest(|| Vec::new().extend_from_slice(black_box(&strings))),
+        fastest(|| MiniVec::new().extend_from_slice(black_box(&strings))),
+    );
+
+    let vec = words.clone();
+    let mini = MiniVec::from(&words[..]);
+    report(
+        "clone u64",
+        fastest(|| black_box(&vec).clone()),
+        fastest(|| black_box(&mini).clone()),
+    );
+
+    report(
+        "from elem u64",
+        fastest(|| vec![black_box(7_u64); 16 * 1024]),
+        fastest(|| mini_vec![black_box(7_u64); 16 * 1024]),
+    );
+
+    report(
+        "extend &u64",
+        fastest(|| {
+            let mut v: Vec<u64> = Vec::new();
+            v.extend(black_box(&words).iter());
+            v
+        }),
+        fastest(|| {
+            let mut v: MiniVec<u64> = MiniVec::new();
+            v.extend(black_box(&words).iter());
+            v
+        }),
+    );
+
+    report(
+        "collect u64",
+        fastest(|| (0..black_box(16 * 1024_u64)).collect::<Vec<_>>()),
+        fastest(|| (0..black_box(16 * 1024_u64)).collect::<MiniVec<_>>()),
+    );
+
+    report(
+        "collect chain u64",
+        fastest(|| {
+            let n = black_box(8 * 1024_u64);
+            (0..n).chain(n..2 * n).collect::<Vec<_>>()
+        }),
+        fastest(|| {
+            let n = black_box(8 * 1024_u64);
+            (0..n).chain(n..2 * n).collect::<MiniVec<_>>()
+        }),
+    );
+
+    report(
+        "push u64",
+        fastest(|| {
+            let mut v: Vec<u64> = Vec::new();
+            for x in 0..black_box(16 * 1024_u64) {
+                v.push(x);
+            }
+            v
+        }),
+        fastest(|| {
+            let mut v: MiniVec<u64> = MiniVec::new();
+            for x in 0..black_box(16 * 1024_u64) {
+                v.push(x);
+            }
+            v
+        }),
+    );
+}
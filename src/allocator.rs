@@ -0,0 +1,258 @@
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+/// `AllocError` is the error type returned by an [`Allocator`](Allocator) when it is unable to
+/// satisfy a request.
+///
+#[derive(core::fmt::Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl core::fmt::Display for AllocError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {}
+
+/// `Allocator` is a stable version of the interface proposed by the stdlib's unstable
+/// `core::alloc::Allocator` trait and mirrors the one exported by the `allocator-api2` crate.
+///
+/// `MiniVec` routes every allocation, reallocation and deallocation through its allocator
+/// parameter which makes it possible to store vectors in arenas, pools or tracking allocators.
+///
+/// When the `allocator-api2` feature is enabled, allocators from the `allocator-api2` crate can be
+/// used by wrapping them in `Api2`.
+///
+/// # Safety
+///
+/// Memory blocks returned from an allocator must point to valid memory and retain their validity
+/// until the block is deallocated or the allocator is dropped. Moving or cloning the allocator
+/// must not invalidate blocks returned from it.
+///
+pub unsafe trait Allocator {
+    /// `allocate` attempts to allocate a block of memory that fits `layout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AllocError`](AllocError) if the allocation could not be made.
+    ///
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+    /// `deallocate` releases the memory referenced by `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block of memory currently allocated by this allocator and `layout` must
+    /// be the layout that was used to allocate it.
+    ///
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// `grow` attempts to extend the memory block referenced by `ptr` so that it fits
+    /// `new_layout`. The contents of the old block are preserved.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block of memory currently allocated by this allocator, `old_layout`
+    /// must be the layout used to allocate it and `new_layout.size()` must be greater than or
+    /// equal to `old_layout.size()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AllocError`](AllocError) if the new block could not be allocated in which case
+    /// the old block is left untouched.
+    ///
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert!(new_layout.size() >= old_layout.size());
+
+        let new_ptr = self.allocate(new_layout)?;
+        core::ptr::copy_nonoverlapping(
+            ptr.as_ptr(),
+            new_ptr.as_ptr().cast::<u8>(),
+            old_layout.size(),
+        );
+        self.deallocate(ptr, old_layout);
+
+        Ok(new_ptr)
+    }
+
+    /// `shrink` attempts to shrink the memory block referenced by `ptr` so that it fits
+    /// `new_layout`. The first `new_layout.size()` bytes of the old block are preserved.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block of memory currently allocated by this allocator, `old_layout`
+    /// must be the layout used to allocate it and `new_layout.size()` must be smaller than or
+    /// equal to `old_layout.size()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AllocError`](AllocError) if the new block could not be allocated in which case
+    /// the old block is left untouched.
+    ///
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert!(new_layout.size() <= old_layout.size());
+
+        let new_ptr = self.allocate(new_layout)?;
+        core::ptr::copy_nonoverlapping(
+            ptr.as_ptr(),
+            new_ptr.as_ptr().cast::<u8>(),
+            new_layout.size(),
+        );
+        self.deallocate(ptr, old_layout);
+
+        Ok(new_ptr)
+    }
+
+    // allocators confined to a fixed region, like `FixedStorage`, report how many bytes the block
+    // at `ptr`, or a new block if there's none, may ever span. `MiniVec` clamps its growth to this
+    // and reports running out of room as a capacity overflow rather than an allocation failure
+    //
+    // `ptr` must denote a block of memory currently allocated by this allocator
+    //
+    #[doc(hidden)]
+    unsafe fn size_limit(&self, ptr: Option<NonNull<u8>>) -> Option<usize> {
+        let _ = ptr;
+        None
+    }
+}
+
+/// `Global` is the default allocator for `MiniVec` and forwards every request to the global
+/// allocator registered via `#[global_allocator]`.
+///
+/// `Global` is a zero-sized type so `MiniVec<T, Global>` is still only the size of a single
+/// pointer.
+///
+#[cfg(feature = "alloc")]
+#[derive(core::fmt::Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Global;
+
+#[cfg(feature = "alloc")]
+unsafe impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert!(layout.size() > 0);
+
+        let ptr = unsafe { alloc::alloc::alloc(layout) };
+        NonNull::new(ptr)
+            .map(|p| NonNull::slice_from_raw_parts(p, layout.size()))
+            .ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        alloc::alloc::dealloc(ptr.as_ptr(), layout);
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert_eq!(old_layout.align(), new_layout.align());
+
+        let new_ptr = alloc::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size());
+        NonNull::new(new_ptr)
+            .map(|p| NonNull::slice_from_raw_parts(p, new_layout.size()))
+            .ok_or(AllocError)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.grow(ptr, old_layout, new_layout)
+    }
+}
+
+unsafe impl<A> Allocator for &A
+where
+    A: Allocator + ?Sized,
+{
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        (**self).deallocate(ptr, layout);
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        (**self).grow(ptr, old_layout, new_layout)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        (**self).shrink(ptr, old_layout, new_layout)
+    }
+
+    unsafe fn size_limit(&self, ptr: Option<NonNull<u8>>) -> Option<usize> {
+        (**self).size_limit(ptr)
+    }
+}
+
+/// `Api2` adapts an allocator implementing `allocator_api2::alloc::Allocator` so that it can be
+/// used with `MiniVec`.
+///
+/// Only available with the `allocator-api2` feature.
+///
+#[cfg(feature = "allocator-api2")]
+#[derive(core::fmt::Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Api2<A>(pub A);
+
+#[cfg(feature = "allocator-api2")]
+unsafe impl<A> Allocator for Api2<A>
+where
+    A: allocator_api2::alloc::Allocator,
+{
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.allocate(layout).map_err(|_| AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.deallocate(ptr, layout);
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.0
+            .grow(ptr, old_layout, new_layout)
+            .map_err(|_| AllocError)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.0
+            .shrink(ptr, old_layout, new_layout)
+            .map_err(|_| AllocError)
+    }
+}
@@ -1,13 +1,14 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T> core::convert::AsMut<[T]> for MiniVec<T> {
+impl<T, A: Allocator, H> core::convert::AsMut<[T]> for MiniVec<T, A, H> {
     fn as_mut(&mut self) -> &mut [T] {
         &mut *self
     }
 }
 
-impl<T> core::convert::AsMut<MiniVec<T>> for MiniVec<T> {
-    fn as_mut(&mut self) -> &mut MiniVec<T> {
+impl<T, A: Allocator, H> core::convert::AsMut<MiniVec<T, A, H>> for MiniVec<T, A, H> {
+    fn as_mut(&mut self) -> &mut MiniVec<T, A, H> {
         self
     }
 }
@@ -1,13 +1,14 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T> AsRef<[T]> for MiniVec<T> {
+impl<T, A: Allocator, H> AsRef<[T]> for MiniVec<T, A, H> {
     fn as_ref(&self) -> &[T] {
-        &*self
+        self
     }
 }
 
-impl<T> AsRef<MiniVec<T>> for MiniVec<T> {
-    fn as_ref(&self) -> &MiniVec<T> {
+impl<T, A: Allocator, H> AsRef<MiniVec<T, A, H>> for MiniVec<T, A, H> {
+    fn as_ref(&self) -> &MiniVec<T, A, H> {
         self
     }
 }
@@ -0,0 +1,205 @@
+use crate::MiniVec;
+
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// `AtomicMiniVec` is a slot holding a [`MiniVec`](crate::MiniVec) which can be swapped out
+/// atomically without locking, making it suitable for publishing snapshots across threads.
+///
+/// As a `MiniVec` is a single pointer, the slot is nothing more than an `AtomicPtr` with the same
+/// size as the vector itself. Values are only ever moved in and out of the slot, there is no way
+/// to borrow the current contents.
+///
+/// Every operation uses acquire-release ordering so that the elements of a vector are always
+/// visible to the thread taking it out of the slot.
+///
+/// # Example
+///
+/// ```
+/// use minivec::{mini_vec, AtomicMiniVec};
+///
+/// let slot = std::sync::Arc::new(AtomicMiniVec::new(mini_vec![1, 2, 3]));
+///
+/// let handle = {
+///     let slot = slot.clone();
+///     std::thread::spawn(move || slot.swap(mini_vec![4, 5, 6]))
+/// };
+///
+/// assert_eq!(handle.join().unwrap(), [1, 2, 3]);
+/// assert_eq!(slot.load_take(), [4, 5, 6]);
+/// assert!(slot.load_take().is_empty());
+/// ```
+///
+pub struct AtomicMiniVec<T> {
+    buf: AtomicPtr<u8>,
+    phantom: core::marker::PhantomData<MiniVec<T>>,
+}
+
+impl<T> AtomicMiniVec<T> {
+    fn into_buf(vec: MiniVec<T>) -> *mut u8 {
+        core::mem::ManuallyDrop::new(vec).buf.as_ptr()
+    }
+
+    unsafe fn from_buf(buf: *mut u8) -> MiniVec<T> {
+        let mut vec = MiniVec::new();
+        vec.buf = NonNull::new_unchecked(buf);
+        vec
+    }
+
+    /// `compare_exchange` stores `new` in the slot if it still holds the vector identified by
+    /// `current`, a token previously returned by [`load`](AtomicMiniVec::load). Returns the
+    /// previous vector on success and hands `new` back on failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns `new` unchanged if the slot no longer holds the vector identified by `current`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minivec::{mini_vec, AtomicMiniVec};
+    ///
+    /// let slot = AtomicMiniVec::new(mini_vec![1, 2]);
+    ///
+    /// let seen = slot.load();
+    /// assert_eq!(slot.compare_exchange(seen, mini_vec![3]).unwrap(), [1, 2]);
+    /// assert_eq!(slot.compare_exchange(seen, mini_vec![4]).unwrap_err(), [4]);
+    ///
+    /// assert_eq!(slot.load_take(), [3]);
+    /// ```
+    ///
+    pub fn compare_exchange(
+        &self,
+        current: SlotToken,
+        new: MiniVec<T>,
+    ) -> Result<MiniVec<T>, MiniVec<T>> {
+        let new = Self::into_buf(new);
+
+        // the token only carries an address to compare against, the vector handed back on success
+        // is rebuilt from the pointer actually stored in the slot
+        //
+        match self.buf.compare_exchange(
+            current.0 as *mut u8,
+            new,
+            Ordering::AcqRel,
+            Ordering::Relaxed,
+        ) {
+            Ok(old) => Ok(unsafe { Self::from_buf(old) }),
+            Err(_) => Err(unsafe { Self::from_buf(new) }),
+        }
+    }
+
+    /// `into_inner` consumes the slot and returns the vector it holds.
+    ///
+    #[must_use]
+    pub fn into_inner(self) -> MiniVec<T> {
+        let this = core::mem::ManuallyDrop::new(self);
+        unsafe { Self::from_buf(this.buf.load(Ordering::Relaxed)) }
+    }
+
+    /// `load` returns a [`SlotToken`](SlotToken) identifying the vector currently in the slot, for
+    /// use with [`compare_exchange`](AtomicMiniVec::compare_exchange).
+    ///
+    /// Note: the vector itself stays in the slot, only its identity is read.
+    ///
+    #[must_use]
+    pub fn load(&self) -> SlotToken {
+        SlotToken(self.buf.load(Ordering::Acquire) as usize)
+    }
+
+    /// `load_take` takes the vector out of the slot, leaving an empty one in its place.
+    ///
+    /// Note: does not allocate any memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let slot = minivec::AtomicMiniVec::new(minivec::mini_vec![1, 2, 3]);
+    ///
+    /// assert_eq!(slot.load_take(), [1, 2, 3]);
+    /// assert!(slot.load_take().is_empty());
+    /// ```
+    ///
+    #[must_use]
+    pub fn load_take(&self) -> MiniVec<T> {
+        self.swap(MiniVec::new())
+    }
+
+    /// `new` constructs a slot holding `vec`.
+    ///
+    #[must_use]
+    pub fn new(vec: MiniVec<T>) -> AtomicMiniVec<T> {
+        AtomicMiniVec {
+            buf: AtomicPtr::new(Self::into_buf(vec)),
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// `store` replaces the vector in the slot with `vec`, dropping the previous one.
+    ///
+    pub fn store(&self, vec: MiniVec<T>) {
+        drop(self.swap(vec));
+    }
+
+    /// `swap` replaces the vector in the slot with `vec`, returning the previous one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let slot = minivec::AtomicMiniVec::new(minivec::mini_vec![1, 2, 3]);
+    ///
+    /// assert_eq!(slot.swap(minivec::mini_vec![4]), [1, 2, 3]);
+    /// assert_eq!(slot.into_inner(), [4]);
+    /// ```
+    ///
+    #[must_use]
+    pub fn swap(&self, vec: MiniVec<T>) -> MiniVec<T> {
+        let old = self.buf.swap(Self::into_buf(vec), Ordering::AcqRel);
+        unsafe { Self::from_buf(old) }
+    }
+}
+
+/// `SlotToken` identifies the vector held by an [`AtomicMiniVec`](AtomicMiniVec) at the time of a
+/// [`load`](AtomicMiniVec::load) so that [`compare_exchange`](AtomicMiniVec::compare_exchange) can
+/// later check whether it's still there.
+///
+/// A token is the address of the vector's allocation, it's never dereferenced and elements are
+/// never compared. As such:
+///
+/// * every empty vector shares the same sentinel so the token of an empty slot matches any other
+///   empty vector.
+/// * a token stays meaningful for as long as its vector is in the slot. Once that vector has been
+///   taken out and dropped, a new allocation may reuse its address and be mistaken for it.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SlotToken(usize);
+
+impl<T> Drop for AtomicMiniVec<T> {
+    fn drop(&mut self) {
+        drop(unsafe { Self::from_buf(*self.buf.get_mut()) });
+    }
+}
+
+impl<T> Default for AtomicMiniVec<T> {
+    fn default() -> Self {
+        AtomicMiniVec::new(MiniVec::new())
+    }
+}
+
+impl<T> core::fmt::Debug for AtomicMiniVec<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AtomicMiniVec").finish_non_exhaustive()
+    }
+}
+
+impl<T> From<MiniVec<T>> for AtomicMiniVec<T> {
+    fn from(vec: MiniVec<T>) -> Self {
+        AtomicMiniVec::new(vec)
+    }
+}
+
+// a shared slot only ever moves vectors between threads, never handing out references to their
+// elements, so `T: Send` is all that's needed, just like a `Mutex<MiniVec<T>>`
+//
+unsafe impl<T: Send> Send for AtomicMiniVec<T> {}
+unsafe impl<T: Send> Sync for AtomicMiniVec<T> {}
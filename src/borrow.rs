@@ -1,12 +1,13 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T> core::borrow::Borrow<[T]> for MiniVec<T> {
+impl<T, A: Allocator, H> core::borrow::Borrow<[T]> for MiniVec<T, A, H> {
     fn borrow(&self) -> &[T] {
         &(self[..])
     }
 }
 
-impl<T> core::borrow::BorrowMut<[T]> for MiniVec<T> {
+impl<T, A: Allocator, H> core::borrow::BorrowMut<[T]> for MiniVec<T, A, H> {
     fn borrow_mut(&mut self) -> &mut [T] {
         &mut (self[..])
     }
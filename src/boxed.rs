@@ -0,0 +1,514 @@
+use crate::r#impl::helpers::{make_layout, max_align, next_aligned};
+use crate::{empty_header, write_header, zst_buf, Header};
+use crate::{Allocator, Global, MiniString, MiniVec};
+
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl<T> Sealed for [T] {}
+    impl Sealed for str {}
+}
+
+/// `FrozenSlice` is implemented for the unsized types a [`MiniBox`](MiniBox) can hold, namely
+/// `[T]` and `str`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+///
+pub trait FrozenSlice: sealed::Sealed {
+    /// The type of the elements stored in the allocation.
+    type Elem;
+
+    #[doc(hidden)]
+    unsafe fn from_raw_parts<'a>(data: *const Self::Elem, len: usize) -> &'a Self;
+
+    #[doc(hidden)]
+    unsafe fn from_raw_parts_mut<'a>(data: *mut Self::Elem, len: usize) -> &'a mut Self;
+}
+
+impl<T> FrozenSlice for [T] {
+    type Elem = T;
+
+    unsafe fn from_raw_parts<'a>(data: *const T, len: usize) -> &'a [T] {
+        core::slice::from_raw_parts(data, len)
+    }
+
+    unsafe fn from_raw_parts_mut<'a>(data: *mut T, len: usize) -> &'a mut [T] {
+        core::slice::from_raw_parts_mut(data, len)
+    }
+}
+
+impl FrozenSlice for str {
+    type Elem = u8;
+
+    unsafe fn from_raw_parts<'a>(data: *const u8, len: usize) -> &'a str {
+        core::str::from_utf8_unchecked(core::slice::from_raw_parts(data, len))
+    }
+
+    unsafe fn from_raw_parts_mut<'a>(data: *mut u8, len: usize) -> &'a mut str {
+        core::str::from_utf8_unchecked_mut(core::slice::from_raw_parts_mut(data, len))
+    }
+}
+
+/// `MiniBox` is a frozen, single-pointer owner of a `[T]` or a `str`.
+///
+/// Where a `MiniVec` stores its length, capacity and alignment in front of its data and a
+/// `Box<[T]>` is two words wide, a `MiniBox` only stores the length in front of the data and its
+/// allocation is always shrunk to fit. This makes it ideal for data which is built once and then
+/// never grows again.
+///
+/// A `MiniBox<[T]>` is created via [`MiniVec::into_frozen`](crate::MiniVec::into_frozen) and a
+/// `MiniBox<str>` via [`MiniString::into_frozen`](crate::MiniString::into_frozen). Both can be
+/// turned back into their growable counterparts, reusing the allocation.
+///
+/// # Example
+///
+/// ```
+/// let frozen = minivec::mini_vec![1, 2, 3].into_frozen();
+///
+/// assert_eq!(frozen, [1, 2, 3]);
+/// assert_eq!(core::mem::size_of_val(&frozen), core::mem::size_of::<usize>());
+///
+/// let mut vec = frozen.into_vec();
+/// vec.push(4);
+/// assert_eq!(vec, [1, 2, 3, 4]);
+/// ```
+///
+pub struct MiniBox<T: ?Sized + FrozenSlice, A: Allocator = Global> {
+    buf: NonNull<u8>,
+    allocator: A,
+    phantom: core::marker::PhantomData<T>,
+}
+
+// frozen allocations are laid out as `[len][pad][data]` and use the same default alignment as
+// `MiniVec` so that converting between the two is a matter of moving the data within a single
+// allocation
+//
+fn frozen_offset<E>() -> usize {
+    next_aligned(core::mem::size_of::<usize>(), max_align::<E>())
+}
+
+fn frozen_layout<E>(len: usize) -> Layout {
+    len.checked_mul(core::mem::size_of::<E>())
+        .and_then(|n| n.checked_add(frozen_offset::<E>()))
+        .and_then(|n| Layout::from_size_align(n, max_align::<E>()).ok())
+        .expect("capacity overflow")
+}
+
+impl<T: ?Sized + FrozenSlice, A: Allocator> MiniBox<T, A> {
+    fn is_allocated(&self) -> bool {
+        core::mem::size_of::<T::Elem>() > 0 && self.buf != empty_header()
+    }
+
+    fn data(&self) -> *mut T::Elem {
+        if !self.is_allocated() {
+            return NonNull::dangling().as_ptr();
+        }
+
+        unsafe { self.buf.as_ptr().add(frozen_offset::<T::Elem>()).cast() }
+    }
+
+    #[allow(clippy::cast_ptr_alignment)]
+    fn elem_len(&self) -> usize {
+        if core::mem::size_of::<T::Elem>() == 0 {
+            self.buf.as_ptr() as usize - 1
+        } else {
+            unsafe { core::ptr::read(self.buf.as_ptr().cast::<usize>()) }
+        }
+    }
+
+    fn into_parts(self) -> (NonNull<u8>, A) {
+        let this = core::mem::ManuallyDrop::new(self);
+        (this.buf, unsafe {
+            core::ptr::read(core::ptr::addr_of!(this.allocator))
+        })
+    }
+
+    /// `allocator` returns a reference to the allocator backing the box.
+    ///
+    #[must_use]
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+}
+
+impl<T, A: Allocator> MiniBox<[T], A> {
+    /// `into_vec` converts the box back into a growable [`MiniVec`](crate::MiniVec).
+    ///
+    /// The allocation is reused. When the `MiniVec` header doesn't fit in front of the data, the
+    /// allocation is grown in place, if the allocator supports it, and the elements are shifted
+    /// over to make room.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let frozen = minivec::mini_vec![String::from("a")].into_frozen();
+    ///
+    /// let mut vec = frozen.into_vec();
+    /// vec.push(String::from("b"));
+    ///
+    /// assert_eq!(vec.as_slice(), ["a", "b"]);
+    /// ```
+    ///
+    #[must_use]
+    pub fn into_vec(self) -> MiniVec<T, A> {
+        MiniVec::from(self)
+    }
+}
+
+impl<A: Allocator> MiniBox<str, A> {
+    /// `into_string` converts the box back into a growable [`MiniString`](crate::MiniString),
+    /// reusing the allocation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let frozen = minivec::MiniString::from("hello").into_frozen();
+    ///
+    /// let mut s = frozen.into_string();
+    /// s.push_str(" world");
+    ///
+    /// assert_eq!(s, "hello world");
+    /// ```
+    ///
+    #[must_use]
+    pub fn into_string(self) -> MiniString<A> {
+        let bytes = MiniBox::<[u8], A>::from(self).into_vec();
+        unsafe { MiniString::from_utf8_unchecked(bytes) }
+    }
+}
+
+impl<T: ?Sized + FrozenSlice, A: Allocator> Drop for MiniBox<T, A> {
+    fn drop(&mut self) {
+        let len = self.elem_len();
+        unsafe { core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(self.data(), len)) };
+
+        if !self.is_allocated() {
+            return;
+        }
+
+        unsafe {
+            self.allocator
+                .deallocate(self.buf, frozen_layout::<T::Elem>(len));
+        }
+    }
+}
+
+impl<T, A: Allocator> From<MiniVec<T, A>> for MiniBox<[T], A> {
+    #[allow(clippy::cast_ptr_alignment)]
+    fn from(vec: MiniVec<T, A>) -> Self {
+        let mut vec = vec;
+        vec.unshare();
+
+        let len = vec.len();
+        let capacity = vec.capacity();
+        let alignment = vec.layout_alignment();
+        let src = vec.data();
+        let is_allocated = vec.is_allocated();
+
+        let vec = core::mem::ManuallyDrop::new(vec);
+        let old_buf = vec.buf;
+        let allocator = unsafe { core::ptr::read(core::ptr::addr_of!(vec.allocator)) };
+
+        let buf = if core::mem::size_of::<T>() == 0 {
+            zst_buf(len)
+        } else if !is_allocated {
+            empty_header()
+        } else if len == 0 {
+            unsafe { allocator.deallocate(old_buf, make_layout::<T, ()>(capacity, alignment)) };
+            empty_header()
+        } else {
+            let old_layout = make_layout::<T, ()>(capacity, alignment);
+            let new_layout = frozen_layout::<T>(len);
+
+            // default-aligned vectors can be shrunk in place after sliding the elements towards
+            // the front whereas over-aligned ones require a fresh allocation as the alignment of
+            // the block itself changes
+            //
+            let buf = if alignment == new_layout.align() {
+                unsafe {
+                    let dst = old_buf.as_ptr().add(frozen_offset::<T>()).cast::<T>();
+                    core::ptr::copy(src, dst, len);
+
+                    allocator.shrink(old_buf, old_layout, new_layout)
+                }
+            } else {
+                let new_buf = allocator.allocate(new_layout);
+
+                if let Ok(p) = new_buf {
+                    unsafe {
+                        let dst = p
+                            .as_ptr()
+                            .cast::<u8>()
+                            .add(frozen_offset::<T>())
+                            .cast::<T>();
+                        core::ptr::copy_nonoverlapping(src, dst, len);
+
+                        allocator.deallocate(old_buf, old_layout);
+                    }
+                }
+
+                new_buf
+            };
+
+            let buf = match buf {
+                Ok(p) => p.cast::<u8>(),
+                Err(_) => alloc::alloc::handle_alloc_error(new_layout),
+            };
+
+            unsafe { core::ptr::write(buf.as_ptr().cast::<usize>(), len) };
+            buf
+        };
+
+        MiniBox {
+            buf,
+            allocator,
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, A: Allocator> From<MiniBox<[T], A>> for MiniVec<T, A> {
+    fn from(b: MiniBox<[T], A>) -> Self {
+        let len = b.elem_len();
+        let is_allocated = b.is_allocated();
+        let (old_buf, allocator) = b.into_parts();
+
+        if core::mem::size_of::<T>() == 0 {
+            let mut vec = MiniVec::new_in(allocator);
+            vec.buf = zst_buf(len);
+            return vec;
+        }
+
+        if !is_allocated {
+            return MiniVec::new_in(allocator);
+        }
+
+        let alignment = max_align::<T>();
+        let old_layout = frozen_layout::<T>(len);
+        let new_layout = make_layout::<T, ()>(len, alignment);
+
+        let buf = if new_layout.size() == old_layout.size() {
+            old_buf
+        } else {
+            match unsafe { allocator.grow(old_buf, old_layout, new_layout) } {
+                Ok(p) => p.cast::<u8>(),
+                Err(_) => alloc::alloc::handle_alloc_error(new_layout),
+            }
+        };
+
+        let offset = next_aligned(core::mem::size_of::<Header>(), alignment);
+        if offset != frozen_offset::<T>() {
+            unsafe {
+                let src = buf.as_ptr().add(frozen_offset::<T>()).cast::<T>();
+                let dst = buf.as_ptr().add(offset).cast::<T>();
+                core::ptr::copy(src, dst, len);
+            }
+        }
+
+        unsafe {
+            write_header::<()>(
+                buf,
+                Header {
+                    len,
+                    cap: len,
+                    alignment,
+                },
+            );
+        }
+
+        let mut vec = MiniVec::new_in(allocator);
+        vec.buf = buf;
+        vec
+    }
+}
+
+impl<A: Allocator> From<MiniString<A>> for MiniBox<str, A> {
+    fn from(s: MiniString<A>) -> Self {
+        let (buf, allocator) = MiniBox::<[u8], A>::from(s.into_bytes()).into_parts();
+
+        MiniBox {
+            buf,
+            allocator,
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<A: Allocator> From<MiniBox<str, A>> for MiniBox<[u8], A> {
+    fn from(s: MiniBox<str, A>) -> Self {
+        let (buf, allocator) = s.into_parts();
+
+        MiniBox {
+            buf,
+            allocator,
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Clone> From<&[T]> for MiniBox<[T]> {
+    fn from(s: &[T]) -> Self {
+        MiniVec::from(s).into_frozen()
+    }
+}
+
+impl From<&str> for MiniBox<str> {
+    fn from(s: &str) -> Self {
+        MiniString::from(s).into_frozen()
+    }
+}
+
+impl<T> core::iter::FromIterator<T> for MiniBox<[T]> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().collect::<MiniVec<T>>().into_frozen()
+    }
+}
+
+impl<T: ?Sized + FrozenSlice, A: Allocator> core::ops::Deref for MiniBox<T, A> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { T::from_raw_parts(self.data(), self.elem_len()) }
+    }
+}
+
+impl<T: ?Sized + FrozenSlice, A: Allocator> core::ops::DerefMut for MiniBox<T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { T::from_raw_parts_mut(self.data(), self.elem_len()) }
+    }
+}
+
+impl<T: ?Sized + FrozenSlice, A: Allocator> AsRef<T> for MiniBox<T, A> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ?Sized + FrozenSlice, A: Allocator> AsMut<T> for MiniBox<T, A> {
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T: ?Sized + FrozenSlice, A: Allocator> core::borrow::Borrow<T> for MiniBox<T, A> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ?Sized + FrozenSlice, A: Allocator> core::borrow::BorrowMut<T> for MiniBox<T, A> {
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for MiniBox<[T], A> {
+    fn clone(&self) -> Self {
+        let mut copy = MiniVec::with_capacity_in(self.len(), self.allocator.clone());
+        copy.extend_from_slice(self);
+        copy.into_frozen()
+    }
+}
+
+impl<A: Allocator + Clone> Clone for MiniBox<str, A> {
+    fn clone(&self) -> Self {
+        let mut copy = MiniString::with_capacity_in(self.len(), self.allocator.clone());
+        copy.push_str(self);
+        copy.into_frozen()
+    }
+}
+
+impl<T: ?Sized + FrozenSlice + core::fmt::Debug, A: Allocator> core::fmt::Debug for MiniBox<T, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let this: &T = self;
+
+        this.fmt(f)
+    }
+}
+
+impl<A: Allocator> core::fmt::Display for MiniBox<str, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let this: &str = self;
+
+        this.fmt(f)
+    }
+}
+
+impl<T> Default for MiniBox<[T]> {
+    fn default() -> Self {
+        MiniVec::new().into_frozen()
+    }
+}
+
+impl Default for MiniBox<str> {
+    fn default() -> Self {
+        MiniString::new().into_frozen()
+    }
+}
+
+impl<T: ?Sized + FrozenSlice + core::hash::Hash, A: Allocator> core::hash::Hash for MiniBox<T, A> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: core::hash::Hasher,
+    {
+        let this: &T = self;
+        core::hash::Hash::hash(this, state);
+    }
+}
+
+impl<T: ?Sized + FrozenSlice + Eq, A: Allocator> Eq for MiniBox<T, A> {}
+
+impl<T: ?Sized + FrozenSlice + Ord, A: Allocator> Ord for MiniBox<T, A> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let x: &T = self;
+        let y: &T = other;
+
+        x.cmp(y)
+    }
+}
+
+impl<T, A: Allocator, V> PartialEq<V> for MiniBox<T, A>
+where
+    T: ?Sized + FrozenSlice + PartialEq,
+    V: AsRef<T>,
+{
+    fn eq(&self, other: &V) -> bool {
+        let lhs: &T = self;
+        let rhs: &T = other.as_ref();
+
+        lhs == rhs
+    }
+}
+
+impl<T, A: Allocator, V> PartialOrd<V> for MiniBox<T, A>
+where
+    T: ?Sized + FrozenSlice + PartialOrd,
+    V: AsRef<T>,
+{
+    fn partial_cmp(&self, other: &V) -> Option<core::cmp::Ordering> {
+        let x: &T = self;
+        let y: &T = other.as_ref();
+
+        x.partial_cmp(y)
+    }
+}
+
+unsafe impl<T, A> Send for MiniBox<T, A>
+where
+    T: ?Sized + FrozenSlice + Send,
+    A: Allocator + Send,
+{
+}
+
+unsafe impl<T, A> Sync for MiniBox<T, A>
+where
+    T: ?Sized + FrozenSlice + Sync,
+    A: Allocator + Sync,
+{
+}
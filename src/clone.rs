@@ -1,18 +1,36 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T: Clone> Clone for MiniVec<T> {
+impl<T: Clone, A: Allocator + Clone, H: Clone> Clone for MiniVec<T, A, H> {
     fn clone(&self) -> Self {
-        if self.buf.is_null() {
-            return MiniVec::new();
-        }
+        // preserve the source's alignment so that over-aligned vectors stay over-aligned
+        //
+        let mut copy = MiniVec::<T, A, H>::empty_in(self.allocator.clone());
+        copy.grow(self.len(), self.alignment());
+        copy.clone_ext_from(self);
+        copy.extend_cloned(self);
 
-        let mut copy = MiniVec::<T>::new();
+        copy
+    }
 
-        copy.reserve(self.len());
-        for i in 0..self.len() {
-            copy.push(self[i].clone());
+    fn clone_from(&mut self, source: &Self) {
+        // the existing allocation is only reused if it's aligned at least as strictly as the
+        // source's, otherwise we'd silently drop an over-alignment
+        //
+        if self.alignment() < source.alignment() {
+            *self = source.clone();
+            return;
         }
 
-        copy
+        self.truncate(source.len());
+
+        let (init, tail) = source.split_at(self.len());
+        self.clone_from_slice(init);
+        self.extend_from_slice(tail);
+
+        match source.ext() {
+            Some(ext) => drop(self.set_ext(ext.clone())),
+            None => drop(self.take_ext()),
+        }
     }
 }
@@ -0,0 +1,220 @@
+use crate::MiniVec;
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// `ConcurrentMiniVec` is an append-only [`MiniVec`](crate::MiniVec) which many threads can push
+/// into at once, up to the capacity it was created with.
+///
+/// A push claims its slot with a single `fetch_add` and never waits on other threads, failing
+/// instead once the capacity is exhausted. The length stored in the header of the allocation is
+/// updated atomically and covers the published prefix: the elements readers can see through
+/// `Deref`. An element becomes part of that prefix once every slot before it has been written
+/// too, so readers never observe a gap.
+///
+/// Once the writers are done, [`into_inner`](ConcurrentMiniVec::into_inner) hands back an
+/// ordinary `MiniVec` holding every pushed element.
+///
+/// # Example
+///
+/// ```
+/// use minivec::{ConcurrentMiniVec, MiniVec};
+///
+/// let results = ConcurrentMiniVec::with_capacity(64);
+///
+/// std::thread::scope(|s| {
+///     for i in 0..4 {
+///         let results = &results;
+///         s.spawn(move || {
+///             for j in 0..16 {
+///                 results.push_within_capacity(i * 16 + j).unwrap();
+///             }
+///         });
+///     }
+/// });
+///
+/// assert!(results.push_within_capacity(64).is_err());
+///
+/// let mut results: MiniVec<i32> = results.into_inner();
+/// results.sort_unstable();
+/// assert!(results.iter().copied().eq(0..64));
+/// ```
+///
+pub struct ConcurrentMiniVec<T> {
+    vec: MiniVec<T>,
+    reserved: AtomicUsize,
+    written: AtomicUsize,
+}
+
+impl<T> ConcurrentMiniVec<T> {
+    // the published length lives in the header of the allocation. Unallocated vectors share a
+    // read-only header and zero-sized types don't have one at all, neither of which needs it:
+    // the former never publish anything and the latter have no elements to wait on
+    //
+    fn published(&self) -> Option<&AtomicUsize> {
+        if core::mem::size_of::<T>() == 0 || !self.vec.is_allocated() {
+            return None;
+        }
+
+        #[allow(clippy::cast_ptr_alignment)]
+        Some(unsafe { &*self.vec.buf.as_ptr().cast::<AtomicUsize>() })
+    }
+
+    /// `capacity` returns the number of elements the vector can hold, which is also the maximum
+    /// number of successful pushes.
+    ///
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// `into_inner` returns the underlying [`MiniVec`](crate::MiniVec), containing every element
+    /// that was pushed.
+    ///
+    #[must_use]
+    pub fn into_inner(self) -> MiniVec<T> {
+        let mut this = core::mem::ManuallyDrop::new(self);
+        let len = *this.written.get_mut();
+
+        let mut vec = unsafe { core::ptr::read(core::ptr::addr_of!(this.vec)) };
+        unsafe { vec.set_len(len) };
+        vec
+    }
+
+    /// `is_empty` returns whether or not the published prefix is empty.
+    ///
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `len` returns the length of the published prefix.
+    ///
+    /// Note: elements that were pushed while other threads were still writing to earlier slots
+    /// may not be counted yet.
+    ///
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self.published() {
+            Some(len) => len.load(Ordering::Acquire),
+            None => self.written.load(Ordering::Acquire),
+        }
+    }
+
+    /// `push_within_capacity` appends `value` to the vector without ever reallocating or waiting
+    /// on other threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns `value` back if the vector is already full.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::ConcurrentMiniVec::with_capacity(1);
+    ///
+    /// assert_eq!(vec.push_within_capacity(1), Ok(()));
+    /// assert_eq!(vec.push_within_capacity(2), Err(2));
+    ///
+    /// assert_eq!(*vec, [1]);
+    /// ```
+    ///
+    pub fn push_within_capacity(&self, value: T) -> Result<(), T> {
+        let capacity = self.capacity();
+
+        // checking first keeps the number of reservations from growing unbounded once full
+        //
+        if self.reserved.load(Ordering::Relaxed) >= capacity {
+            return Err(value);
+        }
+
+        let idx = self.reserved.fetch_add(1, Ordering::SeqCst);
+        if idx >= capacity {
+            return Err(value);
+        }
+
+        unsafe { core::ptr::write(self.vec.data().add(idx), value) };
+
+        // every reservation below the capacity is eventually written so, should the number of
+        // written slots match them, there's no gap below it and the prefix can be published.
+        // Whoever writes the last outstanding slot observes this, so once the writers are done
+        // everything is published
+        //
+        let written = self.written.fetch_add(1, Ordering::SeqCst) + 1;
+        let reserved = core::cmp::min(self.reserved.load(Ordering::SeqCst), capacity);
+
+        if written == reserved {
+            if let Some(len) = self.published() {
+                len.fetch_max(written, Ordering::Release);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `with_capacity` constructs an empty `ConcurrentMiniVec` with room for `capacity`
+    /// elements.
+    ///
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> ConcurrentMiniVec<T> {
+        ConcurrentMiniVec::from(MiniVec::with_capacity(capacity))
+    }
+}
+
+impl<T> Drop for ConcurrentMiniVec<T> {
+    fn drop(&mut self) {
+        let len = *self.written.get_mut();
+        unsafe { self.vec.set_len(len) };
+    }
+}
+
+impl<T> core::ops::Deref for ConcurrentMiniVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.vec.data(), self.len()) }
+    }
+}
+
+impl<T> AsRef<[T]> for ConcurrentMiniVec<T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for ConcurrentMiniVec<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let this: &[T] = self;
+
+        this.fmt(f)
+    }
+}
+
+impl<T> Default for ConcurrentMiniVec<T> {
+    fn default() -> Self {
+        ConcurrentMiniVec::from(MiniVec::new())
+    }
+}
+
+impl<T> From<MiniVec<T>> for ConcurrentMiniVec<T> {
+    fn from(vec: MiniVec<T>) -> Self {
+        let mut vec = vec;
+        vec.unshare();
+
+        let len = vec.len();
+
+        ConcurrentMiniVec {
+            vec,
+            reserved: AtomicUsize::new(len),
+            written: AtomicUsize::new(len),
+        }
+    }
+}
+
+impl<T> From<ConcurrentMiniVec<T>> for MiniVec<T> {
+    fn from(vec: ConcurrentMiniVec<T>) -> Self {
+        vec.into_inner()
+    }
+}
+
+unsafe impl<T: Send> Send for ConcurrentMiniVec<T> {}
+unsafe impl<T: Send + Sync> Sync for ConcurrentMiniVec<T> {}
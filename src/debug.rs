@@ -1,8 +1,9 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T: core::fmt::Debug> core::fmt::Debug for MiniVec<T> {
+impl<T: core::fmt::Debug, A: Allocator, H> core::fmt::Debug for MiniVec<T, A, H> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let this: &[T] = &*self;
+        let this: &[T] = self;
 
         this.fmt(f)
     }
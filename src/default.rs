@@ -1,7 +1,8 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T> Default for MiniVec<T> {
+impl<T, A: Allocator + Default, H> Default for MiniVec<T, A, H> {
     fn default() -> Self {
-        Self::new()
+        Self::empty_in(A::default())
     }
 }
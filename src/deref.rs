@@ -1,29 +1,22 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T> core::ops::Deref for MiniVec<T> {
+impl<T, A: Allocator, H> core::ops::Deref for MiniVec<T, A, H> {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
-        if self.buf.is_null() {
-            return &[];
-        }
-
-        let header = self.header();
         let data = self.data();
-        let len = header.len;
+        let len = self.len();
         unsafe { core::slice::from_raw_parts(data, len) }
     }
 }
 
-impl<T> core::ops::DerefMut for MiniVec<T> {
+impl<T, A: Allocator, H> core::ops::DerefMut for MiniVec<T, A, H> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        if self.buf.is_null() {
-            return &mut [];
-        }
+        self.unshare();
 
-        let header = self.header();
         let data = self.data();
-        let len = header.len;
+        let len = self.len();
         unsafe { core::slice::from_raw_parts_mut(data, len) }
     }
 }
@@ -1,22 +1,35 @@
+use crate::layout_alignment_of;
 use crate::make_layout;
+use crate::Allocator;
 use crate::Header;
 use crate::MiniVec;
 
-extern crate alloc;
-
-impl<T> Drop for MiniVec<T> {
+impl<T, A: Allocator, H> Drop for MiniVec<T, A, H> {
     fn drop(&mut self) {
-        if self.buf.is_null() {
+        // static vectors only hold `Copy` elements and don't own their memory
+        //
+        if self.is_static() {
             return;
         }
 
-        #[allow(clippy::cast_ptr_alignment)]
-        let header = unsafe { core::ptr::read(self.buf as *const Header) };
+        unsafe { core::ptr::drop_in_place(self.as_mut_slice()) };
 
-        for i in 0..header.len {
-            unsafe { core::ptr::read(self.data().add(i)) };
+        if !self.is_allocated() {
+            return;
         }
 
-        unsafe { alloc::alloc::dealloc(self.buf, make_layout::<T>(header.cap, header.alignment)) };
+        if core::mem::size_of::<H>() > 0 {
+            unsafe { core::ptr::drop_in_place(self.ext_ptr()) };
+        }
+
+        #[allow(clippy::cast_ptr_alignment)]
+        let header = unsafe { core::ptr::read(self.buf.cast::<Header>().as_ptr()) };
+
+        unsafe {
+            self.allocator.deallocate(
+                self.buf,
+                make_layout::<T, H>(header.cap, layout_alignment_of(header.alignment)),
+            );
+        };
     }
 }
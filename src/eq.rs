@@ -1,3 +1,4 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T> core::cmp::Eq for MiniVec<T> where T: core::cmp::Eq {}
+impl<T, A: Allocator, H> core::cmp::Eq for MiniVec<T, A, H> where T: core::cmp::Eq {}
@@ -1,6 +1,7 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<'a, T> core::iter::Extend<&'a T> for MiniVec<T>
+impl<'a, T, A: Allocator, H> core::iter::Extend<&'a T> for MiniVec<T, A, H>
 where
     T: 'a + core::marker::Copy,
 {
@@ -8,19 +9,15 @@ where
     where
         I: core::iter::IntoIterator<Item = &'a T>,
     {
-        for &x in iter {
-            self.push(x);
-        }
+        self.extend_iter(iter.into_iter().copied());
     }
 }
 
-impl<T> core::iter::Extend<T> for MiniVec<T> {
+impl<T, A: Allocator, H> core::iter::Extend<T> for MiniVec<T, A, H> {
     fn extend<I>(&mut self, iter: I)
     where
         I: core::iter::IntoIterator<Item = T>,
     {
-        for x in iter {
-            self.push(x);
-        }
+        self.extend_iter(iter.into_iter());
     }
 }
@@ -0,0 +1,146 @@
+use crate::r#impl::helpers::{capacity_in, data_offset, max_align, next_aligned};
+use crate::{write_header, AllocError, Allocator, Header, MiniVec};
+
+use core::alloc::Layout;
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+/// `FixedStorage` is the [`Allocator`](crate::Allocator) behind a `MiniVec` living in a
+/// caller-supplied buffer, see [`MiniVec::from_buffer`](crate::MiniVec::from_buffer).
+///
+/// It never requests memory from anywhere: growing and shrinking happen in place, within the
+/// bounds of the buffer, and every other request fails with an [`AllocError`](crate::AllocError).
+/// A vector growing into a full buffer reports a capacity overflow instead.
+/// As it's a zero-sized type, the vector is still only the size of a single pointer.
+///
+pub struct FixedStorage<'a> {
+    phantom: core::marker::PhantomData<&'a mut [MaybeUninit<u8>]>,
+}
+
+// the number of bytes available to the allocation, counting from its start, is stored in the
+// `usize` immediately preceding it so that the allocator itself doesn't need any state
+//
+#[allow(clippy::cast_ptr_alignment)]
+unsafe fn available_ptr(ptr: NonNull<u8>) -> *mut usize {
+    ptr.as_ptr().cast::<usize>().sub(1)
+}
+
+unsafe impl Allocator for FixedStorage<'_> {
+    fn allocate(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        Err(AllocError)
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert_eq!(old_layout.align(), new_layout.align());
+
+        if new_layout.size() > *available_ptr(ptr) {
+            return Err(AllocError);
+        }
+
+        Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()))
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        _old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()))
+    }
+
+    // a vector whose buffer couldn't even fit the header has no room at all
+    //
+    unsafe fn size_limit(&self, ptr: Option<NonNull<u8>>) -> Option<usize> {
+        Some(ptr.map_or(0, |ptr| *available_ptr(ptr)))
+    }
+}
+
+impl<'a, T> MiniVec<T, FixedStorage<'a>> {
+    /// `from_buffer` constructs an empty `MiniVec` which stores its header and elements in `buf`
+    /// instead of allocating, using the same layout as a regular `MiniVec`.
+    ///
+    /// The capacity is the largest one the buffer can fit once the header is accounted for.
+    /// Buffers too small to even hold the header produce an unallocated vector with a capacity of
+    /// 0. The vector never reallocates: it grows in place, at most up to the end of the buffer,
+    /// and any operation requiring more capacity than that fails with
+    /// [`TryReserveError::CapacityOverflow`](crate::TryReserveError::CapacityOverflow). As such,
+    /// the fallible [`try_push`](MiniVec::try_push) and [`try_reserve`](MiniVec::try_reserve)
+    /// should be preferred over their panicking counterparts. Operations that allocate a new
+    /// buffer always fail, which includes realigning unless the data already meets the new
+    /// alignment.
+    ///
+    /// Passing a `&'static mut` buffer yields a vector that can live for the rest of the program.
+    /// `from_buffer` doesn't depend on the `alloc` feature, making it usable on targets without a
+    /// heap.
+    ///
+    /// Note: the elements are dropped along with the vector but the buffer is never deallocated,
+    /// it remains owned by the caller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut buf = [MaybeUninit::<u8>::uninit(); 64];
+    /// let mut vec = minivec::MiniVec::<u32, _>::from_buffer(&mut buf);
+    ///
+    /// assert_eq!(core::mem::size_of_val(&vec), core::mem::size_of::<usize>());
+    ///
+    /// while vec.try_push(vec.len() as u32).is_ok() {}
+    /// assert_eq!(vec.len(), vec.capacity());
+    ///
+    /// vec.retain(|&x| x % 2 == 0);
+    /// assert!(vec.iter().all(|&x| x % 2 == 0));
+    /// ```
+    ///
+    #[must_use]
+    pub fn from_buffer(buf: &'a mut [MaybeUninit<u8>]) -> MiniVec<T, FixedStorage<'a>> {
+        let mut vec = MiniVec::new_in(FixedStorage {
+            phantom: core::marker::PhantomData,
+        });
+
+        if core::mem::size_of::<T>() == 0 {
+            return vec;
+        }
+
+        let alignment = max_align::<T>();
+        let header_size = data_offset::<()>(alignment);
+
+        let base = buf.as_mut_ptr().cast::<u8>();
+        let offset =
+            next_aligned(base as usize + core::mem::size_of::<usize>(), alignment) - base as usize;
+
+        let available = match buf.len().checked_sub(offset) {
+            Some(n) if n >= header_size => n,
+            _ => return vec,
+        };
+
+        let capacity = capacity_in::<T, ()>(available, alignment);
+
+        unsafe {
+            let ptr = NonNull::new_unchecked(base.add(offset));
+            available_ptr(ptr).write(available);
+
+            write_header::<()>(
+                ptr,
+                Header {
+                    len: 0,
+                    cap: capacity,
+                    alignment,
+                },
+            );
+
+            vec.buf = ptr;
+        }
+
+        vec
+    }
+}
@@ -1,52 +1,134 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-extern crate alloc;
-
+#[cfg(feature = "alloc")]
 impl<'a, T> core::convert::From<&'a [T]> for MiniVec<T>
 where
     T: Clone,
 {
     fn from(s: &'a [T]) -> Self {
         let mut v = MiniVec::with_capacity(s.len());
-        for x in s {
-            v.push(x.clone());
-        }
-
+        v.extend_cloned(s);
         v
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T> core::convert::From<&'a mut [T]> for MiniVec<T>
 where
     T: Clone,
 {
     fn from(s: &'a mut [T]) -> Self {
         let mut v = MiniVec::with_capacity(s.len());
-        for x in s {
-            v.push(x.clone());
+        v.extend_cloned(s);
+        v
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::convert::From<alloc::vec::Vec<T>> for MiniVec<T> {
+    fn from(v: alloc::vec::Vec<T>) -> Self {
+        let mut v = core::mem::ManuallyDrop::new(v);
+        let len = v.len();
+
+        let mut w = MiniVec::with_capacity(len);
+        unsafe {
+            core::ptr::copy_nonoverlapping(v.as_ptr(), w.data(), len);
+            w.set_len(len);
+
+            // the elements now belong to `w` so all that's left is to free the Vec's buffer
+            //
+            v.set_len(0);
+            core::mem::ManuallyDrop::drop(&mut v);
+        }
+
+        w
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, A: Allocator, H> core::convert::From<MiniVec<T, A, H>> for alloc::vec::Vec<T> {
+    fn from(v: MiniVec<T, A, H>) -> Self {
+        let mut v = v;
+        let len = v.len();
+
+        let mut w = alloc::vec::Vec::with_capacity(len);
+        unsafe {
+            core::ptr::copy_nonoverlapping(v.data(), w.as_mut_ptr(), len);
+            w.set_len(len);
+            v.forget_elements();
+        }
+
+        w
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::convert::From<alloc::boxed::Box<[T]>> for MiniVec<T> {
+    fn from(b: alloc::boxed::Box<[T]>) -> Self {
+        MiniVec::from(b.into_vec())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const N: usize> core::convert::From<[T; N]> for MiniVec<T> {
+    fn from(arr: [T; N]) -> Self {
+        let arr = core::mem::ManuallyDrop::new(arr);
+
+        let mut v = MiniVec::with_capacity(N);
+        unsafe {
+            core::ptr::copy_nonoverlapping(arr.as_ptr(), v.data(), N);
+            v.set_len(N);
         }
 
         v
     }
 }
 
+impl<T, A: Allocator, H, const N: usize> core::convert::TryFrom<MiniVec<T, A, H>> for [T; N] {
+    type Error = MiniVec<T, A, H>;
+
+    fn try_from(v: MiniVec<T, A, H>) -> Result<Self, Self::Error> {
+        if v.len() != N {
+            return Err(v);
+        }
+
+        let mut v = v;
+        unsafe {
+            let arr = core::ptr::read(v.data().cast::<[T; N]>());
+            v.forget_elements();
+            Ok(arr)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::convert::From<alloc::string::String> for MiniVec<u8> {
+    fn from(s: alloc::string::String) -> Self {
+        MiniVec::from(s.into_bytes())
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl<'a> core::convert::From<&'a str> for MiniVec<u8> {
     fn from(s: &'a str) -> Self {
         let mut v = MiniVec::with_capacity(s.len());
         unsafe {
             let new_len = s.len();
-            core::ptr::copy_nonoverlapping(s.as_ptr(), v.as_mut_ptr(), new_len);
+            core::ptr::copy_nonoverlapping(s.as_ptr(), v.data(), new_len);
             v.set_len(new_len);
         }
         v
     }
 }
 
-impl<'a, T> core::convert::From<&'a MiniVec<T>> for alloc::borrow::Cow<'a, [T]>
+#[cfg(feature = "alloc")]
+impl<'a, T, A: Allocator, H> core::convert::From<&'a MiniVec<T, A, H>>
+    for alloc::borrow::Cow<'a, [T]>
 where
     T: Clone,
 {
-    fn from(v: &'a MiniVec<T>) -> alloc::borrow::Cow<'a, [T]> {
+    fn from(v: &'a MiniVec<T, A, H>) -> alloc::borrow::Cow<'a, [T]> {
         alloc::borrow::Cow::Borrowed(v.as_slice())
     }
 }
@@ -1,15 +1,14 @@
 use crate::MiniVec;
 
-impl<A> core::iter::FromIterator<A> for MiniVec<A> {
-    fn from_iter<T>(iter: T) -> Self
+impl<T> core::iter::FromIterator<T> for MiniVec<T> {
+    fn from_iter<I>(iter: I) -> Self
     where
-        T: IntoIterator<Item = A>,
+        I: IntoIterator<Item = T>,
     {
-        let mut v = MiniVec::<A>::new();
-        let it = iter.into_iter();
-        for x in it {
-            v.push(x);
-        }
+        let iter = iter.into_iter();
+
+        let mut v = MiniVec::<T>::with_capacity(iter.size_hint().0);
+        v.extend_iter(iter);
         v
     }
 }
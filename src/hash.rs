@@ -1,6 +1,7 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T> core::hash::Hash for MiniVec<T>
+impl<T, A: Allocator, E> core::hash::Hash for MiniVec<T, A, E>
 where
     T: core::hash::Hash,
 {
@@ -8,7 +9,7 @@ where
     where
         H: core::hash::Hasher,
     {
-        let this: &[T] = &**self;
+        let this: &[T] = self;
         core::hash::Hash::hash(this, state);
     }
 }
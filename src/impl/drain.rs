@@ -1,34 +1,78 @@
-use crate::MiniVec;
-
-extern crate alloc;
-
-pub struct Drain<'a, T: 'a> {
-    vec_: core::ptr::NonNull<MiniVec<T>>,
-    drain_pos_: core::ptr::NonNull<T>,
-    drain_end_: core::ptr::NonNull<T>,
-    remaining_pos_: core::ptr::NonNull<T>,
+#[cfg(feature = "alloc")]
+use crate::Global;
+use crate::{Allocator, MiniVec};
+
+#[allow(clippy::struct_field_names)]
+pub struct Drain<
+    'a,
+    T: 'a,
+    #[cfg(feature = "alloc")] A: Allocator = Global,
+    #[cfg(not(feature = "alloc"))] A: Allocator,
+    H = (),
+> {
+    vec_: core::ptr::NonNull<MiniVec<T, A, H>>,
+    data_: *const T,
+    drain_pos_: usize,
+    drain_end_: usize,
+    remaining_pos_: usize,
     remaining_: usize,
     marker_: core::marker::PhantomData<&'a T>,
 }
 
-pub fn make_drain_iterator<'a, T>(
-    vec: &mut MiniVec<T>,
+pub fn make_drain_iterator<'a, T, A: Allocator, H>(
+    vec: &mut MiniVec<T, A, H>,
     data: *mut T,
     remaining: usize,
     start_idx: usize,
     end_idx: usize,
-) -> Drain<'a, T> {
+) -> Drain<'a, T, A, H> {
     Drain {
         vec_: core::ptr::NonNull::from(vec),
-        drain_pos_: unsafe { core::ptr::NonNull::new_unchecked(data.add(start_idx)) },
-        drain_end_: unsafe { core::ptr::NonNull::new_unchecked(data.add(end_idx)) },
-        remaining_pos_: unsafe { core::ptr::NonNull::new_unchecked(data.add(end_idx)) },
+        data_: data,
+        drain_pos_: start_idx,
+        drain_end_: end_idx,
+        remaining_pos_: end_idx,
         remaining_: remaining,
         marker_: core::marker::PhantomData,
     }
 }
 
-impl<T> Iterator for Drain<'_, T> {
+impl<T, A: Allocator, H> Drain<'_, T, A, H> {
+    /// `as_slice` returns the elements that have not yet been yielded by the iterator.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1, 2, 3, 4, 5];
+    ///
+    /// let mut drain = vec.drain(1..4);
+    /// drain.next();
+    ///
+    /// assert_eq!(drain.as_slice(), [3, 4]);
+    /// ```
+    ///
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            core::slice::from_raw_parts(
+                self.data_.add(self.drain_pos_),
+                self.drain_end_ - self.drain_pos_,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn consume(&mut self, amt: usize)
+    where
+        T: Copy,
+    {
+        debug_assert!(amt <= self.drain_end_ - self.drain_pos_);
+        self.drain_pos_ += amt;
+    }
+}
+
+impl<T, A: Allocator, H> Iterator for Drain<'_, T, A, H> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -36,61 +80,55 @@ impl<T> Iterator for Drain<'_, T> {
             return None;
         }
 
-        let p = self.drain_pos_.as_ptr();
-        let tmp = unsafe { core::ptr::read(p) };
-        self.drain_pos_ = unsafe { core::ptr::NonNull::new_unchecked(p.add(1)) };
+        let tmp = unsafe { core::ptr::read(self.data_.add(self.drain_pos_)) };
+        self.drain_pos_ += 1;
         Some(tmp)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = (self.drain_end_.as_ptr() as *const _ as usize
-            - self.drain_pos_.as_ptr() as *const _ as usize)
-            / core::mem::size_of::<T>();
+        let len = self.drain_end_ - self.drain_pos_;
 
         (len, Some(len))
     }
 }
 
-impl<T> ExactSizeIterator for Drain<'_, T> {}
+impl<T, A: Allocator, H> ExactSizeIterator for Drain<'_, T, A, H> {}
 
-impl<T> DoubleEndedIterator for Drain<'_, T> {
+impl<T, A: Allocator, H> DoubleEndedIterator for Drain<'_, T, A, H> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        let pos = unsafe { self.drain_end_.as_ptr().sub(1) };
-        if pos < self.drain_pos_.as_ptr() {
+        if self.drain_end_ <= self.drain_pos_ {
             return None;
         }
 
-        let tmp = unsafe { core::ptr::read(pos) };
-        self.drain_end_ = unsafe { core::ptr::NonNull::new_unchecked(pos) };
+        self.drain_end_ -= 1;
+        let tmp = unsafe { core::ptr::read(self.data_.add(self.drain_end_)) };
         Some(tmp)
     }
 }
 
-impl<T> Drop for Drain<'_, T> {
+impl<T, A: Allocator, H> Drop for Drain<'_, T, A, H> {
     fn drop(&mut self) {
-        struct DropGuard<'b, 'a, T> {
-            drain: &'b mut Drain<'a, T>,
-        };
+        struct DropGuard<'b, 'a, T, A: Allocator, H> {
+            drain: &'b mut Drain<'a, T, A, H>,
+        }
 
-        impl<'b, 'a, T> Drop for DropGuard<'b, 'a, T> {
+        impl<T, A: Allocator, H> Drop for DropGuard<'_, '_, T, A, H> {
             fn drop(&mut self) {
-                while let Some(_) = self.drain.next() {}
+                for _ in self.drain.by_ref() {}
 
                 if self.drain.remaining_ > 0 {
                     let v = unsafe { self.drain.vec_.as_mut() };
                     let v_len = v.len();
 
-                    let src = self.drain.remaining_pos_.as_ptr();
-                    let dst = unsafe { v.as_mut_ptr().add(v_len) };
-
-                    if src == dst {
-                        return;
+                    if self.drain.remaining_pos_ != v_len {
+                        unsafe {
+                            let src = self.drain.data_.add(self.drain.remaining_pos_);
+                            let dst = self.drain.data_.cast_mut().add(v_len);
+                            core::ptr::copy(src, dst, self.drain.remaining_);
+                        };
                     }
 
-                    unsafe {
-                        core::ptr::copy(src, dst, self.drain.remaining_);
-                        v.set_len(v_len + self.drain.remaining_);
-                    };
+                    unsafe { v.set_len(v_len + self.drain.remaining_) };
                 }
             }
         }
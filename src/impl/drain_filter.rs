@@ -1,8 +1,18 @@
-pub struct DrainFilter<'a, T, F>
-where
+#[cfg(feature = "alloc")]
+use crate::Global;
+use crate::{Allocator, MiniVec};
+
+pub struct DrainFilter<
+    'a,
+    T,
+    F,
+    #[cfg(feature = "alloc")] A: Allocator = Global,
+    #[cfg(not(feature = "alloc"))] A: Allocator,
+    H = (),
+> where
     F: core::ops::FnMut(&mut T) -> bool,
 {
-    vec: &'a mut crate::MiniVec<T>,
+    vec: &'a mut MiniVec<T, A, H>,
     pred: F,
     old_len: usize,
     new_len: usize,
@@ -10,10 +20,10 @@ where
     panicked: bool,
 }
 
-pub fn make_drain_filter_iterator<T, F>(
-    vec: &mut crate::MiniVec<T>,
+pub fn make_drain_filter_iterator<T, F, A: Allocator, H>(
+    vec: &mut MiniVec<T, A, H>,
     pred: F,
-) -> DrainFilter<'_, T, F>
+) -> DrainFilter<'_, T, F, A, H>
 where
     F: core::ops::FnMut(&mut T) -> bool,
 {
@@ -28,7 +38,7 @@ where
     }
 }
 
-impl<T, F> core::iter::Iterator for DrainFilter<'_, T, F>
+impl<T, F, A: Allocator, H> core::iter::Iterator for DrainFilter<'_, T, F, A, H>
 where
     F: core::ops::FnMut(&mut T) -> bool,
 {
@@ -37,21 +47,21 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         while self.pos < self.old_len {
             let data = self.vec.data();
-            let mut val = unsafe { &mut *data.add(self.pos) };
+            let val = unsafe { &mut *data.add(self.pos) };
 
             self.panicked = true;
 
-            let pred_result = (self.pred)(&mut val);
+            let pred_result = (self.pred)(val);
 
             self.panicked = false;
 
             if pred_result {
                 self.pos += 1;
-                return Some(unsafe { core::ptr::read(val as *mut T) });
+                return Some(unsafe { core::ptr::read(core::ptr::from_mut::<T>(val)) });
             }
 
             if self.pos > self.new_len {
-                let src = val as *mut T;
+                let src = core::ptr::from_mut::<T>(val);
                 let dst = unsafe { data.add(self.new_len) };
                 unsafe { core::ptr::copy_nonoverlapping(src, dst, 1) };
             }
@@ -68,14 +78,14 @@ where
     }
 }
 
-struct DropGuard<'a, 'b, T, F>
+struct DropGuard<'a, 'b, T, F, A: Allocator, H>
 where
     F: core::ops::FnMut(&mut T) -> bool,
 {
-    drain: &'b mut DrainFilter<'a, T, F>,
+    drain: &'b mut DrainFilter<'a, T, F, A, H>,
 }
 
-impl<'a, 'b, T, F> Drop for DropGuard<'a, 'b, T, F>
+impl<T, F, A: Allocator, H> Drop for DropGuard<'_, '_, T, F, A, H>
 where
     F: core::ops::FnMut(&mut T) -> bool,
 {
@@ -100,7 +110,7 @@ where
     }
 }
 
-impl<T, F> Drop for DrainFilter<'_, T, F>
+impl<T, F, A: Allocator, H> Drop for DrainFilter<'_, T, F, A, H>
 where
     F: core::ops::FnMut(&mut T) -> bool,
 {
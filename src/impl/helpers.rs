@@ -1,13 +1,24 @@
 use crate::Header;
+use crate::LayoutErr;
+use crate::TryReserveError;
 
-extern crate alloc;
-
+// alignments are always powers of two so rounding up is a matter of masking, which keeps a
+// division out of every access to the data of a vector
+//
 pub const fn next_aligned(n: usize, alignment: usize) -> usize {
-    let remaining = n % alignment;
-    if remaining == 0 {
-        n
-    } else {
-        n + (alignment - remaining)
+    debug_assert!(alignment.is_power_of_two());
+
+    let mask = alignment - 1;
+    (n + mask) & !mask
+}
+
+pub const fn checked_next_aligned(n: usize, alignment: usize) -> Option<usize> {
+    debug_assert!(alignment.is_power_of_two());
+
+    let mask = alignment - 1;
+    match n.checked_add(mask) {
+        Some(n) => Some(n & !mask),
+        None => None,
     }
 }
 
@@ -22,7 +33,28 @@ pub const fn next_capacity<T>(capacity: usize) -> usize {
         };
     }
 
-    2 * capacity
+    capacity.saturating_mul(2)
+}
+
+// the data of a default-aligned vector starts right after the 24 byte header so vectorized
+// writes into it would straddle every 16 byte boundary. `unaligned_head` is the number of elements
+// to write one by one before `dst` reaches a cache line boundary, capped at `n`, or 0 if it never
+// does
+//
+pub fn unaligned_head<T>(dst: *const T, n: usize) -> usize {
+    const LINE: usize = 64;
+
+    let elem_size = core::mem::size_of::<T>();
+    if elem_size == 0 || LINE % elem_size != 0 {
+        return 0;
+    }
+
+    let gap = (LINE - dst as usize % LINE) % LINE;
+    if gap % elem_size != 0 {
+        return 0;
+    }
+
+    core::cmp::min(gap / elem_size, n)
 }
 
 pub fn max_align<T>() -> usize {
@@ -31,16 +63,92 @@ pub fn max_align<T>() -> usize {
     core::cmp::max(align_t, header_align)
 }
 
-pub fn make_layout<T>(capacity: usize, alignment: usize) -> alloc::alloc::Layout {
-    let header_size = core::mem::size_of::<Header>();
+pub fn min_alignment<T, H>() -> usize {
+    core::cmp::max(max_align::<T>(), core::mem::align_of::<Option<H>>())
+}
+
+pub fn validate_alignment<T, H>(alignment: usize) -> Result<(), LayoutErr> {
+    if !alignment.is_power_of_two() {
+        return Err(LayoutErr::AlignmentNotPowerOfTwo);
+    }
+
+    if alignment < min_alignment::<T, H>() {
+        return Err(LayoutErr::AlignmentTooSmall);
+    }
+
+    // the header is padded up to the alignment so a large enough one leaves no room for it
+    //
+    if try_make_layout::<T, H>(0, alignment).is_err() {
+        return Err(LayoutErr::AlignmentTooLarge);
+    }
+
+    Ok(())
+}
+
+// a non-zero-sized header extension `H` is stored as an `Option<H>` directly after the header,
+// followed by the copy of the alignment that precedes the data. Zero-sized extensions, such as the
+// default of `()`, take up no space at all
+//
+pub fn ext_offset<H>() -> usize {
+    next_aligned(
+        core::mem::size_of::<Header>(),
+        core::mem::align_of::<Option<H>>(),
+    )
+}
+
+pub fn data_offset<H>(alignment: usize) -> usize {
+    if core::mem::size_of::<H>() == 0 {
+        return next_aligned(core::mem::size_of::<Header>(), alignment);
+    }
+
+    let ext_end = ext_offset::<H>() + core::mem::size_of::<Option<H>>();
+    next_aligned(ext_end + core::mem::size_of::<usize>(), alignment)
+}
+
+// the largest capacity whose layout, as computed by `try_make_layout`, spans at most `bytes`
+//
+pub fn capacity_in<T, H>(bytes: usize, alignment: usize) -> usize {
+    let elem_size = core::mem::size_of::<T>();
+    debug_assert!(elem_size > 0);
+
+    let max_bytes = bytes.saturating_sub(data_offset::<H>(alignment)) & !(alignment - 1);
+    max_bytes / elem_size
+}
+
+// the largest capacity `try_make_layout` accepts, i.e. the one that keeps the size of the
+// allocation within `isize::MAX` bytes
+//
+// zero-sized types never allocate but store their length plus one in place of the pointer
+//
+pub fn max_capacity<T, H>(alignment: usize) -> usize {
+    if core::mem::size_of::<T>() == 0 {
+        return usize::MAX - 1;
+    }
+
+    capacity_in::<T, H>(isize::MAX as usize, alignment)
+}
+
+pub fn make_layout<T, H>(capacity: usize, alignment: usize) -> core::alloc::Layout {
+    try_make_layout::<T, H>(capacity, alignment).unwrap_or_else(|_| panic!("capacity overflow"))
+}
+
+pub fn try_make_layout<T, H>(
+    capacity: usize,
+    alignment: usize,
+) -> Result<core::alloc::Layout, TryReserveError> {
+    let header_size = data_offset::<H>(alignment);
     let num_bytes = if capacity == 0 {
-        next_aligned(header_size, alignment)
+        Some(header_size)
     } else {
-        next_aligned(header_size, alignment)
-            + next_aligned(capacity * core::mem::size_of::<T>(), alignment)
+        capacity
+            .checked_mul(core::mem::size_of::<T>())
+            .and_then(|n| checked_next_aligned(n, alignment))
+            .and_then(|n| n.checked_add(header_size))
     };
 
-    alloc::alloc::Layout::from_size_align(num_bytes, alignment).unwrap()
+    num_bytes
+        .and_then(|n| core::alloc::Layout::from_size_align(n, alignment).ok())
+        .ok_or(TryReserveError::CapacityOverflow)
 }
 
 #[cfg(test)]
@@ -57,6 +165,76 @@ mod tests {
         assert_eq!(next_aligned(16, 512), 512);
     }
 
+    #[test]
+    fn max_capacity_test() {
+        assert_eq!(max_capacity::<(), ()>(max_align::<()>()), usize::MAX - 1);
+
+        let alignment = max_align::<u64>();
+        let max = max_capacity::<u64, ()>(alignment);
+
+        assert!(try_make_layout::<u64, ()>(max, alignment).is_ok());
+        assert_eq!(
+            try_make_layout::<u64, ()>(max + 1, alignment),
+            Err(TryReserveError::CapacityOverflow)
+        );
+
+        let max = max_capacity::<[u8; 3], ()>(64);
+        assert!(try_make_layout::<[u8; 3], ()>(max, 64).is_ok());
+        assert!(try_make_layout::<[u8; 3], ()>(max + 1, 64).is_err());
+
+        assert_eq!(next_capacity::<u64>(usize::MAX / 2 + 1), usize::MAX);
+    }
+
+    #[test]
+    fn unaligned_head_test() {
+        assert_eq!(unaligned_head(64 as *const u64, 100), 0);
+        assert_eq!(unaligned_head(40 as *const u64, 100), 3);
+        assert_eq!(unaligned_head(40 as *const u64, 2), 2);
+        assert_eq!(unaligned_head(41 as *const u8, 100), 23);
+        assert_eq!(unaligned_head(8 as *const [u8; 3], 100), 0);
+        assert_eq!(unaligned_head(4 as *const u64, 100), 0);
+        assert_eq!(unaligned_head(8 as *const (), 100), 0);
+    }
+
+    #[test]
+    fn checked_next_aligned_test() {
+        assert_eq!(checked_next_aligned(9, 4), Some(12));
+        assert_eq!(checked_next_aligned(12, 4), Some(12));
+        assert_eq!(checked_next_aligned(usize::MAX, 1), Some(usize::MAX));
+        assert_eq!(checked_next_aligned(usize::MAX - 2, 8), None);
+    }
+
+    #[test]
+    fn validate_alignment_test() {
+        assert_eq!(validate_alignment::<u8, ()>(max_align::<u8>()), Ok(()));
+        assert_eq!(validate_alignment::<u8, ()>(64), Ok(()));
+        assert_eq!(
+            validate_alignment::<u8, ()>(2),
+            Err(LayoutErr::AlignmentTooSmall)
+        );
+        assert_eq!(
+            validate_alignment::<u8, ()>(65),
+            Err(LayoutErr::AlignmentNotPowerOfTwo)
+        );
+        assert_eq!(
+            validate_alignment::<u8, ()>(24),
+            Err(LayoutErr::AlignmentNotPowerOfTwo)
+        );
+        assert_eq!(
+            validate_alignment::<u8, ()>(6),
+            Err(LayoutErr::AlignmentNotPowerOfTwo)
+        );
+        assert_eq!(
+            validate_alignment::<u8, ()>(0),
+            Err(LayoutErr::AlignmentNotPowerOfTwo)
+        );
+        assert_eq!(
+            validate_alignment::<u8, ()>(1 << (usize::BITS - 1)),
+            Err(LayoutErr::AlignmentTooLarge)
+        );
+        assert_eq!(validate_alignment::<u8, ()>(1 << (usize::BITS - 2)), Ok(()));
+    }
+
     #[repr(align(512))]
     struct OverAligned {
         _data: [u8; 512],
@@ -83,14 +261,14 @@ mod tests {
     fn make_layout_test() {
         // empty
         //
-        let layout = make_layout::<i32>(0, max_align::<i32>());
+        let layout = make_layout::<i32, ()>(0, max_align::<i32>());
 
         assert_eq!(layout.align(), core::mem::align_of::<Header>());
         assert_eq!(layout.size(), core::mem::size_of::<Header>());
 
         // non-empty, less than
         //
-        let layout = make_layout::<i32>(512, max_align::<i32>());
+        let layout = make_layout::<i32, ()>(512, max_align::<i32>());
         assert!(core::mem::align_of::<i32>() < core::mem::align_of::<Header>());
         assert_eq!(layout.align(), core::mem::align_of::<Header>());
         assert_eq!(
@@ -100,7 +278,7 @@ mod tests {
 
         // non-empty, equal
         //
-        let layout = make_layout::<i64>(512, max_align::<i64>());
+        let layout = make_layout::<i64, ()>(512, max_align::<i64>());
         assert_eq!(
             core::mem::align_of::<i64>(),
             core::mem::align_of::<Header>()
@@ -112,7 +290,7 @@ mod tests {
         );
 
         // non-empty, greater
-        let layout = make_layout::<OverAligned>(512, max_align::<OverAligned>());
+        let layout = make_layout::<OverAligned, ()>(512, max_align::<OverAligned>());
         assert!(core::mem::align_of::<OverAligned>() > core::mem::align_of::<Header>());
         assert_eq!(layout.align(), core::mem::align_of::<OverAligned>());
         assert_eq!(
@@ -124,7 +302,7 @@ mod tests {
         );
 
         // non-empty, over-aligned
-        let layout = make_layout::<i32>(512, 32);
+        let layout = make_layout::<i32, ()>(512, 32);
         assert_eq!(layout.align(), 32);
         assert_eq!(
             layout.size(),
@@ -132,4 +310,50 @@ mod tests {
                 + next_aligned(core::mem::size_of::<i32>() * 512, 32)
         );
     }
+
+    #[test]
+    fn data_offset_test() {
+        let header_size = core::mem::size_of::<Header>();
+        let word = core::mem::size_of::<usize>();
+
+        assert_eq!(data_offset::<()>(word), header_size);
+        assert_eq!(data_offset::<()>(64), 64);
+
+        // the extension is followed by a copy of the alignment, right before the data
+        //
+        assert_eq!(ext_offset::<u8>(), header_size);
+        assert_eq!(data_offset::<u8>(word), header_size + 2 * word);
+        assert_eq!(data_offset::<u8>(64), 64);
+
+        let align = core::mem::align_of::<OverAligned>();
+        assert_eq!(
+            ext_offset::<OverAligned>(),
+            next_aligned(header_size, align)
+        );
+        assert_eq!(
+            data_offset::<OverAligned>(align),
+            next_aligned(
+                ext_offset::<OverAligned>() + core::mem::size_of::<Option<OverAligned>>() + word,
+                align
+            )
+        );
+    }
+
+    #[test]
+    fn try_make_layout_test() {
+        assert_eq!(
+            try_make_layout::<i32, ()>(512, 32).unwrap(),
+            make_layout::<i32, ()>(512, 32)
+        );
+
+        assert_eq!(
+            try_make_layout::<u16, ()>(usize::MAX / 2 + 1, max_align::<u16>()),
+            Err(TryReserveError::CapacityOverflow)
+        );
+
+        assert_eq!(
+            try_make_layout::<u8, ()>(isize::MAX as usize, max_align::<u8>()),
+            Err(TryReserveError::CapacityOverflow)
+        );
+    }
 }
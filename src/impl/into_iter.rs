@@ -1,4 +1,6 @@
-extern crate alloc;
+#[cfg(feature = "alloc")]
+use crate::Global;
+use crate::{Allocator, MiniVec};
 
 // we diverge pretty heavily from the stdlib here
 //
@@ -6,21 +8,22 @@ extern crate alloc;
 // making it a data member of the struct and then manually adjusting things in
 // the Header of the MiniVec
 //
-pub struct IntoIter<T> {
-    v: crate::MiniVec<T>,
+pub struct IntoIter<
+    T,
+    #[cfg(feature = "alloc")] A: Allocator = Global,
+    #[cfg(not(feature = "alloc"))] A: Allocator,
+    H = (),
+> {
+    v: MiniVec<T, A, H>,
     pos: *const T,
     marker: core::marker::PhantomData<T>,
 }
 
-impl<T> IntoIter<T> {
+impl<T, A: Allocator, H> IntoIter<T, A, H> {
     #[must_use]
-    pub fn new(w: crate::MiniVec<T>) -> Self {
-        let v = w;
-        let pos = if v.buf.is_null() {
-            core::ptr::null_mut()
-        } else {
-            v.data()
-        };
+    pub fn new(w: MiniVec<T, A, H>) -> Self {
+        let mut v = w;
+        let pos = v.as_mut_ptr();
 
         Self {
             v,
@@ -31,7 +34,7 @@ impl<T> IntoIter<T> {
 
     #[must_use]
     pub fn as_slice(&self) -> &[T] {
-        if self.v.buf.is_null() {
+        if self.v.is_empty() {
             &[]
         } else {
             let data = self.pos;
@@ -40,70 +43,70 @@ impl<T> IntoIter<T> {
     }
 
     pub fn as_mut_slice(&mut self) -> &mut [T] {
-        if self.v.buf.is_null() {
+        if self.v.is_empty() {
             &mut []
         } else {
-            let data: *mut T = self.pos as *mut T;
+            let data: *mut T = self.pos.cast_mut();
             unsafe { core::slice::from_raw_parts_mut(data, self.v.len()) }
         }
     }
 }
 
-impl<T> AsRef<[T]> for IntoIter<T> {
+#[cfg(feature = "std")]
+impl<T: Copy, A: Allocator, H> IntoIter<T, A, H> {
+    pub(crate) fn consume(&mut self, amt: usize) {
+        let len = self.v.len();
+        debug_assert!(amt <= len);
+
+        self.pos = unsafe { self.pos.add(amt) };
+        unsafe { self.v.set_len(len - amt) };
+    }
+}
+
+impl<T, A: Allocator, H> AsRef<[T]> for IntoIter<T, A, H> {
     fn as_ref(&self) -> &[T] {
         self.as_slice()
     }
 }
 
-impl<T: Clone> Clone for IntoIter<T> {
-    fn clone(&self) -> IntoIter<T> {
-        let w = self.v.clone();
-        let pos_cpy = self.pos;
-        IntoIter {
-            v: w,
-            pos: pos_cpy,
-            marker: core::marker::PhantomData,
-        }
+impl<T: Clone, A: Allocator + Clone, H: Clone> Clone for IntoIter<T, A, H> {
+    fn clone(&self) -> IntoIter<T, A, H> {
+        let mut w = MiniVec::empty_in(self.v.allocator().clone());
+        w.grow(self.v.len(), self.v.alignment());
+        w.clone_ext_from(&self.v);
+        w.extend_from_slice(self.as_slice());
+        IntoIter::new(w)
     }
 }
 
-impl<T: alloc::fmt::Debug> alloc::fmt::Debug for IntoIter<T> {
-    fn fmt(&self, f: &mut alloc::fmt::Formatter<'_>) -> alloc::fmt::Result {
+impl<T: core::fmt::Debug, A: Allocator, H> core::fmt::Debug for IntoIter<T, A, H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_tuple("MiniVec::IntoIter")
             .field(&self.as_slice())
             .finish()
     }
 }
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T, A: Allocator, H> DoubleEndedIterator for IntoIter<T, A, H> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.v.buf.is_null() {
+        let len = self.v.len();
+        if len == 0 {
             return None;
         }
 
-        let header = self.v.header_mut();
+        unsafe { self.v.set_len(len - 1) };
 
-        let data = self.pos;
-        let count = header.len;
-        let end = unsafe { data.add(count) };
-
-        if data >= end {
-            return None;
-        };
-
-        header.len -= 1;
-
-        Some(unsafe { core::ptr::read(data.add(header.len)) })
+        Some(unsafe { core::ptr::read(self.pos.add(len - 1)) })
     }
 }
 
-impl<T> Drop for IntoIter<T> {
+impl<T, A: Allocator, H> Drop for IntoIter<T, A, H> {
     fn drop(&mut self) {
         for _ in self {}
     }
 }
 
-impl<T> ExactSizeIterator for IntoIter<T> {
+impl<T, A: Allocator, H> ExactSizeIterator for IntoIter<T, A, H> {
     fn len(&self) -> usize {
         self.v.len()
     }
@@ -113,28 +116,21 @@ impl<T> ExactSizeIterator for IntoIter<T> {
     // }
 }
 
-impl<T> core::iter::FusedIterator for IntoIter<T> {}
+impl<T, A: Allocator, H> core::iter::FusedIterator for IntoIter<T, A, H> {}
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator, H> Iterator for IntoIter<T, A, H> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.v.buf.is_null() {
+        let len = self.v.len();
+        if len == 0 {
             return None;
         }
 
-        let header = self.v.header_mut();
-
         let data = self.pos;
-        let count = header.len;
-        let end = unsafe { data.add(count) };
-
-        if data >= end {
-            return None;
-        }
 
         self.pos = unsafe { data.add(1) };
-        header.len -= 1;
+        unsafe { self.v.set_len(len - 1) };
 
         Some(unsafe { core::ptr::read(data) })
     }
@@ -145,5 +141,5 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
-unsafe impl<T: Send> Send for IntoIter<T> {}
-unsafe impl<T: Sync> Sync for IntoIter<T> {}
+unsafe impl<T: Send, A: Allocator + Send, H: Send> Send for IntoIter<T, A, H> {}
+unsafe impl<T: Sync, A: Allocator + Sync, H: Sync> Sync for IntoIter<T, A, H> {}
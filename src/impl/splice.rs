@@ -1,40 +1,48 @@
-use crate::MiniVec;
+#[cfg(feature = "alloc")]
+use crate::Global;
+use crate::{Allocator, MiniVec};
 
-extern crate alloc;
-
-pub struct Splice<'a, I>
-where
+#[allow(clippy::struct_field_names)]
+pub struct Splice<
+    'a,
+    I,
+    #[cfg(feature = "alloc")] A: Allocator = Global,
+    #[cfg(not(feature = "alloc"))] A: Allocator,
+    H = (),
+> where
     I: 'a + Iterator,
 {
-    vec_: core::ptr::NonNull<MiniVec<I::Item>>,
-    drain_pos_: core::ptr::NonNull<I::Item>,
-    drain_end_: core::ptr::NonNull<I::Item>,
-    remaining_pos_: core::ptr::NonNull<I::Item>,
+    vec_: core::ptr::NonNull<MiniVec<I::Item, A, H>>,
+    data_: *const I::Item,
+    drain_pos_: usize,
+    drain_end_: usize,
+    remaining_pos_: usize,
     remaining_: usize,
     marker_: core::marker::PhantomData<&'a I::Item>,
     fill_: I,
 }
 
-pub fn make_splice_iterator<'a, I: 'a + Iterator>(
-    vec: &mut MiniVec<I::Item>,
+pub fn make_splice_iterator<'a, I: 'a + Iterator, A: Allocator, H>(
+    vec: &mut MiniVec<I::Item, A, H>,
     data: *mut I::Item,
     remaining: usize,
     start_idx: usize,
     end_idx: usize,
     fill: I,
-) -> Splice<'a, I> {
+) -> Splice<'a, I, A, H> {
     Splice {
         vec_: core::ptr::NonNull::from(vec),
-        drain_pos_: unsafe { core::ptr::NonNull::new_unchecked(data.add(start_idx)) },
-        drain_end_: unsafe { core::ptr::NonNull::new_unchecked(data.add(end_idx)) },
-        remaining_pos_: unsafe { core::ptr::NonNull::new_unchecked(data.add(end_idx)) },
+        data_: data,
+        drain_pos_: start_idx,
+        drain_end_: end_idx,
+        remaining_pos_: end_idx,
         remaining_: remaining,
         marker_: core::marker::PhantomData,
         fill_: fill,
     }
 }
 
-impl<I> Iterator for Splice<'_, I>
+impl<I, A: Allocator, H> Iterator for Splice<'_, I, A, H>
 where
     I: Iterator,
 {
@@ -45,49 +53,45 @@ where
             return None;
         }
 
-        let p = self.drain_pos_.as_ptr();
-        let tmp = unsafe { core::ptr::read(p) };
-        self.drain_pos_ = unsafe { core::ptr::NonNull::new_unchecked(p.add(1)) };
+        let tmp = unsafe { core::ptr::read(self.data_.add(self.drain_pos_)) };
+        self.drain_pos_ += 1;
         Some(tmp)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = (self.drain_end_.as_ptr() as *const _ as usize
-            - self.drain_pos_.as_ptr() as *const _ as usize)
-            / core::mem::size_of::<I::Item>();
+        let len = self.drain_end_ - self.drain_pos_;
 
         (len, Some(len))
     }
 }
 
-impl<I: Iterator> ExactSizeIterator for Splice<'_, I> {}
+impl<I: Iterator, A: Allocator, H> ExactSizeIterator for Splice<'_, I, A, H> {}
 
-impl<I> DoubleEndedIterator for Splice<'_, I>
+impl<I, A: Allocator, H> DoubleEndedIterator for Splice<'_, I, A, H>
 where
     I: Iterator,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        let pos = unsafe { self.drain_end_.as_ptr().sub(1) };
-        if pos < self.drain_pos_.as_ptr() {
+        if self.drain_end_ <= self.drain_pos_ {
             return None;
         }
 
-        let tmp = unsafe { core::ptr::read(pos) };
-        self.drain_end_ = unsafe { core::ptr::NonNull::new_unchecked(pos) };
+        self.drain_end_ -= 1;
+        let tmp = unsafe { core::ptr::read(self.data_.add(self.drain_end_)) };
         Some(tmp)
     }
 }
 
-impl<I: Iterator> Drop for Splice<'_, I> {
+impl<I: Iterator, A: Allocator, H> Drop for Splice<'_, I, A, H> {
     fn drop(&mut self) {
-        struct DropGuard<'b, 'a, I>
+        struct DropGuard<'b, 'a, I, A: Allocator, H>
         where
             I: Iterator,
         {
-            splice: &'b mut Splice<'a, I>,
-        };
+            splice: &'b mut Splice<'a, I, A, H>,
+        }
 
-        impl<'b, 'a, I> Drop for DropGuard<'b, 'a, I>
+        impl<I, A: Allocator, H> Drop for DropGuard<'_, '_, I, A, H>
         where
             I: Iterator,
         {
@@ -95,20 +99,18 @@ impl<I: Iterator> Drop for Splice<'_, I> {
                 // much like Drain, remove the rest of the elements from the splice range if they
                 // haven't already been exhausted
                 //
-                while let Some(_) = self.splice.next() {}
+                for _ in self.splice.by_ref() {}
 
                 let vec = unsafe { self.splice.vec_.as_mut() };
 
                 // first, figure out where our draining operation started
                 // this is at offset vec.len() from the start of [T]'s data
                 //
-                let drain_begin = unsafe { vec.as_mut_ptr().add(vec.len()) };
+                let drain_begin = vec.len();
 
                 // infer the number of items we drained by where the remaining_pos_ is
                 //
-                let num_drained = (self.splice.remaining_pos_.as_ptr() as usize
-                    - drain_begin as usize)
-                    / core::mem::size_of::<I::Item>();
+                let num_drained = self.splice.remaining_pos_ - drain_begin;
 
                 // fill the drained sub-section using the iterator the user supplied
                 // if the iterator, for example, has more elements than the draiend region allows,
@@ -119,7 +121,10 @@ impl<I: Iterator> Drop for Splice<'_, I> {
                     for idx in 0..num_drained {
                         if let Some(val) = self.splice.fill_.next() {
                             unsafe {
-                                core::ptr::write(drain_begin.add(idx), val);
+                                core::ptr::write(
+                                    self.splice.data_.cast_mut().add(drain_begin + idx),
+                                    val,
+                                );
                                 vec.set_len(vec.len() + 1);
                             };
                         } else {
@@ -138,8 +143,7 @@ impl<I: Iterator> Drop for Splice<'_, I> {
                     // we don't need to memcpy and can instead just adjust the length of the vector
                     // and return
                     //
-                    if unsafe { vec.as_ptr().add(vec.len()) == self.splice.remaining_pos_.as_ptr() }
-                    {
+                    if vec.len() == self.splice.remaining_pos_ {
                         unsafe { vec.set_len(vec.len() + self.splice.remaining_) };
                         return;
                     }
@@ -149,8 +153,8 @@ impl<I: Iterator> Drop for Splice<'_, I> {
                     // this basically downshifts the elements from right-to-left so it's safe to
                     // call `core::ptr::copy`
                     //
-                    let src = self.splice.remaining_pos_.as_ptr();
-                    let dst = unsafe { vec.as_mut_ptr().add(vec.len()) };
+                    let src = unsafe { self.splice.data_.add(self.splice.remaining_pos_) };
+                    let dst = unsafe { self.splice.data_.cast_mut().add(vec.len()) };
                     let count = self.splice.remaining_;
                     unsafe {
                         core::ptr::copy(src, dst, count);
@@ -160,61 +164,12 @@ impl<I: Iterator> Drop for Splice<'_, I> {
                     return;
                 }
 
-                // we need to handle the rest of the iterator's elements now
-                // pool them into a temporary vector for storage
-                //
-                let mut tmp: MiniVec<_> = (&mut self.splice.fill_).collect();
-
-                // reserve extra capacity if required
-                // note, this will invalidate all of our previously cached pointers in the Splice
-                // iterator so we have to store the offset of the drain tail manually
-                //
-                let capacity = vec.capacity();
-                let remaining_offset = (self.splice.remaining_pos_.as_ptr() as usize
-                    - vec.as_ptr() as usize)
-                    / core::mem::size_of::<I::Item>();
-
-                // if our vector's length + the remaining elements + the extra tmp length exceeds
-                // our capacity we need to reallocate
-                //
-                let total_elements = vec.len() + self.splice.remaining_ + tmp.len();
-
-                if total_elements > capacity {
-                    vec.grow(total_elements, vec.alignment());
-                }
-
-                // let's first move the Drain tail over to the right
-                // we know our Drain's tail starts at the `remaining_offset` and we have to copy
-                // self.splice.remaining_ elements over starting at the offset of our current len
-                // plus the tail len
-                //
-                if self.splice.remaining_ > 0 {
-                    unsafe {
-                        let src = vec.as_ptr().add(remaining_offset);
-                        let dst = vec.as_mut_ptr().add(vec.len() + tmp.len());
-                        let count = self.splice.remaining_;
-                        core::ptr::copy(src, dst, count);
-                    };
-                }
-
-                // finally we copy the remaining tmp elements into the vector and then we make sure
-                // to set its length to 0 to prevent any sort of double-frees
-                //
-                if !tmp.is_empty() {
-                    unsafe {
-                        let src = tmp.as_ptr();
-                        let dst = vec.as_mut_ptr().add(vec.len());
-                        let count = tmp.len();
-                        core::ptr::copy(src, dst, count);
-                    };
-                }
-
-                unsafe {
-                    vec.set_len(vec.len() + self.splice.remaining_ + tmp.len());
-                    if !tmp.is_empty() {
-                        tmp.set_len(0)
-                    };
-                };
+                fill_tail(
+                    vec,
+                    &mut self.splice.fill_,
+                    self.splice.remaining_pos_,
+                    self.splice.remaining_,
+                );
             }
         }
 
@@ -227,3 +182,113 @@ impl<I: Iterator> Drop for Splice<'_, I> {
         DropGuard { splice: self };
     }
 }
+
+// moves every element left in `fill` into the vector, in between its current end and the drain
+// tail of `remaining` elements at offset `tail`, and then commits the length of the whole vector
+//
+#[cfg(feature = "alloc")]
+fn fill_tail<I, A, H>(vec: &mut MiniVec<I::Item, A, H>, fill: &mut I, tail: usize, remaining: usize)
+where
+    I: Iterator,
+    A: Allocator,
+{
+    // we need to handle the rest of the iterator's elements now
+    // pool them into a temporary vector for storage
+    //
+    let mut tmp: MiniVec<_> = fill.collect();
+
+    // reserve extra capacity if required
+    // note, this will invalidate all of our previously cached pointers in the Splice
+    // iterator so we have to store the offset of the drain tail manually
+    //
+    let capacity = vec.capacity();
+
+    // if our vector's length + the remaining elements + the extra tmp length exceeds
+    // our capacity we need to reallocate
+    //
+    let total_elements = vec.len() + remaining + tmp.len();
+
+    if total_elements > capacity {
+        vec.grow(total_elements, vec.alignment());
+    }
+
+    // let's first move the Drain tail over to the right
+    // we know our Drain's tail starts at the `tail` offset and we have to copy `remaining`
+    // elements over starting at the offset of our current len plus the tmp len
+    //
+    if remaining > 0 {
+        unsafe {
+            let src = vec.as_ptr().add(tail);
+            let dst = vec.as_mut_ptr().add(vec.len() + tmp.len());
+            core::ptr::copy(src, dst, remaining);
+        };
+    }
+
+    // finally we copy the remaining tmp elements into the vector and then we make sure
+    // to set its length to 0 to prevent any sort of double-frees
+    //
+    if !tmp.is_empty() {
+        unsafe {
+            let src = tmp.as_ptr();
+            let dst = vec.as_mut_ptr().add(vec.len());
+            let count = tmp.len();
+            core::ptr::copy(src, dst, count);
+        };
+    }
+
+    unsafe {
+        vec.set_len(vec.len() + remaining + tmp.len());
+        if !tmp.is_empty() {
+            tmp.set_len(0);
+        }
+    };
+}
+
+// without a temporary vector to pool the rest of the iterator's elements into, the tail is
+// instead moved out of the way one batch at a time, sized after the iterator's lower bound, and
+// whatever gap is left once the iterator runs out is closed again
+//
+#[cfg(not(feature = "alloc"))]
+fn fill_tail<I, A, H>(vec: &mut MiniVec<I::Item, A, H>, fill: &mut I, tail: usize, remaining: usize)
+where
+    I: Iterator,
+    A: Allocator,
+{
+    let mut tail = tail;
+
+    while let Some(first) = fill.next() {
+        let extra = fill.size_hint().0.saturating_add(1);
+        let total_elements = vec.len() + extra + remaining;
+
+        if total_elements > vec.capacity() {
+            vec.grow(total_elements, vec.alignment());
+        }
+
+        let data = vec.as_mut_ptr();
+        unsafe {
+            core::ptr::copy(data.add(tail), data.add(tail + extra), remaining);
+            tail += extra;
+
+            data.add(vec.len()).write(first);
+            vec.set_len(vec.len() + 1);
+        }
+
+        while vec.len() < tail {
+            match fill.next() {
+                Some(val) => unsafe {
+                    data.add(vec.len()).write(val);
+                    vec.set_len(vec.len() + 1);
+                },
+                None => break,
+            }
+        }
+
+        if vec.len() < tail {
+            unsafe { core::ptr::copy(data.add(tail), data.add(vec.len()), remaining) };
+            tail = vec.len();
+            break;
+        }
+    }
+
+    unsafe { vec.set_len(tail + remaining) };
+}
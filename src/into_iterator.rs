@@ -1,17 +1,18 @@
+use crate::Allocator;
 use crate::MiniVec;
 
 use crate::r#impl::into_iter::IntoIter;
 
-impl<T> core::iter::IntoIterator for MiniVec<T> {
+impl<T, A: Allocator, H> core::iter::IntoIterator for MiniVec<T, A, H> {
     type Item = T;
-    type IntoIter = IntoIter<T>;
+    type IntoIter = IntoIter<T, A, H>;
 
     fn into_iter(self) -> Self::IntoIter {
-        IntoIter::<T>::new(self)
+        IntoIter::<T, A, H>::new(self)
     }
 }
 
-impl<'a, T> core::iter::IntoIterator for &'a MiniVec<T> {
+impl<'a, T, A: Allocator, H> core::iter::IntoIterator for &'a MiniVec<T, A, H> {
     type Item = &'a T;
     type IntoIter = core::slice::Iter<'a, T>;
 
@@ -20,7 +21,7 @@ impl<'a, T> core::iter::IntoIterator for &'a MiniVec<T> {
     }
 }
 
-impl<'a, T> core::iter::IntoIterator for &'a mut MiniVec<T> {
+impl<'a, T, A: Allocator, H> core::iter::IntoIterator for &'a mut MiniVec<T, A, H> {
     type Item = &'a mut T;
     type IntoIter = core::slice::IterMut<'a, T>;
 
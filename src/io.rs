@@ -0,0 +1,136 @@
+use crate::{Allocator, Drain, IntoIter, MiniVec};
+
+use std::io;
+
+impl<A: Allocator> io::Write for MiniVec<u8, A> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        let len = bufs.iter().map(|b| b.len()).sum();
+        self.reserve(len);
+
+        for buf in bufs {
+            self.extend_from_slice(buf);
+        }
+
+        Ok(len)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `read_to_end_into` reads every byte from `reader` until EOF and appends them to `buf`,
+/// returning the number of bytes read.
+///
+/// Unlike going through an intermediate `Vec`, the bytes are read directly into the vector's
+/// [`spare_capacity_mut()`](MiniVec::spare_capacity_mut) which is grown as required.
+///
+/// Requires the `std` feature.
+///
+/// # Panics
+///
+/// Panics if `reader` reports having read more bytes than the buffer it was given.
+///
+/// # Errors
+///
+/// Returns any error produced by `reader` other than `io::ErrorKind::Interrupted`, which is
+/// retried. Any bytes read before the error remain in `buf`.
+///
+/// # Example
+///
+/// ```
+/// let mut vec = minivec::mini_vec![b'>', b' '];
+/// let mut reader: &[u8] = b"hello world";
+///
+/// let n = minivec::read_to_end_into(&mut reader, &mut vec).unwrap();
+///
+/// assert_eq!(n, 11);
+/// assert_eq!(vec, b"> hello world");
+/// ```
+///
+pub fn read_to_end_into<R, A>(reader: &mut R, buf: &mut MiniVec<u8, A>) -> io::Result<usize>
+where
+    R: io::Read + ?Sized,
+    A: Allocator,
+{
+    let start_len = buf.len();
+
+    // `io::Read` requires an initialized buffer so we zero out the spare capacity but we keep
+    // track of how much of it we've already touched so that every byte is only zeroed once
+    //
+    let mut initialized = 0;
+
+    loop {
+        if buf.len() == buf.capacity() {
+            buf.reserve(32);
+        }
+
+        let spare = buf.spare_capacity_mut();
+        let spare_len = spare.len();
+
+        for b in &mut spare[initialized..] {
+            *b = core::mem::MaybeUninit::new(0);
+        }
+        initialized = spare_len;
+
+        let dst = unsafe { &mut *(core::ptr::from_mut(spare) as *mut [u8]) };
+
+        match reader.read(dst) {
+            Ok(0) => return Ok(buf.len() - start_len),
+            Ok(n) => {
+                assert!(n <= spare_len, "reader returned an invalid length");
+
+                unsafe { buf.set_len(buf.len() + n) };
+                initialized -= n;
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+impl<A: Allocator> io::Read for IntoIter<u8, A> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = io::Read::read(&mut self.as_slice(), buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<A: Allocator> io::BufRead for IntoIter<u8, A> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.as_slice())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        IntoIter::consume(self, amt);
+    }
+}
+
+impl<A: Allocator> io::Read for Drain<'_, u8, A> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = io::Read::read(&mut self.as_slice(), buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<A: Allocator> io::BufRead for Drain<'_, u8, A> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.as_slice())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        Drain::consume(self, amt);
+    }
+}
@@ -20,238 +20,1584 @@
 //! Nightly features are not supported. `MiniVec` also supports myriad extensions, one such being
 //! support for over-alignment via the associated function [`with_alignment`](MiniVec::with_alignment).
 //!
+//! The crate also provides other single-pointer containers built on the same allocation layout:
+//! [`MiniString`](MiniString), [`MiniBox`](MiniBox), [`MiniVec32`](MiniVec32),
+//! [`MiniSmallVec`](MiniSmallVec), [`MiniRc`](MiniRc), [`MiniArc`](MiniArc),
+//! [`TaggedMiniVec`](TaggedMiniVec), [`AtomicMiniVec`](AtomicMiniVec) and
+//! [`ConcurrentMiniVec`](ConcurrentMiniVec).
+//!
+//! # Features
+//!
+//! * `alloc` (default): everything that relies on the global allocator. Without it, `MiniVec`
+//!   only works with caller-supplied allocators such as [`MiniVec::from_buffer`](MiniVec::from_buffer).
+//! * `std`: `std::io` and `std::error::Error` implementations.
+//! * `allocator-api2`: adds `Api2`, which makes the allocators of the `allocator-api2` crate
+//!   usable with `MiniVec`.
+//! * `serde`: `Serialize` and `Deserialize` implementations.
+//!
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod r#impl;
 
+mod allocator;
+
 mod as_mut;
 mod as_ref;
+#[cfg(feature = "alloc")]
+mod atomic;
 mod borrow;
+#[cfg(feature = "alloc")]
+mod boxed;
 mod clone;
+#[cfg(feature = "alloc")]
+mod concurrent;
 mod debug;
 mod default;
 mod deref;
 mod drop;
 mod eq;
 mod extend;
+mod fixed;
 mod from;
+#[cfg(feature = "alloc")]
 mod from_iterator;
 mod hash;
 mod index;
 mod into_iterator;
+#[cfg(feature = "std")]
+mod io;
 mod ord;
 mod partial_eq;
-#[cfg(feature = "serde")]
+#[cfg(feature = "alloc")]
+mod rc;
+#[cfg(all(feature = "serde", feature = "alloc"))]
 mod serde;
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(feature = "alloc")]
+mod small;
+#[cfg(feature = "alloc")]
+mod string;
+#[cfg(feature = "alloc")]
+mod sync;
+#[cfg(feature = "alloc")]
+mod tagged;
+mod vec32;
 
 use crate::r#impl::drain::make_drain_iterator;
 use crate::r#impl::drain_filter::make_drain_filter_iterator;
-use crate::r#impl::helpers::{make_layout, max_align, next_aligned, next_capacity};
+use crate::r#impl::helpers::{
+    capacity_in, data_offset, ext_offset, make_layout, max_capacity, min_alignment, next_aligned,
+    next_capacity, try_make_layout, unaligned_head, validate_alignment,
+};
 use crate::r#impl::splice::make_splice_iterator;
 
+#[cfg(feature = "allocator-api2")]
+pub use crate::allocator::Api2;
+#[cfg(feature = "alloc")]
+pub use crate::allocator::Global;
+pub use crate::allocator::{AllocError, Allocator};
+#[cfg(feature = "alloc")]
+pub use crate::atomic::{AtomicMiniVec, SlotToken};
+#[cfg(feature = "alloc")]
+pub use crate::boxed::{FrozenSlice, MiniBox};
+#[cfg(feature = "alloc")]
+pub use crate::concurrent::ConcurrentMiniVec;
+pub use crate::fixed::FixedStorage;
+#[cfg(feature = "std")]
+pub use crate::io::read_to_end_into;
 pub use crate::r#impl::{Drain, DrainFilter, IntoIter, Splice};
-
-#[derive(core::fmt::Debug)]
+#[cfg(feature = "alloc")]
+pub use crate::rc::MiniRc;
+#[cfg(feature = "alloc")]
+pub use crate::small::MiniSmallVec;
+#[cfg(feature = "alloc")]
+pub use crate::string::{FromUtf8Error, MiniString, StringDrain};
+#[cfg(feature = "alloc")]
+pub use crate::sync::MiniArc;
+#[cfg(feature = "alloc")]
+pub use crate::tagged::{TaggedMiniVec, TaggedMut, TaggedRef};
+pub use crate::vec32::MiniVec32;
+
+/// `LayoutErr` is the error type returned by the functions accepting a user-supplied alignment,
+/// such as [`with_alignment`](MiniVec::with_alignment) and [`realign`](MiniVec::realign).
+///
+#[derive(core::fmt::Debug, Clone, PartialEq, Eq)]
 pub enum LayoutErr {
+    /// The alignment is smaller than the one required by the element type and the header.
     AlignmentTooSmall,
-    AlignmentNotDivisibleByTwo,
+    /// The alignment is not a power of two.
+    AlignmentNotPowerOfTwo,
+    /// The alignment is so large that not even the header fits in an allocation on this platform.
+    AlignmentTooLarge,
+    /// The requested capacity, at this alignment, exceeds the maximum number of bytes an
+    /// allocation may span.
+    SizeOverflow,
+}
+
+/// `TryReserveError` is the error type returned by the fallible allocation APIs such as
+/// [`try_reserve`](MiniVec::try_reserve) and [`try_push`](MiniVec::try_push).
+///
+/// When one of these functions returns an error, the vector is left exactly as it was before the
+/// call.
+///
+#[derive(core::fmt::Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds the maximum number of bytes an allocation may span.
+    CapacityOverflow,
+    /// The allocator was unable to satisfy a request for the contained `layout`.
+    AllocError { layout: core::alloc::Layout },
+    /// The requested alignment was rejected. Only returned by functions that accept a
+    /// user-supplied alignment, such as [`try_with_alignment`](MiniVec::try_with_alignment).
+    InvalidAlignment(LayoutErr),
+}
+
+impl core::fmt::Display for LayoutErr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LayoutErr::AlignmentTooSmall => {
+                f.write_str("alignment is smaller than the minimum required alignment")
+            }
+            LayoutErr::AlignmentNotPowerOfTwo => f.write_str("alignment is not a power of two"),
+            LayoutErr::AlignmentTooLarge => {
+                f.write_str("alignment exceeds the maximum supported by the platform")
+            }
+            LayoutErr::SizeOverflow => {
+                f.write_str("requested capacity exceeds the maximum allocation size")
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TryReserveError::CapacityOverflow => {
+                f.write_str("requested capacity exceeds the maximum allocation size")
+            }
+            TryReserveError::AllocError { layout } => write!(
+                f,
+                "memory allocation of {} bytes with alignment {} failed",
+                layout.size(),
+                layout.align()
+            ),
+            TryReserveError::InvalidAlignment(err) => write!(f, "invalid alignment: {err}"),
+        }
+    }
 }
 
-pub struct MiniVec<T> {
-    buf: *mut u8,
-    phantom: core::marker::PhantomData<T>,
+#[cfg(feature = "std")]
+impl std::error::Error for LayoutErr {}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryReserveError {}
+
+/// `MiniVec` is a growable array which, unlike `Vec`, is only the size of a single pointer.
+///
+/// The length, capacity and alignment are stored in a header at the front of the allocation.
+/// `MiniVec` is generic over the [`Allocator`](Allocator) it uses, defaulting to the global one,
+/// and over a header extension `H`: user-defined metadata, such as a cached hash, that's stored
+/// in the same header and accessed via [`ext`](MiniVec::ext) and [`ext_mut`](MiniVec::ext_mut).
+///
+pub struct MiniVec<
+    T,
+    #[cfg(feature = "alloc")] A: Allocator = Global,
+    #[cfg(not(feature = "alloc"))] A: Allocator,
+    H = (),
+> {
+    buf: core::ptr::NonNull<u8>,
+    allocator: A,
+    phantom: core::marker::PhantomData<(T, Option<H>)>,
 }
 
+#[repr(C)]
 struct Header {
     len: usize,
     cap: usize,
     alignment: usize,
 }
 
-impl<T> MiniVec<T> {
-    fn header(&self) -> &Header {
-        #[allow(clippy::cast_ptr_alignment)]
-        unsafe {
-            &*(self.buf as *const Header)
-        }
-    }
+// every unallocated vector points at this header which lets us keep `buf` non-null, giving
+// `Option<MiniVec<T>>` a niche, while still being able to read a length and capacity of `0`
+// without branching
+//
+// the sentinel is never written to
+//
+static EMPTY_HEADER: Header = Header {
+    len: 0,
+    cap: 0,
+    alignment: 0,
+};
+
+// alignments are powers of two no smaller than the header's own so their lowest bit is always
+// free. Setting it marks storage that's borrowed from static memory rather than allocated
+//
+const BORROWED: usize = 1;
+
+// `realign` raises the alignment of a vector whose data already satisfies the new one without
+// moving anything. The alignment field then holds two bits: the lowest one is the alignment the
+// allocation was laid out with, which fixes where the data starts, and the highest one is the
+// alignment reported by `alignment()`. The vector is laid out anew the next time it reallocates
+//
+const fn layout_alignment_of(field: usize) -> usize {
+    let bits = field & !BORROWED;
+    bits & bits.wrapping_neg()
+}
 
-    fn header_mut(&mut self) -> &mut Header {
-        #[allow(clippy::cast_ptr_alignment)]
-        unsafe {
-            &mut *(self.buf as *mut Header)
+const fn reported_alignment_of(field: usize) -> usize {
+    let bits = field & !BORROWED;
+    1 << (usize::BITS - 1 - bits.leading_zeros())
+}
+
+const fn empty_header() -> core::ptr::NonNull<u8> {
+    unsafe { core::ptr::NonNull::new_unchecked(core::ptr::addr_of!(EMPTY_HEADER) as *mut u8) }
+}
+
+/// `StaticStorage` holds the header and elements of a vector created by
+/// [`static_mini_vec!`](static_mini_vec), laid out exactly like a heap allocation.
+///
+/// The header reports the real length and capacity of `N` but its alignment carries the `BORROWED`
+/// flag: vectors pointing at such storage never write to or free it and instead copy their
+/// elements into an allocation of their own before the first mutation.
+///
+#[doc(hidden)]
+#[repr(C)]
+pub struct StaticStorage<T, const N: usize> {
+    header: Header,
+    data: [T; N],
+}
+
+impl<T: Copy, const N: usize> StaticStorage<T, N> {
+    /// `new` places the header in front of `data`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is aligned more strictly than `usize`.
+    ///
+    #[must_use]
+    pub const fn new(data: [T; N]) -> StaticStorage<T, N> {
+        // the elements directly follow the header which means the alignment stored in it doubles
+        // as the copy expected right in front of the data
+        //
+        assert!(
+            core::mem::align_of::<T>() <= core::mem::align_of::<usize>(),
+            "over-aligned types cannot be stored in a static MiniVec"
+        );
+
+        StaticStorage {
+            header: Header {
+                len: N,
+                cap: N,
+                alignment: core::mem::align_of::<Header>() | BORROWED,
+            },
+            data,
         }
     }
+}
 
-    fn data(&self) -> *mut T {
-        debug_assert!(!self.buf.is_null());
+// vectors of zero-sized types never allocate and instead store `len + 1` directly in `buf`
+// which means they can hold at most `usize::MAX - 1` elements
+//
+const fn zst_buf(len: usize) -> core::ptr::NonNull<u8> {
+    assert!(len < usize::MAX, "capacity overflow");
+    unsafe { core::ptr::NonNull::new_unchecked((len + 1) as *mut u8) }
+}
+
+// the `usize` immediately preceding the first element of an allocation always holds its
+// alignment, either as the header's own `alignment` field or as a copy stored in the padding
+// between the header and the data
+//
+// this is what lets us find the header from nothing but a data pointer, regardless of how
+// over-aligned the vector is
+//
+#[allow(clippy::cast_ptr_alignment)]
+unsafe fn write_header<H>(buf: core::ptr::NonNull<u8>, header: Header) {
+    let offset = data_offset::<H>(layout_alignment_of(header.alignment));
+    let tag = buf.as_ptr().add(offset).cast::<usize>().sub(1);
+    let alignment = header.alignment;
+
+    core::ptr::write(buf.cast::<Header>().as_ptr(), header);
+    core::ptr::write(tag, alignment);
+}
 
-        let count = next_aligned(core::mem::size_of::<Header>(), self.alignment());
-        unsafe { self.buf.add(count) as *mut T }
+#[allow(clippy::cast_ptr_alignment)]
+unsafe fn header_from_data(data: *mut u8) -> core::ptr::NonNull<u8> {
+    if data.is_null() {
+        return empty_header();
     }
 
-    fn alignment(&self) -> usize {
-        if self.buf.is_null() {
-            max_align::<T>()
-        } else {
-            self.header().alignment
-        }
+    let alignment = layout_alignment_of(core::ptr::read(data.cast::<usize>().sub(1)));
+    let offset = next_aligned(core::mem::size_of::<Header>(), alignment);
+
+    core::ptr::NonNull::new_unchecked(data.sub(offset))
+}
+
+// commits a length that's tracked locally while elements are being written into the spare
+// capacity, updating the header once per batch instead of once per element. As this happens on
+// drop, a panic midway through still leaves every element written so far owned by the vector
+//
+struct SetLenOnDrop<'a, T, A: Allocator, H> {
+    vec: &'a mut MiniVec<T, A, H>,
+    len: usize,
+}
+
+impl<T, A: Allocator, H> Drop for SetLenOnDrop<'_, T, A, H> {
+    fn drop(&mut self) {
+        unsafe { self.vec.set_len(self.len) };
     }
+}
 
-    fn grow(&mut self, capacity: usize, alignment: usize) {
-        debug_assert!(capacity >= self.len());
+#[cfg(feature = "alloc")]
+const _: () = assert!(
+    core::mem::size_of::<Option<MiniVec<u8>>>() == core::mem::size_of::<usize>()
+        && core::mem::size_of::<Option<MiniVec<()>>>() == core::mem::size_of::<usize>()
+);
 
-        let old_capacity = self.capacity();
-        let new_capacity = capacity;
+#[cfg(feature = "alloc")]
+impl<T> MiniVec<T> {
+    /// `collect_aligned` is the over-aligned counterpart to [`collect()`](core::iter::Iterator::collect)
+    /// and gathers every element of `iter` into a new `MiniVec` whose allocation is aligned to
+    /// `alignment`.
+    ///
+    /// The supplied alignment has the same requirements as the one passed to [`with_alignment`](MiniVec::with_alignment).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `LayoutErr` if `alignment` is rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniVec::collect_aligned((0..16).map(|x| x as f32), 32).unwrap();
+    ///
+    /// assert_eq!(vec.len(), 16);
+    /// assert_eq!(vec.as_ptr() as usize % 32, 0);
+    /// ```
+    ///
+    pub fn collect_aligned<I>(iter: I, alignment: usize) -> Result<MiniVec<T>, LayoutErr>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
 
-        if new_capacity == old_capacity {
-            return;
+        let mut v = MiniVec::with_alignment(lower, alignment)?;
+        v.extend_iter(iter);
+
+        Ok(v)
+    }
+
+    #[doc(hidden)]
+    #[must_use]
+    pub fn from_array_aligned<const N: usize>(arr: [T; N], alignment: usize) -> MiniVec<T> {
+        let arr = core::mem::ManuallyDrop::new(arr);
+
+        let mut v = MiniVec::try_with_alignment(N, alignment)
+            .unwrap_or_else(|err| handle_reserve_error(err));
+        unsafe {
+            core::ptr::copy_nonoverlapping(arr.as_ptr(), v.data(), N);
+            v.set_len(N);
         }
 
-        let new_layout = make_layout::<T>(new_capacity, alignment);
+        v
+    }
 
-        let len = self.len();
+    #[doc(hidden)]
+    #[must_use]
+    pub fn from_elem(elem: T, n: usize) -> MiniVec<T>
+    where
+        T: Clone,
+    {
+        MiniVec::with_capacity(n).fill_with_clones(elem, n)
+    }
 
-        let new_buf = if self.buf.is_null() {
-            unsafe { alloc::alloc::alloc(new_layout) }
-        } else {
-            let old_layout = make_layout::<T>(old_capacity, alignment);
+    #[doc(hidden)]
+    #[must_use]
+    pub fn from_elem_aligned(elem: T, n: usize, alignment: usize) -> MiniVec<T>
+    where
+        T: Clone,
+    {
+        MiniVec::try_with_alignment(n, alignment)
+            .unwrap_or_else(|err| handle_reserve_error(err))
+            .fill_with_clones(elem, n)
+    }
 
-            unsafe { alloc::alloc::realloc(self.buf, old_layout, new_layout.size()) }
-        };
+    // writes `n - 1` clones of `elem` followed by `elem` itself, committing the length once at the
+    // end rather than after every element. A panicking `clone()` still drops every element written
+    // so far
+    //
+    fn fill_with_clones(mut self, elem: T, n: usize) -> MiniVec<T>
+    where
+        T: Clone,
+    {
+        debug_assert!(self.is_empty() && self.capacity() >= n);
 
-        if new_buf.is_null() {
-            alloc::alloc::handle_alloc_error(new_layout);
+        if n == 0 {
+            return self;
         }
 
-        let header = Header {
-            len,
-            cap: new_capacity,
-            alignment,
+        let data = self.data();
+        let head = unaligned_head(data, n - 1);
+        let mut guard = SetLenOnDrop {
+            vec: &mut self,
+            len: 0,
         };
 
-        #[allow(clippy::cast_ptr_alignment)]
-        unsafe {
-            core::ptr::write(new_buf as *mut Header, header)
-        };
+        // the unaligned head is written by a loop of its own so that the bulk of the clones are
+        // written to cache line boundaries
+        //
+        for end in [head, n - 1] {
+            for idx in guard.len..end {
+                unsafe { data.add(idx).write(elem.clone()) };
+                guard.len = idx + 1;
+            }
+        }
 
-        self.buf = new_buf;
+        unsafe { data.add(n - 1).write(elem) };
+        guard.len = n;
+        drop(guard);
+
+        self
     }
 
-    /// `append` moves every element from `other` to the back of `self`. `other.is_empty()` is
-    /// `true` once this operation completes and its capacity is unaffected.
+    /// `from_raw` reconstructs a `MiniVec` from the pointer returned by [`into_raw`](MiniVec::into_raw).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be the result of a call to `into_raw` and must not have been used to
+    /// reconstruct a `MiniVec` already.
     ///
     /// # Example
     ///
     /// ```
-    /// let mut vec = minivec::mini_vec![1, 2, 3];
-    /// let mut vec2 = minivec::mini_vec![4, 5, 6];
-    /// vec.append(&mut vec2);
-    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6]);
-    /// assert_eq!(vec2, []);
+    /// let vec = minivec::MiniVec::from_slice_aligned(&[1_u8, 2, 3, 4], 4096).unwrap();
+    ///
+    /// let ptr = vec.into_raw();
+    /// assert_eq!(ptr as usize % 4096, 0);
+    ///
+    /// let vec = unsafe { minivec::MiniVec::from_raw(ptr) };
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    /// assert_eq!(vec.alignment(), 4096);
     /// ```
     ///
-    pub fn append(&mut self, other: &mut MiniVec<T>) {
-        if other.is_empty() {
-            return;
-        }
-
-        let other_len = other.len();
-        self.reserve(other_len);
-
-        unsafe {
-            core::ptr::copy_nonoverlapping(
-                other.as_ptr(),
-                self.as_mut_ptr().add(self.len()),
-                other_len,
-            );
+    pub unsafe fn from_raw(ptr: *mut T) -> MiniVec<T> {
+        let buf = if core::mem::size_of::<T>() == 0 {
+            core::ptr::NonNull::new_unchecked(ptr.cast::<u8>())
+        } else {
+            header_from_data(ptr.cast::<u8>())
         };
 
-        other.header_mut().len = 0;
-        self.header_mut().len += other_len;
+        MiniVec {
+            buf,
+            allocator: Global,
+            phantom: core::marker::PhantomData,
+        }
     }
 
-    /// `as_mut_ptr` returns a `*mut T` to the underlying array.
+    /// `from_raw_part` reconstructs a `MiniVec` from a previous call to [`MiniVec::as_mut_ptr`](MiniVec::as_mut_ptr)
+    /// or the pointer from [`into_raw_parts`](MiniVec::into_raw_parts).
     ///
-    /// * May return a null pointer.
-    /// * May be invalidated by calls to [`reserve()`](MiniVec::reserve)
-    /// * Can outlive its backing `MiniVec`
+    /// # Safety
+    ///
+    /// `from_raw_part` is incredibly unsafe and can only be used with the value of
+    /// `MiniVec::as_mut_ptr`. This is because the allocation for the backing array stores metadata
+    /// at its head and is not guaranteed to be stable so users are discouraged from attempting to
+    /// support this directly.
+    ///
+    /// The pointer may come from a vector of any alignment, including vectors created with
+    /// [`with_alignment`](MiniVec::with_alignment), and a null pointer yields an empty vector.
+    ///
+    /// Vectors of zero-sized types don't store their length behind the data pointer so this
+    /// function panics for them. Use [`from_raw`](MiniVec::from_raw) or [`from_raw_parts`](MiniVec::from_raw_parts)
+    /// instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is a zero-sized type.
     ///
     /// # Example
+    ///
     /// ```
     /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
-    /// let mut p = vec.as_mut_ptr();
     ///
-    /// for idx in 0..vec.len() {
-    ///     unsafe {
-    ///         *p.add(idx) = *p.add(idx) + 3;
-    ///     }
-    /// }
+    /// let ptr = vec.as_mut_ptr();
     ///
-    /// assert_eq!(vec, [4, 5, 6, 7]);
+    /// std::mem::forget(vec);
+    ///
+    /// let new_vec = unsafe { minivec::MiniVec::from_raw_part(ptr) };
+    ///
+    /// assert_eq!(new_vec, [1, 2, 3, 4]);
     /// ```
     ///
-    pub fn as_mut_ptr(&mut self) -> *mut T {
-        if self.buf.is_null() {
-            return core::ptr::null_mut();
-        }
+    pub unsafe fn from_raw_part(ptr: *mut T) -> MiniVec<T> {
+        assert!(
+            core::mem::size_of::<T>() > 0,
+            "from_raw_part cannot recover the length of a vector of zero-sized types"
+        );
 
-        self.data()
+        MiniVec {
+            buf: header_from_data(ptr.cast::<u8>()),
+            allocator: Global,
+            phantom: core::marker::PhantomData,
+        }
     }
 
-    /// `as_mut_slice` obtains a mutable reference to a slice that's attached to the backing array.
+    /// `from_raw_parts` is an API-compatible version of `alloc::vec::Vec::from_raw_parts`. Because
+    /// of `MiniVec`'s optimized layout, it's not strictly required for a user to pass the length
+    /// and capacity explicitly.
     ///
-    /// # Example
-    /// ```
-    /// let mut vec = minivec::mini_vec![1, 2, 3];
-    /// {
-    ///     let as_slice: &mut [_] = vec.as_mut_slice();
-    ///     as_slice[0] = 1337;
-    /// }
-    /// assert_eq!(vec[0], 1337);
-    /// ```
+    /// Like [`MiniVec::from_raw_part`](MiniVec::from_raw_part), this function is only safe to use
+    /// with the result of a call to [`MiniVec::as_mut_ptr()`](MiniVec::as_mut_ptr).
     ///
-    pub fn as_mut_slice(&mut self) -> &mut [T] {
-        self
-    }
-
-    /// `as_ptr` obtains a `*const T` to the underlying allocation.
+    /// # Safety
     ///
-    /// * May return a null pointer.
-    /// * May be invalidated by calls to `reserve()`
-    /// * Can outlive its backing `MiniVec`
+    /// A very unsafe function that should only really be used when passing the vector to a C API.
     ///
     /// # Example
+    ///
     /// ```
     /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
-    /// let mut p = vec.as_mut_ptr();
+    /// let len = vec.len();
+    /// let cap = vec.capacity();
     ///
-    /// let mut sum = 0;
-    /// for idx in 0..vec.len() {
-    ///     unsafe {
-    ///         sum += *p.add(idx);
-    ///     }
-    /// }
+    /// let ptr = vec.as_mut_ptr();
     ///
-    /// assert_eq!(sum, 1 + 2 + 3 + 4);
+    /// std::mem::forget(vec);
+    ///
+    /// let new_vec = unsafe { minivec::MiniVec::from_raw_parts(ptr, len, cap) };
+    ///
+    /// assert_eq!(new_vec, [1, 2, 3, 4]);
     /// ```
     ///
-    #[must_use]
-    pub fn as_ptr(&self) -> *const T {
-        if self.buf.is_null() {
-            return core::ptr::null();
-        }
-
-        self.data()
+    pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> MiniVec<T> {
+        MiniVec::from_raw_parts_in(ptr, length, capacity, Global)
     }
 
-    /// `as_slice` obtains a reference to the backing array as an immutable slice of `T`.
+    /// `from_slice_aligned` clones every element of `elems` into a new `MiniVec` whose allocation
+    /// is aligned to `alignment`.
     ///
-    /// # Example
-    /// ```
-    /// let vec = minivec::mini_vec![1, 2, 3, 4];
-    /// let mut sum = 0;
+    /// The supplied alignment has the same requirements as the one passed to [`with_alignment`](MiniVec::with_alignment).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `LayoutErr` if `alignment` is rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniVec::from_slice_aligned(&[1.0_f32, 2.0, 3.0, 4.0], 64).unwrap();
+    ///
+    /// assert_eq!(vec, [1.0, 2.0, 3.0, 4.0]);
+    /// assert_eq!(vec.as_ptr() as usize % 64, 0);
+    /// ```
+    ///
+    pub fn from_slice_aligned(elems: &[T], alignment: usize) -> Result<MiniVec<T>, LayoutErr>
+    where
+        T: Clone,
+    {
+        let mut v = MiniVec::with_alignment(elems.len(), alignment)?;
+        v.extend_from_slice(elems);
+        Ok(v)
+    }
+
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn from_static<const N: usize>(storage: &'static StaticStorage<T, N>) -> MiniVec<T>
+    where
+        T: Copy + Sync,
+    {
+        let buf = if core::mem::size_of::<T>() == 0 {
+            zst_buf(N)
+        } else if N == 0 {
+            empty_header()
+        } else {
+            unsafe {
+                core::ptr::NonNull::new_unchecked(
+                    core::ptr::from_ref(storage).cast::<u8>().cast_mut(),
+                )
+            }
+        };
+
+        MiniVec {
+            buf,
+            allocator: Global,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// `into_concurrent` turns the vector into a [`ConcurrentMiniVec`](ConcurrentMiniVec) which
+    /// many threads can append to at once, up to its current capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniVec::<i32>::with_capacity(8).into_concurrent();
+    ///
+    /// std::thread::scope(|s| {
+    ///     s.spawn(|| vec.push_within_capacity(1).unwrap());
+    ///     s.spawn(|| vec.push_within_capacity(1).unwrap());
+    /// });
+    ///
+    /// assert_eq!(vec.into_inner(), [1, 1]);
+    /// ```
+    ///
+    #[must_use]
+    pub fn into_concurrent(self) -> ConcurrentMiniVec<T> {
+        ConcurrentMiniVec::from(self)
+    }
+
+    /// `into_raw` consumes the vector and returns a single pointer that owns its allocation. The
+    /// vector can be rebuilt with [`from_raw`](MiniVec::from_raw).
+    ///
+    /// For vectors that have allocated, the returned pointer is the same as [`as_ptr()`](MiniVec::as_ptr)
+    /// so it may be handed to APIs expecting a `*mut T` to `len()` elements. Unallocated vectors
+    /// return a null pointer. Vectors of zero-sized types return an opaque, non-null pointer which
+    /// encodes their length and must not be dereferenced.
+    ///
+    /// The header of an allocated vector can always be found from the returned pointer: the
+    /// `usize` immediately preceding the first element holds the alignment of the allocation,
+    /// `alignment`, and the header itself begins `size_of::<[usize; 3]>()` bytes, rounded up to
+    /// the next multiple of `alignment`, before the first element. The header stores the length,
+    /// capacity and alignment of the vector, in that order, as `usize` values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniVec::<u32>::with_alignment(8, 64).unwrap();
+    /// vec.extend_from_slice(&[1, 2, 3]);
+    ///
+    /// let ptr = vec.into_raw();
+    ///
+    /// unsafe {
+    ///     let alignment = *(ptr as *const usize).sub(1);
+    ///     assert_eq!(alignment, 64);
+    ///
+    ///     let offset = (3 * core::mem::size_of::<usize>() + alignment - 1) / alignment * alignment;
+    ///     let header = (ptr as *const u8).sub(offset) as *const usize;
+    ///     assert_eq!(*header, 3);
+    ///     assert_eq!(*header.add(1), 8);
+    ///
+    ///     drop(minivec::MiniVec::from_raw(ptr));
+    /// }
+    /// ```
+    ///
+    #[must_use]
+    pub fn into_raw(self) -> *mut T {
+        let v = core::mem::ManuallyDrop::new(self);
+
+        if core::mem::size_of::<T>() == 0 {
+            v.buf.as_ptr().cast::<T>()
+        } else if v.is_allocated() {
+            v.data()
+        } else {
+            core::ptr::null_mut()
+        }
+    }
+
+    /// `MiniVec::new` constructs an empty `MiniVec`.
+    ///
+    /// Note: does not allocate any memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniVec::<i32>::new();
+    ///
+    /// assert_eq!(vec.as_mut_ptr(), std::ptr::null_mut());
+    /// assert_eq!(vec.len(), 0);
+    /// assert_eq!(vec.capacity(), 0);
+    /// ```
+    ///
+    #[must_use]
+    pub const fn new() -> MiniVec<T> {
+        MiniVec::new_in(Global)
+    }
+
+    /// `try_with_alignment` is the fallible counterpart to [`with_alignment`](MiniVec::with_alignment).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError::InvalidAlignment`](TryReserveError::InvalidAlignment) if the
+    /// supplied alignment is rejected and any other [`TryReserveError`](TryReserveError) if the
+    /// allocation itself cannot be made.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniVec::<f32>::try_with_alignment(64, 32).unwrap();
+    /// assert_eq!(vec.as_ptr() as usize % 32, 0);
+    ///
+    /// assert_eq!(
+    ///     minivec::MiniVec::<f32>::try_with_alignment(64, 2),
+    ///     Err(minivec::TryReserveError::InvalidAlignment(minivec::LayoutErr::AlignmentTooSmall))
+    /// );
+    /// ```
+    ///
+    pub fn try_with_alignment(
+        capacity: usize,
+        alignment: usize,
+    ) -> Result<MiniVec<T>, TryReserveError> {
+        MiniVec::try_with_alignment_in(capacity, alignment, Global)
+    }
+
+    /// `try_with_capacity` is the fallible counterpart to [`with_capacity`](MiniVec::with_capacity).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](TryReserveError) if the allocation for `capacity` elements
+    /// cannot be made.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniVec::<i32>::try_with_capacity(128).unwrap();
+    /// assert_eq!(vec.capacity(), 128);
+    ///
+    /// assert!(minivec::MiniVec::<i32>::try_with_capacity(usize::MAX).is_err());
+    /// ```
+    ///
+    pub fn try_with_capacity(capacity: usize) -> Result<MiniVec<T>, TryReserveError> {
+        MiniVec::try_with_capacity_in(capacity, Global)
+    }
+
+    /// `with_alignment` is similar to its counterpart [`with_capacity`](MiniVec::with_capacity)
+    /// except it takes an additional argument: the alignment to use for the allocation.
+    ///
+    /// The supplied alignment must be a power of two and larger than or equal to the result of
+    /// `core::mem::align_of::<*const ()>()`.
+    ///
+    /// The internal allocation used to store the header information for `MiniVec` is aligned to the
+    /// supplied value and then sufficient padding is inserted such that the result of [`as_ptr()`](MiniVec::as_ptr)
+    /// will always be aligned as well.
+    ///
+    /// This is useful for creating over-aligned allocations for primitive types such as when using
+    /// `SIMD` intrinsics. For example, some vectorized floating point loads and stores _must_ be
+    /// aligned on a 32 byte boundary. `with_alignment` is intended to make this possible with a
+    /// `Vec`-like container.
+    ///
+    /// Vectors of zero-sized types never allocate so for them the alignment is only validated.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LayoutErr`](LayoutErr) if the alignment is rejected or `capacity` elements
+    /// don't fit in an allocation at that alignment.
+    ///
+    /// # Example
+    /// ```
+    /// #[cfg(target_arch = "x86")]
+    /// use std::arch::x86::*;
+    /// #[cfg(target_arch = "x86_64")]
+    /// use std::arch::x86_64::*;
+    ///
+    /// let alignment = 32;
+    /// let num_elems = 2048;
+    /// let mut v1 = minivec::MiniVec::<f32>::with_alignment(num_elems, alignment).unwrap();
+    /// let mut v2 = minivec::MiniVec::<f32>::with_alignment(num_elems, alignment).unwrap();
+    ///
+    /// v1
+    ///     .spare_capacity_mut()
+    ///     .iter_mut()
+    ///     .zip(v2.spare_capacity_mut().iter_mut())
+    ///     .enumerate()
+    ///     .for_each(|(idx, (x1, x2))| {
+    ///         *x1 = core::mem::MaybeUninit::new(idx as f32);
+    ///         *x2 = core::mem::MaybeUninit::new(idx as f32);
+    ///     });
+    ///
+    /// unsafe {
+    ///     v1.set_len(num_elems);
+    ///     v2.set_len(num_elems);
+    ///
+    ///     // use vectorization to speed up the summation of two vectors
+    ///     //
+    ///     for idx in 0..(num_elems / 8) {
+    ///         let offset = idx * 8;
+    ///
+    ///         let p = v1.as_mut_ptr().add(offset);
+    ///         let q = v2.as_mut_ptr().add(offset);
+    ///
+    ///         let r1 = _mm256_load_ps(p);
+    ///         let r2 = _mm256_load_ps(q);
+    ///         let r3 = _mm256_add_ps(r1, r2);
+    ///
+    ///         _mm256_store_ps(p, r3);
+    ///     }
+    /// }
+    ///
+    /// v1
+    ///     .iter()
+    ///     .enumerate()
+    ///     .for_each(|(idx, v)| {
+    ///         assert_eq!(*v, idx as f32 * 2.0);
+    ///     });
+    /// ```
+    ///
+    pub fn with_alignment(capacity: usize, alignment: usize) -> Result<MiniVec<T>, LayoutErr> {
+        MiniVec::with_alignment_in(capacity, alignment, Global)
+    }
+
+    /// `with_capacity` is a static factory function that returns a `MiniVec` that contains space
+    /// for `capacity` elements.
+    ///
+    /// This function is logically equivalent to calling [`.reserve_exact()`](MiniVec::reserve_exact)
+    /// on a vector with `0` capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniVec::<i32>::with_capacity(128);
+    ///
+    /// assert_eq!(vec.len(), 0);
+    /// assert_eq!(vec.capacity(), 128);
+    /// ```
+    ///
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> MiniVec<T> {
+        MiniVec::with_capacity_in(capacity, Global)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, H> MiniVec<T, Global, H> {
+    /// `with_ext` constructs an empty `MiniVec` whose allocation carries the header extension
+    /// `ext` in front of the data, see [`ext`](MiniVec::ext).
+    ///
+    /// Note: allocates room for the header and the extension even though the vector is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `T` or `H` is a zero-sized type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minivec::{Global, MiniVec};
+    ///
+    /// let mut vec = MiniVec::<u8, Global, u64>::with_ext(7);
+    /// vec.extend_from_slice(&[0; 512]);
+    ///
+    /// assert_eq!(vec.ext(), Some(&7));
+    /// ```
+    ///
+    #[must_use]
+    pub fn with_ext(ext: H) -> MiniVec<T, Global, H> {
+        MiniVec::with_ext_in(ext, Global)
+    }
+}
+
+impl<T, A: Allocator> MiniVec<T, A> {
+    /// `from_raw_parts_in` is the allocator-aware version of [`from_raw_parts`](MiniVec::from_raw_parts).
+    ///
+    /// # Safety
+    ///
+    /// In addition to the requirements of `from_raw_parts`, the allocation must have been made by
+    /// `allocator`, i.e. `ptr` should come from [`into_raw_parts_with_alloc`](MiniVec::into_raw_parts_with_alloc).
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::mini_vec![1, 2, 3, 4];
+    ///
+    /// let (ptr, len, cap, alloc) = vec.into_raw_parts_with_alloc();
+    ///
+    /// let new_vec = unsafe { minivec::MiniVec::from_raw_parts_in(ptr, len, cap, alloc) };
+    ///
+    /// assert_eq!(new_vec, [1, 2, 3, 4]);
+    /// ```
+    ///
+    #[allow(clippy::cast_ptr_alignment)]
+    pub unsafe fn from_raw_parts_in(
+        ptr: *mut T,
+        length: usize,
+        capacity: usize,
+        allocator: A,
+    ) -> MiniVec<T, A> {
+        if core::mem::size_of::<T>() == 0 {
+            let mut v = MiniVec::new_in(allocator);
+            v.set_len(length);
+            return v;
+        }
+
+        let v = MiniVec {
+            buf: header_from_data(ptr.cast::<u8>()),
+            allocator,
+            phantom: core::marker::PhantomData,
+        };
+
+        debug_assert!(v.len() == length);
+        debug_assert!(v.capacity() == capacity);
+
+        v
+    }
+
+    /// `into_frozen` converts the vector into a [`MiniBox<[T]>`](MiniBox), a single-pointer boxed
+    /// slice that only stores its length in front of the data.
+    ///
+    /// The allocation is shrunk to fit and, unless the vector is over-aligned, reused.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniVec::with_capacity(128);
+    /// vec.extend_from_slice(&[1, 2, 3]);
+    ///
+    /// let frozen: minivec::MiniBox<[i32]> = vec.into_frozen();
+    /// assert_eq!(frozen, [1, 2, 3]);
+    /// ```
+    ///
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn into_frozen(self) -> MiniBox<[T], A> {
+        MiniBox::from(self)
+    }
+
+    /// `into_raw_parts` will leak the underlying allocation and return a tuple containing a pointer
+    /// to the start of the backing array and its length and capacity.
+    ///
+    /// The results of this function are directly compatible with [`from_raw_parts`](MiniVec::from_raw_parts).
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::mini_vec![1, 2, 3, 4, 5];
+    /// let (old_len, old_cap) = (vec.len(), vec.capacity());
+    ///
+    /// let (ptr, len, cap) = vec.into_raw_parts();
+    /// assert_eq!(len, old_len);
+    /// assert_eq!(cap, old_cap);
+    ///
+    /// let vec = unsafe { minivec::MiniVec::from_raw_parts(ptr, len, cap) };
+    /// assert_eq!(vec, [1, 2, 3, 4, 5]);
+    /// ```
+    ///
+    #[must_use]
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let mut v = core::mem::ManuallyDrop::new(self);
+        (v.as_mut_ptr(), v.len(), v.capacity())
+    }
+
+    /// `into_raw_parts_with_alloc` is similar to [`into_raw_parts`](MiniVec::into_raw_parts) but
+    /// additionally returns the allocator the vector was using.
+    ///
+    /// The results of this function are directly compatible with [`from_raw_parts_in`](MiniVec::from_raw_parts_in).
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::mini_vec![1, 2, 3, 4, 5];
+    ///
+    /// let (ptr, len, cap, alloc) = vec.into_raw_parts_with_alloc();
+    ///
+    /// let vec = unsafe { minivec::MiniVec::from_raw_parts_in(ptr, len, cap, alloc) };
+    /// assert_eq!(vec, [1, 2, 3, 4, 5]);
+    /// ```
+    ///
+    #[must_use]
+    pub fn into_raw_parts_with_alloc(self) -> (*mut T, usize, usize, A) {
+        let mut v = core::mem::ManuallyDrop::new(self);
+        let allocator = unsafe { core::ptr::read(core::ptr::addr_of!(v.allocator)) };
+        (v.as_mut_ptr(), v.len(), v.capacity(), allocator)
+    }
+
+    /// `new_in` constructs an empty `MiniVec` which will use `allocator` for all of its memory.
+    ///
+    /// Note: does not allocate any memory.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::MiniVec::<i32, _>::new_in(minivec::Global);
+    ///
+    /// assert_eq!(vec.len(), 0);
+    /// assert_eq!(vec.capacity(), 0);
+    ///
+    /// vec.push(1);
+    /// assert_eq!(vec, [1]);
+    /// ```
+    ///
+    pub const fn new_in(allocator: A) -> MiniVec<T, A> {
+        MiniVec::empty_in(allocator)
+    }
+
+    /// `try_with_alignment_in` is the allocator-aware version of [`try_with_alignment`](MiniVec::try_with_alignment).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError::InvalidAlignment`](TryReserveError::InvalidAlignment) if the
+    /// supplied alignment is rejected and any other [`TryReserveError`](TryReserveError) if the
+    /// allocation itself cannot be made.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::MiniVec::<f32, _>::try_with_alignment_in(64, 32, minivec::Global).unwrap();
+    /// assert_eq!(vec.as_ptr() as usize % 32, 0);
+    /// ```
+    ///
+    pub fn try_with_alignment_in(
+        capacity: usize,
+        alignment: usize,
+        allocator: A,
+    ) -> Result<MiniVec<T, A>, TryReserveError> {
+        validate_alignment::<T, ()>(alignment).map_err(TryReserveError::InvalidAlignment)?;
+
+        let mut v = MiniVec::new_in(allocator);
+        v.try_grow(capacity, alignment)?;
+        Ok(v)
+    }
+
+    /// `try_with_capacity_in` is the allocator-aware version of [`try_with_capacity`](MiniVec::try_with_capacity).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](TryReserveError) if the allocation for `capacity` elements
+    /// cannot be made.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::MiniVec::<i32, _>::try_with_capacity_in(128, minivec::Global).unwrap();
+    /// assert_eq!(vec.capacity(), 128);
+    /// ```
+    ///
+    pub fn try_with_capacity_in(
+        capacity: usize,
+        allocator: A,
+    ) -> Result<MiniVec<T, A>, TryReserveError> {
+        let mut v = MiniVec::new_in(allocator);
+        v.try_reserve_exact(capacity)?;
+        Ok(v)
+    }
+
+    /// `with_alignment_in` is the allocator-aware version of [`with_alignment`](MiniVec::with_alignment).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Result` that contains either `MiniVec<T, A>` or a `LayoutErr`.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::MiniVec::<f32, _>::with_alignment_in(64, 32, minivec::Global).unwrap();
+    /// assert_eq!(vec.as_ptr() as usize % 32, 0);
+    /// ```
+    ///
+    pub fn with_alignment_in(
+        capacity: usize,
+        alignment: usize,
+        allocator: A,
+    ) -> Result<MiniVec<T, A>, LayoutErr> {
+        match MiniVec::try_with_alignment_in(capacity, alignment, allocator) {
+            Ok(v) => Ok(v),
+            Err(TryReserveError::InvalidAlignment(err)) => Err(err),
+            Err(TryReserveError::CapacityOverflow) => Err(LayoutErr::SizeOverflow),
+            Err(err) => handle_reserve_error(err),
+        }
+    }
+
+    /// `with_capacity_in` is the allocator-aware version of [`with_capacity`](MiniVec::with_capacity).
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::MiniVec::<i32, _>::with_capacity_in(128, minivec::Global);
+    ///
+    /// assert_eq!(vec.len(), 0);
+    /// assert_eq!(vec.capacity(), 128);
+    /// ```
+    ///
+    pub fn with_capacity_in(capacity: usize, allocator: A) -> MiniVec<T, A> {
+        match MiniVec::try_with_capacity_in(capacity, allocator) {
+            Ok(v) => v,
+            Err(err) => handle_reserve_error(err),
+        }
+    }
+}
+
+impl<T, A: Allocator, H> MiniVec<T, A, H> {
+    const fn empty_in(allocator: A) -> Self {
+        let buf = if core::mem::size_of::<T>() == 0 {
+            zst_buf(0)
+        } else {
+            empty_header()
+        };
+
+        MiniVec {
+            buf,
+            allocator,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    fn ext_ptr(&self) -> *mut Option<H> {
+        debug_assert!(self.is_allocated() && core::mem::size_of::<H>() > 0);
+
+        unsafe { self.buf.as_ptr().add(ext_offset::<H>()).cast::<Option<H>>() }
+    }
+
+    fn clone_ext_from(&mut self, other: &Self)
+    where
+        H: Clone,
+    {
+        if let Some(ext) = other.ext() {
+            self.set_ext(ext.clone());
+        }
+    }
+
+    fn header(&self) -> &Header {
+        debug_assert!(core::mem::size_of::<T>() > 0);
+
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            self.buf.cast::<Header>().as_ref()
+        }
+    }
+
+    fn header_mut(&mut self) -> &mut Header {
+        debug_assert!(self.is_allocated());
+
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            self.buf.cast::<Header>().as_mut()
+        }
+    }
+
+    fn is_allocated(&self) -> bool {
+        core::mem::size_of::<T>() > 0 && self.buf != empty_header()
+    }
+
+    fn is_static(&self) -> bool {
+        self.is_allocated() && self.header().alignment & BORROWED != 0
+    }
+
+    fn data(&self) -> *mut T {
+        // zero-sized types never allocate so every element lives at the same dangling, but
+        // suitably aligned, address
+        //
+        // we use the same address for unallocated vectors so that we never hand out pointers
+        // into the shared empty header
+        //
+        if !self.is_allocated() {
+            return core::ptr::NonNull::dangling().as_ptr();
+        }
+
+        let count = data_offset::<H>(self.layout_alignment());
+        unsafe { self.buf.as_ptr().add(count).cast::<T>() }
+    }
+
+    // the alignment the allocation was laid out with, which can be smaller than the one reported
+    // by `alignment()` after an in-place `realign`
+    //
+    fn layout_alignment(&self) -> usize {
+        if self.is_allocated() {
+            layout_alignment_of(self.header().alignment)
+        } else {
+            min_alignment::<T, H>()
+        }
+    }
+
+    fn try_grow(&mut self, capacity: usize, alignment: usize) -> Result<(), TryReserveError> {
+        debug_assert!(capacity >= self.len());
+
+        if core::mem::size_of::<T>() == 0 {
+            return Ok(());
+        }
+
+        // the alignment of an existing allocation is fixed but an unallocated vector can still
+        // pick one, in which case we allocate even if only to store the header. An allocation that
+        // had its alignment raised in place is laid out for the new one when it's next moved
+        //
+        debug_assert!(!self.is_allocated() || alignment == self.alignment());
+
+        let old_capacity = self.capacity();
+        let new_capacity = capacity;
+
+        if new_capacity == old_capacity && alignment == self.alignment() {
+            return Ok(());
+        }
+
+        let new_layout = try_make_layout::<T, H>(new_capacity, alignment)?;
+
+        let len = self.len();
+        let was_allocated = self.is_allocated();
+
+        if was_allocated && alignment != self.layout_alignment() {
+            return self.try_relocate(new_capacity, alignment);
+        }
+
+        let new_buf = if was_allocated {
+            let old_layout = try_make_layout::<T, H>(old_capacity, alignment)?;
+            let old_buf = self.buf;
+
+            if new_layout.size() >= old_layout.size() {
+                unsafe { self.allocator.grow(old_buf, old_layout, new_layout) }
+            } else {
+                unsafe { self.allocator.shrink(old_buf, old_layout, new_layout) }
+            }
+        } else {
+            self.allocator.allocate(new_layout)
+        };
+
+        let new_buf = match new_buf {
+            Ok(p) => p.cast::<u8>(),
+            Err(_) => return Err(TryReserveError::AllocError { layout: new_layout }),
+        };
+
+        let header = Header {
+            len,
+            cap: new_capacity,
+            alignment,
+        };
+
+        unsafe { write_header::<H>(new_buf, header) };
+
+        self.buf = new_buf;
+
+        // the extension is carried along by the reallocation and only needs initializing once
+        //
+        if !was_allocated && core::mem::size_of::<H>() > 0 {
+            unsafe { self.ext_ptr().write(None) };
+        }
+
+        Ok(())
+    }
+
+    // the largest capacity the vector may grow to, which is bounded by the address space or, for
+    // allocators confined to a fixed region, by what's left of that region
+    //
+    fn capacity_limit(&self, alignment: usize) -> usize {
+        let max = max_capacity::<T, H>(alignment);
+        if core::mem::size_of::<T>() == 0 {
+            return max;
+        }
+
+        let ptr = if self.is_allocated() {
+            Some(self.buf)
+        } else {
+            None
+        };
+
+        match unsafe { self.allocator.size_limit(ptr) } {
+            Some(bytes) => core::cmp::min(max, capacity_in::<T, H>(bytes, alignment)),
+            None => max,
+        }
+    }
+
+    fn grow(&mut self, capacity: usize, alignment: usize) {
+        if let Err(err) = self.try_grow(capacity, alignment) {
+            handle_reserve_error(err);
+        }
+    }
+
+    // moves the elements and the extension into a new allocation laid out for `alignment` as the
+    // allocator can't change the alignment of an existing one
+    //
+    fn try_relocate(&mut self, capacity: usize, alignment: usize) -> Result<(), TryReserveError> {
+        debug_assert!(self.is_allocated() && !self.is_static() && capacity >= self.len());
+
+        let len = self.len();
+
+        let new_layout = try_make_layout::<T, H>(capacity, alignment)?;
+        let new_buf = match self.allocator.allocate(new_layout) {
+            Ok(p) => p.cast::<u8>(),
+            Err(_) => return Err(TryReserveError::AllocError { layout: new_layout }),
+        };
+
+        let header = Header {
+            len,
+            cap: capacity,
+            alignment,
+        };
+
+        let old_buf = self.buf;
+        let old_layout = make_layout::<T, H>(self.capacity(), self.layout_alignment());
+        let old_data = self.data();
+        let old_ext = if core::mem::size_of::<H>() > 0 {
+            self.ext_ptr()
+        } else {
+            core::ptr::null_mut()
+        };
+
+        unsafe { write_header::<H>(new_buf, header) };
+
+        self.buf = new_buf;
+
+        unsafe {
+            if !old_ext.is_null() {
+                core::ptr::copy_nonoverlapping(old_ext, self.ext_ptr(), 1);
+            }
+
+            core::ptr::copy_nonoverlapping(old_data, self.data(), len);
+            self.allocator.deallocate(old_buf, old_layout);
+        };
+
+        Ok(())
+    }
+
+    // appends every element of `iter`, reserving its lower bound up front and writing straight
+    // into the spare capacity through a raw pointer so that the length is committed once per
+    // batch. An exact size hint, as reported by `TrustedLen` iterators, fills the vector in a
+    // single batch
+    //
+    // batches are consumed via `for_each` which lets adapters such as `Chain` or `FlatMap` drive
+    // the loop themselves
+    //
+    // the size hint is only trusted for performance, never for safety: an iterator yielding more
+    // than it claimed makes us reserve again, one yielding less just leaves spare capacity, and
+    // one that panics leaves every element written so far owned by the vector
+    //
+    fn extend_iter<I>(&mut self, mut iter: I)
+    where
+        I: Iterator<Item = T>,
+    {
+        loop {
+            self.reserve(iter.size_hint().0);
+
+            let len = self.len();
+            let spare = self.capacity() - len;
+
+            if spare == 0 {
+                match iter.next() {
+                    Some(x) => self.push(x),
+                    None => return,
+                }
+                continue;
+            }
+
+            let dst = unsafe { self.data().add(len) };
+            let head = unaligned_head(dst, spare);
+            let mut guard = SetLenOnDrop { vec: self, len };
+
+            // as in `fill_with_clones`, the unaligned head is written separately
+            //
+            for end in [head, spare] {
+                let written = guard.len - len;
+                let mut ptr = unsafe { dst.add(written) };
+
+                iter.by_ref().take(end - written).for_each(|x| unsafe {
+                    ptr.write(x);
+                    ptr = ptr.add(1);
+                    guard.len += 1;
+                });
+
+                if guard.len < len + end {
+                    return;
+                }
+            }
+        }
+    }
+
+    // vectors created by `static_mini_vec!` borrow their elements from static memory which must
+    // never be written to so every mutating operation first copies them into an allocation of
+    // their own
+    //
+    // the elements of a static vector are `Copy` so the originals are simply left in place
+    //
+    fn unshare(&mut self) {
+        if !self.is_static() {
+            return;
+        }
+
+        let (len, alignment, src) = (self.len(), self.alignment(), self.data());
+
+        self.buf = empty_header();
+        self.grow(len, alignment);
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(src, self.data(), len);
+            self.set_len(len);
+        }
+    }
+
+    // empties the vector after its elements have been moved elsewhere without dropping them. A
+    // static vector lets go of its storage instead as its header must never be written to
+    //
+    unsafe fn forget_elements(&mut self) {
+        if self.is_static() {
+            self.buf = empty_header();
+        } else {
+            self.set_len(0);
+        }
+    }
+
+    /// `alignment` returns the alignment of the vector's allocation. The pointer returned by
+    /// [`as_ptr()`](MiniVec::as_ptr) is always aligned to this value once the vector has allocated.
+    ///
+    /// Vectors that haven't allocated, and vectors of zero-sized types, report the default
+    /// alignment which is the largest of `T`'s alignment, the alignment of the internal header and
+    /// the alignment of the header extension.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::MiniVec::<i32>::with_alignment(16, 64).unwrap();
+    /// assert_eq!(vec.alignment(), 64);
+    ///
+    /// let vec = minivec::mini_vec![1, 2, 3];
+    /// assert_eq!(vec.alignment(), core::mem::align_of::<usize>());
+    /// ```
+    ///
+    #[must_use]
+    pub fn alignment(&self) -> usize {
+        if self.is_allocated() {
+            reported_alignment_of(self.header().alignment)
+        } else {
+            min_alignment::<T, H>()
+        }
+    }
+
+    /// `allocator` returns a reference to the allocator backing this vector.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::mini_vec![1, 2, 3];
+    /// let alloc: &minivec::Global = vec.allocator();
+    /// ```
+    ///
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+
+    /// `append` moves every element from `other` to the back of `self`. `other.is_empty()` is
+    /// `true` once this operation completes and its capacity is unaffected.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1, 2, 3];
+    /// let mut vec2 = minivec::mini_vec![4, 5, 6];
+    /// vec.append(&mut vec2);
+    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(vec2, []);
+    /// ```
+    ///
+    pub fn append(&mut self, other: &mut MiniVec<T, A, H>) {
+        self.unshare();
+
+        if other.is_empty() {
+            return;
+        }
+
+        let other_len = other.len();
+        self.reserve(other_len);
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                other.as_ptr(),
+                self.as_mut_ptr().add(self.len()),
+                other_len,
+            );
+        };
+
+        unsafe {
+            other.forget_elements();
+            self.set_len(self.len() + other_len);
+        };
+    }
+
+    /// `as_mut_ptr` returns a `*mut T` to the underlying array.
+    ///
+    /// * May return a null pointer.
+    /// * May be invalidated by calls to [`reserve()`](MiniVec::reserve)
+    /// * Can outlive its backing `MiniVec`
+    ///
+    /// # Example
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
+    /// let mut p = vec.as_mut_ptr();
+    ///
+    /// for idx in 0..vec.len() {
+    ///     unsafe {
+    ///         *p.add(idx) = *p.add(idx) + 3;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(vec, [4, 5, 6, 7]);
+    /// ```
+    ///
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.unshare();
+
+        if !self.is_allocated() && core::mem::size_of::<T>() > 0 {
+            return core::ptr::null_mut();
+        }
+
+        self.data()
+    }
+
+    /// `as_mut_slice` obtains a mutable reference to a slice that's attached to the backing array.
+    ///
+    /// # Example
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1, 2, 3];
+    /// {
+    ///     let as_slice: &mut [_] = vec.as_mut_slice();
+    ///     as_slice[0] = 1337;
+    /// }
+    /// assert_eq!(vec[0], 1337);
+    /// ```
+    ///
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    /// `as_ptr` obtains a `*const T` to the underlying allocation.
+    ///
+    /// * May return a null pointer.
+    /// * May be invalidated by calls to `reserve()`
+    /// * Can outlive its backing `MiniVec`
+    ///
+    /// # Example
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
+    /// let mut p = vec.as_mut_ptr();
+    ///
+    /// let mut sum = 0;
+    /// for idx in 0..vec.len() {
+    ///     unsafe {
+    ///         sum += *p.add(idx);
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(sum, 1 + 2 + 3 + 4);
+    /// ```
+    ///
+    #[must_use]
+    pub fn as_ptr(&self) -> *const T {
+        if !self.is_allocated() && core::mem::size_of::<T>() > 0 {
+            return core::ptr::null();
+        }
+
+        self.data()
+    }
+
+    /// `as_slice` obtains a reference to the backing array as an immutable slice of `T`.
+    ///
+    /// # Example
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::mini_vec![1, 2, 3, 4];
+    /// let mut sum = 0;
     ///
     /// let as_slice : &[_] = vec.as_slice();
     ///
@@ -270,21 +1616,26 @@ impl<T> MiniVec<T> {
     /// `capacity` obtains the number of elements that can be inserted into the `MiniVec` before a
     /// reallocation will be required.
     ///
-    /// Note: `MiniVec` aims to use the same reservation policy as `alloc::vec::Vec`.
+    /// Note: `MiniVec` aims to use the same reservation policy as `alloc::vec::Vec`. Like `Vec`,
+    /// vectors of zero-sized types never allocate and always report a capacity of `usize::MAX`.
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let vec = minivec::MiniVec::<i32>::with_capacity(128);
     ///
     /// assert_eq!(vec.len(), 0);
     /// assert_eq!(vec.capacity(), 128);
+    ///
+    /// let vec = minivec::MiniVec::<()>::with_capacity(128);
+    /// assert_eq!(vec.capacity(), usize::MAX);
     /// ```
     ///
     #[must_use]
     pub fn capacity(&self) -> usize {
-        if self.buf.is_null() {
-            0
+        if core::mem::size_of::<T>() == 0 {
+            usize::MAX
         } else {
             self.header().cap
         }
@@ -299,7 +1650,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![-1; 256];
     ///
     /// let cap = vec.capacity();
@@ -322,7 +1674,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut v = minivec::mini_vec![1, 2, 1, 1, 3, 3, 3, 4, 5, 4];
     /// v.dedup();
     ///
@@ -341,7 +1694,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
     ///
     /// vec.dedup_by(|x, y| *x + *y < 8);
@@ -353,6 +1707,8 @@ impl<T> MiniVec<T> {
     where
         F: FnMut(&mut T, &mut T) -> bool,
     {
+        self.unshare();
+
         // In essence copy what the C++ stdlib does:
         // https://github.com/llvm/llvm-project/blob/032810f58986cd568980227c9531de91d8bcb1cd/libcxx/include/algorithm#L2174-L2191
         //
@@ -363,31 +1719,33 @@ impl<T> MiniVec<T> {
 
         let data = self.as_mut_ptr();
 
-        let mut read = unsafe { data.add(1) };
-        let mut write = read;
-
-        let last = unsafe { data.add(len) };
+        // we track positions by index instead of by pointer so that this works for zero-sized
+        // types as well
+        //
+        let mut read = 1;
+        let mut write = 1;
 
-        while read < last {
-            let matches = unsafe { pred(&mut *read, &mut *write.sub(1)) };
+        while read < len {
+            let matches = unsafe { pred(&mut *data.add(read), &mut *data.add(write - 1)) };
             if !matches {
                 if read != write {
-                    unsafe { core::mem::swap(&mut *read, &mut *write) };
+                    unsafe { core::ptr::swap(data.add(read), data.add(write)) };
                 }
-                write = unsafe { write.add(1) };
+                write += 1;
             }
 
-            read = unsafe { read.add(1) };
+            read += 1;
         }
 
-        self.truncate((write as usize - data as usize) / core::mem::size_of::<T>());
+        self.truncate(write);
     }
 
     /// `dedup_by_key` "de-duplicates" all adjacent elements where `key(elem1) == key(elem2)`.
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec!["a", "b", "c", "aa", "bbb", "cc", "dd"];
     ///
     /// vec.dedup_by_key(|x| x.len());
@@ -409,11 +1767,14 @@ impl<T> MiniVec<T> {
     /// If the returned iterator is not iterated until exhaustion then the `Drop` implementation
     /// for `Drain` will remove the remaining elements.
     ///
-    /// Note: panics if the supplied range would be outside the vector
+    /// # Panics
+    ///
+    /// Panics if the supplied range would be outside the vector.
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
     ///
     /// let other_vec : minivec::MiniVec<_> = vec.drain(1..7).map(|x| x + 2).collect();
@@ -422,10 +1783,12 @@ impl<T> MiniVec<T> {
     /// assert_eq!(other_vec, [4, 5, 6, 7, 8, 9]);
     /// ```
     ///
-    pub fn drain<R>(&mut self, range: R) -> Drain<T>
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, A, H>
     where
         R: core::ops::RangeBounds<usize>,
     {
+        self.unshare();
+
         let len = self.len();
 
         let start_idx = match range.start_bound() {
@@ -440,21 +1803,21 @@ impl<T> MiniVec<T> {
             core::ops::Bound::Unbounded => len,
         };
 
-        if start_idx > end_idx {
-            panic!(
-                "start drain index (is {}) should be <= end drain index (is {})",
-                start_idx, end_idx
-            );
-        }
+        assert!(
+            start_idx <= end_idx,
+            "start drain index (is {}) should be <= end drain index (is {})",
+            start_idx,
+            end_idx
+        );
 
-        if end_idx > len {
-            panic!(
-                "end drain index (is {}) should be <= len (is {})",
-                end_idx, len
-            );
-        }
+        assert!(
+            end_idx <= len,
+            "end drain index (is {}) should be <= len (is {})",
+            end_idx,
+            len
+        );
 
-        let data = self.as_mut_ptr();
+        let data = self.data();
 
         unsafe { self.set_len(start_idx) };
 
@@ -471,7 +1834,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![
     ///     1, 2, 4, 6, 7, 9, 11, 13, 15, 17, 18, 20, 22, 24, 26, 27, 29, 31, 33, 34, 35, 36, 37,
     ///     39,
@@ -488,97 +1852,67 @@ impl<T> MiniVec<T> {
     /// );
     /// ```
     ///
-    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, T, F>
+    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, T, F, A, H>
     where
         F: core::ops::FnMut(&mut T) -> bool,
     {
+        self.unshare();
+
         make_drain_filter_iterator(self, pred)
     }
 
-    /// `from_raw_part` reconstructs a `MiniVec` from a previous call to [`MiniVec::as_mut_ptr`](MiniVec::as_mut_ptr)
-    /// or the pointer from [`into_raw_parts`](MiniVec::into_raw_parts).
+    /// `ext` returns a reference to the header extension stored in the vector's allocation, if one
+    /// has been set via [`set_ext`](MiniVec::set_ext) or [`with_ext`](MiniVec::with_ext).
     ///
-    /// # Safety
-    ///
-    /// `from_raw_part` is incredibly unsafe and can only be used with the value of
-    /// `MiniVec::as_mut_ptr`. This is because the allocation for the backing array stores metadata
-    /// at its head and is not guaranteed to be stable so users are discouraged from attempting to
-    /// support this directly.
+    /// Note: vectors that haven't allocated have nowhere to store an extension and always return
+    /// `None`, as do vectors whose extension type is zero-sized such as the default of `()`.
     ///
     /// # Example
     ///
-    /// ```
-    /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
-    ///
-    /// let ptr = vec.as_mut_ptr();
-    ///
-    /// std::mem::forget(vec);
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// use minivec::{Global, MiniVec};
     ///
-    /// let new_vec = unsafe { minivec::MiniVec::from_raw_part(ptr) };
+    /// let mut vec = MiniVec::<i32, Global, u64>::with_ext(0xdead_beef);
+    /// vec.push(1);
     ///
-    /// assert_eq!(new_vec, [1, 2, 3, 4]);
+    /// assert_eq!(vec.ext(), Some(&0xdead_beef));
+    /// assert_eq!(MiniVec::<i32, Global, u64>::default().ext(), None);
     /// ```
     ///
-    #[allow(clippy::cast_ptr_alignment)]
-    pub unsafe fn from_raw_part(ptr: *mut T) -> MiniVec<T> {
-        debug_assert!(!ptr.is_null());
-
-        let header_size = core::mem::size_of::<Header>();
-        let aligned = next_aligned(header_size, core::mem::align_of::<T>());
-
-        let p = ptr as *mut u8;
-        let buf = p.sub(aligned);
-
-        MiniVec {
-            buf,
-            phantom: core::marker::PhantomData,
+    #[must_use]
+    pub fn ext(&self) -> Option<&H> {
+        if core::mem::size_of::<H>() == 0 || !self.is_allocated() {
+            return None;
         }
+
+        unsafe { (*self.ext_ptr()).as_ref() }
     }
 
-    /// `from_raw_parts` is an API-compatible version of `alloc::vec::Vec::from_raw_parts`. Because
-    /// of `MiniVec`'s optimized layout, it's not strictly required for a user to pass the length
-    /// and capacity explicitly.
-    ///
-    /// Like [`MiniVec::from_raw_part`](MiniVec::from_raw_part), this function is only safe to use
-    /// with the result of a call to [`MiniVec::as_mut_ptr()`](MiniVec::as_mut_ptr).
-    ///
-    /// # Safety
-    ///
-    /// A very unsafe function that should only really be used when passing the vector to a C API.
+    /// `ext_mut` returns a mutable reference to the header extension, see [`ext`](MiniVec::ext).
     ///
     /// # Example
     ///
-    /// ```
-    /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
-    /// let len = vec.len();
-    /// let cap = vec.capacity();
-    ///
-    /// let ptr = vec.as_mut_ptr();
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// use minivec::{Global, MiniVec};
     ///
-    /// std::mem::forget(vec);
+    /// let mut vec = MiniVec::<u8, Global, u32>::with_ext(0);
     ///
-    /// let new_vec = unsafe { minivec::MiniVec::from_raw_parts(ptr, len, cap) };
+    /// for x in b"generation" {
+    ///     vec.push(*x);
+    ///     *vec.ext_mut().unwrap() += 1;
+    /// }
     ///
-    /// assert_eq!(new_vec, [1, 2, 3, 4]);
+    /// assert_eq!(vec.ext(), Some(&10));
     /// ```
     ///
-    #[allow(clippy::cast_ptr_alignment)]
-    pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> MiniVec<T> {
-        debug_assert!(!ptr.is_null());
-
-        let header_size = core::mem::size_of::<Header>();
-        let aligned = next_aligned(header_size, core::mem::align_of::<T>());
-
-        let p = ptr as *mut u8;
-        let buf = p.sub(aligned);
-
-        debug_assert!((*(buf as *mut Header)).len == length);
-        debug_assert!((*(buf as *mut Header)).cap == capacity);
-
-        MiniVec {
-            buf,
-            phantom: core::marker::PhantomData,
+    pub fn ext_mut(&mut self) -> Option<&mut H> {
+        if core::mem::size_of::<H>() == 0 || !self.is_allocated() {
+            return None;
         }
+
+        unsafe { (*self.ext_ptr()).as_mut() }
     }
 
     /// `insert` places an element at the specified index, subsequently shifting all elements to the
@@ -588,7 +1922,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![0, 1, 2, 3];
     /// vec.insert(1, 1337);
     /// assert_eq!(vec, [0, 1337, 1, 2, 3]);
@@ -598,50 +1933,27 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     pub fn insert(&mut self, index: usize, element: T) {
-        let len = self.len();
-
-        if index > len {
-            panic!(
-                "insertion index (is {}) should be <= len (is {})",
-                index, len
-            );
-        }
-
-        if len == self.capacity() {
-            self.reserve(1);
-        }
-
-        let p = unsafe { self.as_mut_ptr().add(index) };
-        unsafe {
-            core::ptr::copy(p, p.add(1), len - index);
-            core::ptr::write(p, element);
-            self.set_len(len + 1);
+        if let Err(err) = self.try_insert(index, element) {
+            handle_reserve_error(err);
         }
     }
 
-    /// `into_raw_parts` will leak the underlying allocation and return a tuple containing a pointer
-    /// to the start of the backing array and its length and capacity.
-    ///
-    /// The results of this function are directly compatible with [`from_raw_parts`](MiniVec::from_raw_parts).
+    /// `into_boxed_slice` converts the vector into a `Box<[T]>`, moving its elements into an
+    /// allocation that is exactly [`len()`](MiniVec::len) elements large.
     ///
     /// # Example
     ///
     /// ```
-    /// let vec = minivec::mini_vec![1, 2, 3, 4, 5];
-    /// let (old_len, old_cap) = (vec.len(), vec.capacity());
-    ///
-    /// let (ptr, len, cap) = vec.into_raw_parts();
-    /// assert_eq!(len, old_len);
-    /// assert_eq!(cap, old_cap);
+    /// let vec = minivec::mini_vec![1, 2, 3];
     ///
-    /// let vec = unsafe { minivec::MiniVec::from_raw_parts(ptr, len, cap) };
-    /// assert_eq!(vec, [1, 2, 3, 4, 5]);
+    /// let slice: Box<[i32]> = vec.into_boxed_slice();
+    /// assert_eq!(&*slice, &[1, 2, 3]);
     /// ```
     ///
+    #[cfg(feature = "alloc")]
     #[must_use]
-    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
-        let mut v = core::mem::ManuallyDrop::new(self);
-        (v.as_mut_ptr(), v.len(), v.capacity())
+    pub fn into_boxed_slice(self) -> alloc::boxed::Box<[T]> {
+        alloc::vec::Vec::from(self).into_boxed_slice()
     }
 
     /// `is_empty()` returns whether or not the `MiniVec` has a length greater than 0.
@@ -650,7 +1962,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let vec = minivec::MiniVec::<i32>::with_capacity(256);
     /// assert!(vec.is_empty());
     /// assert!(vec.capacity() > 0);
@@ -669,7 +1982,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let vec = minivec::mini_vec![1, 2, 3];
     /// let static_ref: &'static mut [i32] = minivec::MiniVec::leak(vec);
     /// static_ref[0] += 1;
@@ -677,13 +1991,13 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     #[must_use]
-    pub fn leak<'a>(vec: MiniVec<T>) -> &'a mut [T]
+    pub fn leak<'a>(vec: MiniVec<T, A, H>) -> &'a mut [T]
     where
         T: 'a,
     {
         let len = vec.len();
         let mut vec = core::mem::ManuallyDrop::new(vec);
-        let vec: &mut MiniVec<T> = &mut *vec;
+        let vec: &mut MiniVec<T, A, H> = &mut vec;
         unsafe { core::slice::from_raw_parts_mut(vec.as_mut_ptr(), len) }
     }
 
@@ -693,45 +2007,44 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![-1; 256];
     /// assert_eq!(vec.len(), 256);
     /// ```
     ///
     #[must_use]
     pub fn len(&self) -> usize {
-        if self.buf.is_null() {
-            0
+        if core::mem::size_of::<T>() == 0 {
+            self.buf.as_ptr() as usize - 1
         } else {
             self.header().len
         }
     }
 
-    /// `MiniVec::new` constructs an empty `MiniVec`.
+    /// `max_capacity` returns the largest capacity a vector of `T` can be created with or grown to
+    /// using the default alignment. Requesting more than this results in a capacity overflow.
     ///
-    /// Note: does not allocate any memory.
+    /// The size of an allocation, header included, is limited to `isize::MAX` bytes. Zero-sized
+    /// types never allocate and can hold up to `usize::MAX - 1` elements.
     ///
     /// # Example
     ///
-    /// ```
-    /// let mut vec = minivec::MiniVec::<i32>::new();
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let max = minivec::MiniVec::<u64>::max_capacity();
+    /// assert!(max < isize::MAX as usize / 8);
     ///
-    /// assert_eq!(vec.as_mut_ptr(), std::ptr::null_mut());
-    /// assert_eq!(vec.len(), 0);
-    /// assert_eq!(vec.capacity(), 0);
+    /// let mut vec = minivec::MiniVec::<u64>::new();
+    /// assert_eq!(
+    ///     vec.try_reserve(max + 1),
+    ///     Err(minivec::TryReserveError::CapacityOverflow)
+    /// );
     /// ```
     ///
     #[must_use]
-    pub fn new() -> MiniVec<T> {
-        assert!(
-            core::mem::size_of::<T>() > 0,
-            "ZSTs currently not supported"
-        );
-
-        MiniVec {
-            buf: core::ptr::null_mut(),
-            phantom: core::marker::PhantomData,
-        }
+    pub fn max_capacity() -> usize {
+        max_capacity::<T, H>(min_alignment::<T, H>())
     }
 
     /// `pop` removes the last element from the vector, should it exist, and returns an [`Option`](core::option::Option)
@@ -739,7 +2052,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![Box::new(1)];
     /// let ptr = vec.pop().unwrap();
     /// assert_eq!(*ptr, 1);
@@ -748,6 +2062,8 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     pub fn pop(&mut self) -> Option<T> {
+        self.unshare();
+
         let len = self.len();
 
         if len == 0 {
@@ -764,7 +2080,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::MiniVec::<i32>::with_capacity(64);
     ///
     /// for idx in 0..128 {
@@ -775,33 +2092,70 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     pub fn push(&mut self, value: T) {
-        let (len, capacity, alignment) = (self.len(), self.capacity(), self.alignment());
-        if len == capacity {
-            self.grow(next_capacity::<T>(capacity), alignment);
+        if let Err(err) = self.try_push(value) {
+            handle_reserve_error(err);
         }
+    }
 
-        let len = self.len();
-        let data = self.data();
-
-        let dst = unsafe { data.add(len) };
-
-        unsafe {
-            core::ptr::write(dst, value);
-        };
-
-        let mut header = self.header_mut();
-        header.len += 1;
+    /// `realign` changes the alignment of the vector's allocation to be at least `new_alignment`.
+    ///
+    /// The supplied alignment has the same requirements as the one passed to [`with_alignment`](MiniVec::with_alignment).
+    ///
+    /// The elements are only moved into a new allocation when the data doesn't already start at a
+    /// multiple of `new_alignment`. Otherwise only the stored alignment is raised, and the vector
+    /// keeps it when it later reallocates. Requesting an alignment that is smaller than the
+    /// current one leaves the vector untouched and [`alignment()`](MiniVec::alignment) keeps
+    /// reporting the larger value. The length and capacity of the vector are always preserved.
+    ///
+    /// Vectors of zero-sized types never allocate so for them this only validates
+    /// `new_alignment`, and `alignment()` keeps reporting the default alignment.
+    ///
+    /// A failed allocation isn't reported as an error but aborts, as it does when growing the
+    /// vector. In particular a vector backed by [`FixedStorage`] has nowhere to move to, so
+    /// `realign` only succeeds when its data already meets `new_alignment` and the vector can't
+    /// grow past its capacity afterwards. [`try_realign`](MiniVec::try_realign) returns the
+    /// allocation error instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `LayoutErr` if `new_alignment` is rejected, in which case the vector is unchanged.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec: minivec::MiniVec<f32> = (0..64).map(|x| x as f32).collect();
+    ///
+    /// vec.realign(64).unwrap();
+    ///
+    /// assert_eq!(vec.alignment(), 64);
+    /// assert_eq!(vec.as_ptr() as usize % 64, 0);
+    /// assert_eq!(vec[63], 63.0);
+    ///
+    /// assert!(vec.realign(3).is_err());
+    /// ```
+    ///
+    pub fn realign(&mut self, new_alignment: usize) -> Result<(), LayoutErr> {
+        match self.try_realign(new_alignment) {
+            Ok(()) => Ok(()),
+            Err(TryReserveError::InvalidAlignment(err)) => Err(err),
+            Err(TryReserveError::CapacityOverflow) => Err(LayoutErr::SizeOverflow),
+            Err(err) => handle_reserve_error(err),
+        }
     }
 
     /// `remove` moves the element at the specified `index` and then returns it to the user. This
     /// operation shifts all elements to the right `index` to the left by one so it has a linear
     /// time complexity of `vec.len() - index`.
     ///
+    /// # Panics
+    ///
     /// Panics if `index >= len()`.
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![0, 1, 2, 3];
     /// vec.remove(0);
     ///
@@ -809,10 +2163,15 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     pub fn remove(&mut self, index: usize) -> T {
+        self.unshare();
+
         let len = self.len();
-        if index >= len {
-            panic!("removal index (is {}) should be < len (is {})", index, len);
-        }
+        assert!(
+            index < len,
+            "removal index (is {}) should be < len (is {})",
+            index,
+            len
+        );
 
         unsafe {
             let p = self.as_mut_ptr().add(index);
@@ -835,7 +2194,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![0, 1, 1, 1, 2, 3, 4];
     /// vec.remove_item(&1);
     ///
@@ -863,7 +2223,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::MiniVec::<i32>::new();
     ///
     /// assert_eq!(vec.capacity(), 0);
@@ -874,19 +2235,9 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     pub fn reserve(&mut self, additional: usize) {
-        let capacity = self.capacity();
-        let total_required = self.len() + additional;
-
-        if total_required <= capacity {
-            return;
-        }
-
-        let mut new_capacity = next_capacity::<T>(capacity);
-        while new_capacity < total_required {
-            new_capacity = next_capacity::<T>(new_capacity);
+        if let Err(err) = self.try_reserve(additional) {
+            handle_reserve_error(err);
         }
-
-        self.grow(new_capacity, self.alignment());
     }
 
     /// `reserve_exact` ensures that the capacity of the vector is exactly equal to
@@ -895,7 +2246,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::MiniVec::<i32>::new();
     /// vec.reserve_exact(57);
     ///
@@ -903,15 +2255,9 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     pub fn reserve_exact(&mut self, additional: usize) {
-        let capacity = self.capacity();
-        let len = self.len();
-
-        let total_required = len + additional;
-        if capacity >= total_required {
-            return;
+        if let Err(err) = self.try_reserve_exact(additional) {
+            handle_reserve_error(err);
         }
-
-        self.grow(total_required, self.alignment());
     }
 
     /// `resize` will clone the supplied `value` as many times as required until `len()` becomes
@@ -921,7 +2267,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![-1; 256];
     ///
     /// vec.resize(512, -1);
@@ -935,6 +2282,8 @@ impl<T> MiniVec<T> {
     where
         T: Clone,
     {
+        self.unshare();
+
         let len = self.len();
         match new_len.cmp(&len) {
             core::cmp::Ordering::Equal => {}
@@ -958,7 +2307,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::MiniVec::<i32>::new();
     ///
     /// vec.resize_with(128, || 1337);
@@ -969,6 +2319,8 @@ impl<T> MiniVec<T> {
     where
         F: FnMut() -> T,
     {
+        self.unshare();
+
         let len = self.len();
         match new_len.cmp(&len) {
             core::cmp::Ordering::Equal => {}
@@ -990,7 +2342,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![1, 2, 3, 4, 5, 6];
     ///
     /// let is_even = |x: &i32| *x % 2 == 0;
@@ -1002,28 +2355,28 @@ impl<T> MiniVec<T> {
     where
         F: FnMut(&T) -> bool,
     {
+        self.unshare();
+
         let len = self.len();
 
         let data = self.as_mut_ptr();
 
-        let mut read = data;
-        let mut write = read;
+        let mut read = 0;
+        let mut write = 0;
 
-        let last = unsafe { data.add(len) };
-
-        while read < last {
-            let should_retain = unsafe { f(&mut *read) };
+        while read < len {
+            let should_retain = unsafe { f(&*data.add(read)) };
             if should_retain {
                 if read != write {
-                    unsafe { core::mem::swap(&mut *read, &mut *write) };
+                    unsafe { core::ptr::swap(data.add(read), data.add(write)) };
                 }
-                write = unsafe { write.add(1) };
+                write += 1;
             }
 
-            read = unsafe { read.add(1) };
+            read += 1;
         }
 
-        self.truncate((write as usize - data as usize) / core::mem::size_of::<T>());
+        self.truncate(write);
     }
 
     /// `set_len` reassigns the internal `len_` data member to the user-supplied `len`.
@@ -1033,9 +2386,16 @@ impl<T> MiniVec<T> {
     /// This function is unsafe in the sense that it will NOT call `.drop()` on the elements
     /// excluded from the new len so this function should only be called when `T` is a `Copy` type.
     ///
+    /// The vector must own its buffer: a vector created by [`static_mini_vec!`](static_mini_vec)
+    /// borrows read-only memory until it's first mutated, e.g. by [`reserve`](MiniVec::reserve).
+    ///
+    /// Note: vectors of zero-sized types store their length in place of a pointer so this
+    /// function panics if `T` is zero-sized and `len` is `usize::MAX`.
+    ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
     /// unsafe { vec.set_len(2) };
     ///
@@ -1043,9 +2403,82 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     pub unsafe fn set_len(&mut self, len: usize) {
+        if core::mem::size_of::<T>() == 0 {
+            self.buf = zst_buf(len);
+            return;
+        }
+
+        // the shared empty header must never be written to
+        //
+        if !self.is_allocated() {
+            debug_assert_eq!(len, 0);
+            return;
+        }
+
         self.header_mut().len = len;
     }
 
+    /// `set_ext` stores `ext` in the vector's allocation, returning the previous extension if there
+    /// was one. The extension is carried along when the vector reallocates and is cloned by
+    /// [`clone`](Clone::clone) and [`split_off`](MiniVec::split_off).
+    ///
+    /// Note: if the vector hasn't allocated yet, an allocation large enough to hold just the header
+    /// and the extension is made.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `T` or `H` is a zero-sized type as neither ever allocates.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// use minivec::{Global, MiniVec};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Shape {
+    ///     rows: usize,
+    ///     cols: usize,
+    /// }
+    ///
+    /// let mut matrix = MiniVec::<f32, Global, Shape>::default();
+    /// assert_eq!(matrix.set_ext(Shape { rows: 2, cols: 2 }), None);
+    /// matrix.extend_from_slice(&[1.0, 0.0, 0.0, 1.0]);
+    ///
+    /// let copy = matrix.clone();
+    /// assert_eq!(copy.ext(), Some(&Shape { rows: 2, cols: 2 }));
+    /// ```
+    ///
+    pub fn set_ext(&mut self, ext: H) -> Option<H> {
+        assert!(
+            core::mem::size_of::<T>() > 0 && core::mem::size_of::<H>() > 0,
+            "zero-sized types cannot store a header extension"
+        );
+
+        if !self.is_allocated() {
+            let alignment = self.alignment();
+            let layout = make_layout::<T, H>(0, alignment);
+            let buf = match self.allocator.allocate(layout) {
+                Ok(p) => p.cast::<u8>(),
+                Err(_) => handle_reserve_error(TryReserveError::AllocError { layout }),
+            };
+
+            let header = Header {
+                len: 0,
+                cap: 0,
+                alignment,
+            };
+
+            unsafe { write_header::<H>(buf, header) };
+            self.buf = buf;
+
+            unsafe { self.ext_ptr().write(Some(ext)) };
+            return None;
+        }
+
+        unsafe { (*self.ext_ptr()).replace(ext) }
+    }
+
     /// `shrink_to` will attempt to adjust the backing allocation such that it has space for at
     /// least `min_capacity` elements.
     ///
@@ -1055,13 +2488,16 @@ impl<T> MiniVec<T> {
     /// If the [`capacity()`](MiniVec::capacity) is identical to `min_capacity` then this function
     /// does nothing.
     ///
-    /// If the `min_capacity` is larger than the current capacity this function will panic.
-    ///
     /// Otherwise, the allocation is reallocated with the new `min_capacity` kept in mind.
     ///
+    /// # Panics
+    ///
+    /// Panics if `min_capacity` is larger than the current capacity.
+    ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::MiniVec::<i32>::with_capacity(128);
     /// assert!(vec.capacity() >= 128);
     ///
@@ -1070,6 +2506,8 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.unshare();
+
         let (len, capacity) = (self.len(), self.capacity());
 
         if min_capacity < len {
@@ -1081,9 +2519,10 @@ impl<T> MiniVec<T> {
             return;
         }
 
-        if capacity < min_capacity {
-            panic!("Tried to shrink to a larger capacity");
-        }
+        assert!(
+            capacity >= min_capacity,
+            "Tried to shrink to a larger capacity"
+        );
 
         self.grow(min_capacity, self.alignment());
     }
@@ -1093,7 +2532,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::MiniVec::with_capacity(512);
     ///
     /// vec.push(1);
@@ -1106,6 +2546,8 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     pub fn shrink_to_fit(&mut self) {
+        self.unshare();
+
         let len = self.len();
         if len == self.capacity() {
             return;
@@ -1126,7 +2568,8 @@ impl<T> MiniVec<T> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::MiniVec::<i32>::with_capacity(24);
     /// let mut buf = vec.spare_capacity_mut();
     ///
@@ -1140,13 +2583,15 @@ impl<T> MiniVec<T> {
     /// ```
     ///
     pub fn spare_capacity_mut(&mut self) -> &mut [core::mem::MaybeUninit<T>] {
+        self.unshare();
+
         let capacity = self.capacity();
         if capacity == 0 {
             return &mut [];
         }
 
         let len = self.len();
-        let data = unsafe { self.data().add(len) as *mut core::mem::MaybeUninit<T> };
+        let data = unsafe { self.data().add(len).cast::<core::mem::MaybeUninit<T>>() };
         let spare_len = capacity - len;
 
         unsafe { core::slice::from_raw_parts_mut(data, spare_len) }
@@ -1161,11 +2606,14 @@ impl<T> MiniVec<T> {
     ///
     /// `Splice` only fills the removed region when it is dropped.
     ///
-    /// Note: panics if the supplied `range` is outside of the vector's bounds.
+    /// # Panics
+    ///
+    /// Panics if the supplied `range` is outside of the vector's bounds.
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut x = minivec::mini_vec![1, 2, 3, 4, 5, 6];
     /// let new = [7, 8];
     ///
@@ -1179,11 +2627,13 @@ impl<T> MiniVec<T> {
         &mut self,
         range: R,
         replace_with: I,
-    ) -> Splice<<I as IntoIterator>::IntoIter>
+    ) -> Splice<'_, <I as IntoIterator>::IntoIter, A, H>
     where
         I: IntoIterator<Item = T>,
         R: core::ops::RangeBounds<usize>,
     {
+        self.unshare();
+
         let len = self.len();
 
         let start_idx = match range.start_bound() {
@@ -1198,21 +2648,21 @@ impl<T> MiniVec<T> {
             core::ops::Bound::Unbounded => len,
         };
 
-        if start_idx > end_idx {
-            panic!(
-                "start splice index (is {}) should be <= end splice index (is {})",
-                start_idx, end_idx
-            );
-        }
+        assert!(
+            start_idx <= end_idx,
+            "start splice index (is {}) should be <= end splice index (is {})",
+            start_idx,
+            end_idx
+        );
 
-        if end_idx > len {
-            panic!(
-                "end splice index (is {}) should be <= len (is {})",
-                end_idx, len
-            );
-        }
+        assert!(
+            end_idx <= len,
+            "end splice index (is {}) should be <= len (is {})",
+            end_idx,
+            len
+        );
 
-        let data = self.as_mut_ptr();
+        let data = self.data();
 
         unsafe { self.set_len(start_idx) };
 
@@ -1226,243 +2676,590 @@ impl<T> MiniVec<T> {
         )
     }
 
-    /// `split_off` will segment the vector into two, returning the new segment to the user.
+    /// `split_off` will segment the vector into two, returning the new segment to the user.
+    ///
+    /// After this function call, `self` will have kept elements `[0, at)` while the new segment
+    /// contains elements `[at, len)`. The new segment uses the same alignment as `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is greater than [`len()`](MiniVec::len).
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    ///
+    /// let tail = vec.split_off(7);
+    ///
+    /// assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(tail, [7, 8, 9, 10]);
+    /// ```
+    ///
+    #[must_use = "use `.truncate()` if you don't need the other half"]
+    pub fn split_off(&mut self, at: usize) -> MiniVec<T, A, H>
+    where
+        A: Clone,
+        H: Clone,
+    {
+        self.unshare();
+
+        let len = self.len();
+        assert!(
+            at <= len,
+            "`at` split index (is {}) should be <= len (is {})",
+            at,
+            len
+        );
+
+        let mut other = MiniVec::empty_in(self.allocator.clone());
+        other.grow(self.capacity(), self.alignment());
+        other.clone_ext_from(self);
+
+        unsafe { self.set_len(at) }
+        unsafe { other.set_len(len - at) }
+
+        let src = unsafe { self.data().add(at) };
+        let dst = other.data();
+        let count = len - at;
+
+        unsafe { core::ptr::copy_nonoverlapping(src, dst, count) }
+
+        other
+    }
+
+    /// `swap_remove` removes the element located at `index` and replaces it with the last value
+    /// in the vector, returning the removed element to the caller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
+    ///
+    /// let num = vec.swap_remove(0);
+    /// assert_eq!(num, 1);
+    /// assert_eq!(vec, [4, 2, 3]);
+    /// ```
+    ///
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        self.unshare();
+
+        let len = self.len();
+        assert!(
+            index < len,
+            "swap_remove index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+
+        let src = unsafe { core::ptr::read(self.as_ptr().add(len - 1)) };
+        unsafe { self.set_len(len - 1) };
+
+        let dst = unsafe { self.as_mut_ptr().add(index) };
+        unsafe { core::ptr::replace(dst, src) }
+    }
+
+    /// `take_ext` removes the header extension from the vector, returning it.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::MiniVec::<i32, minivec::Global, char>::with_ext('x');
+    ///
+    /// assert_eq!(vec.take_ext(), Some('x'));
+    /// assert_eq!(vec.ext(), None);
+    /// ```
+    ///
+    pub fn take_ext(&mut self) -> Option<H> {
+        if core::mem::size_of::<H>() == 0 || !self.is_allocated() {
+            return None;
+        }
+
+        unsafe { (*self.ext_ptr()).take() }
+    }
+
+    /// `truncate` adjusts the length of the vector to be `len`. If `len` is greater than or equal
+    /// to the current length no operation is performed. Otherwise, the vector's length is
+    /// readjusted to `len` and any remaining elements to the right of `len` are dropped.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1, 2, 3, 4, 5];
+    /// vec.truncate(2);
+    ///
+    /// assert_eq!(vec, [1, 2]);
+    /// ```
+    ///
+    pub fn truncate(&mut self, len: usize) {
+        let self_len = self.len();
+
+        if len >= self_len {
+            return;
+        }
+
+        self.unshare();
+        unsafe { self.set_len(len) };
+
+        if !core::mem::needs_drop::<T>() {
+            return;
+        }
+
+        let s = unsafe { core::slice::from_raw_parts_mut(self.data().add(len), self_len - len) };
+
+        unsafe { core::ptr::drop_in_place(s) };
+    }
+
+    /// `try_insert` is the fallible counterpart to [`insert`](MiniVec::insert). Instead of
+    /// aborting when the vector needs to grow and the allocation fails, an error is returned and
+    /// the vector is left unmodified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](TryReserveError) if the required capacity overflows or the
+    /// allocator reports a failure.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![0, 1, 2, 3];
+    /// vec.try_insert(1, 1337).unwrap();
+    ///
+    /// assert_eq!(vec, [0, 1337, 1, 2, 3]);
+    /// ```
+    ///
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), TryReserveError> {
+        self.unshare();
+
+        let len = self.len();
+
+        assert!(
+            index <= len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+
+        if len == self.capacity() {
+            self.try_reserve(1)?;
+        }
+
+        let p = unsafe { self.as_mut_ptr().add(index) };
+        unsafe {
+            core::ptr::copy(p, p.add(1), len - index);
+            core::ptr::write(p, element);
+            self.set_len(len + 1);
+        }
+
+        Ok(())
+    }
+
+    /// `try_push` is the fallible counterpart to [`push`](MiniVec::push). If the vector is full and
+    /// cannot be grown, an error is returned, `value` is dropped and the vector is left unmodified.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](TryReserveError) if the required capacity overflows or the
+    /// allocator reports a failure.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::MiniVec::<i32>::new();
+    ///
+    /// for idx in 0..128 {
+    ///     vec.try_push(idx).unwrap();
+    /// }
+    ///
+    /// assert_eq!(vec.len(), 128);
+    /// ```
+    ///
+    pub fn try_push(&mut self, value: T) -> Result<(), TryReserveError> {
+        // static vectors are always full so they're copied into an allocation of their own by
+        // `try_reserve`, keeping the check off the fast path
+        //
+        let len = self.len();
+        if len == self.capacity() {
+            self.try_reserve(1)?;
+        }
+
+        let dst = unsafe { self.data().add(len) };
+
+        unsafe {
+            core::ptr::write(dst, value);
+            self.set_len(len + 1);
+        };
+
+        Ok(())
+    }
+
+    /// `try_realign` is the fallible counterpart to [`realign`](MiniVec::realign).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError::InvalidAlignment`](TryReserveError::InvalidAlignment) if the
+    /// supplied alignment is rejected and any other [`TryReserveError`](TryReserveError) if the new
+    /// allocation cannot be made. The vector is unchanged in either case.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1_u8, 2, 3, 4];
+    ///
+    /// vec.try_realign(32).unwrap();
+    /// assert_eq!(vec.as_ptr() as usize % 32, 0);
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    ///
+    /// assert_eq!(
+    ///     vec.try_realign(2),
+    ///     Err(minivec::TryReserveError::InvalidAlignment(minivec::LayoutErr::AlignmentTooSmall))
+    /// );
+    /// ```
+    ///
+    pub fn try_realign(&mut self, new_alignment: usize) -> Result<(), TryReserveError> {
+        validate_alignment::<T, H>(new_alignment).map_err(TryReserveError::InvalidAlignment)?;
+
+        if core::mem::size_of::<T>() == 0 || new_alignment <= self.alignment() {
+            return Ok(());
+        }
+
+        self.unshare();
+
+        if !self.is_allocated() {
+            return self.try_grow(0, new_alignment);
+        }
+
+        if self.data() as usize % new_alignment != 0 {
+            return self.try_relocate(self.capacity(), new_alignment);
+        }
+
+        let header = Header {
+            len: self.len(),
+            cap: self.capacity(),
+            alignment: self.layout_alignment() | new_alignment,
+        };
+
+        unsafe { write_header::<H>(self.buf, header) };
+
+        Ok(())
+    }
+
+    /// `try_reserve` is the fallible counterpart to [`reserve`](MiniVec::reserve). It uses the
+    /// same growth policy but reports failures to the caller instead of aborting.
     ///
-    /// After this function call, `self` will have kept elements `[0, at)` while the new segment
-    /// contains elements `[at, len)`.
+    /// # Errors
     ///
-    /// Note: panics if `at` is greater than [`len()`](MiniVec::len).
+    /// Returns [`TryReserveError::CapacityOverflow`](TryReserveError::CapacityOverflow) if
+    /// `len() + additional` cannot be represented by an allocation and
+    /// [`TryReserveError::AllocError`](TryReserveError::AllocError) if the allocator fails. The
+    /// vector is left unmodified in both cases.
     ///
     /// # Example
     ///
-    /// ```
-    /// let mut vec = minivec::mini_vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1, 2, 3];
     ///
-    /// let tail = vec.split_off(7);
+    /// vec.try_reserve(128).unwrap();
+    /// assert!(vec.capacity() >= 131);
     ///
-    /// assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6]);
-    /// assert_eq!(tail, [7, 8, 9, 10]);
+    /// assert_eq!(
+    ///     vec.try_reserve(usize::MAX),
+    ///     Err(minivec::TryReserveError::CapacityOverflow)
+    /// );
+    /// assert_eq!(vec, [1, 2, 3]);
     /// ```
     ///
-    pub fn split_off(&mut self, at: usize) -> MiniVec<T> {
-        let len = self.len();
-        if at > len {
-            panic!("`at` split index (is {}) should be <= len (is {})", at, len);
-        }
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.unshare();
 
-        let mut other = MiniVec::with_capacity(self.capacity());
+        let capacity = self.capacity();
+        let total_required = self
+            .len()
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
 
-        unsafe { self.set_len(at) }
-        unsafe { other.set_len(len - at) }
+        if total_required <= capacity {
+            return Ok(());
+        }
 
-        let src = unsafe { self.as_ptr().add(at) };
-        let dst = other.as_mut_ptr();
-        let count = len - at;
+        let alignment = self.alignment();
+        let max_capacity = self.capacity_limit(alignment);
+        if total_required > max_capacity {
+            return Err(TryReserveError::CapacityOverflow);
+        }
 
-        unsafe { core::ptr::copy_nonoverlapping(src, dst, count) }
+        // amortized growth may overshoot the largest possible capacity even when the requested
+        // one fits
+        //
+        let mut new_capacity = next_capacity::<T>(capacity);
+        while new_capacity < total_required {
+            new_capacity = new_capacity.saturating_mul(2);
+        }
 
-        other
+        self.try_grow(core::cmp::min(new_capacity, max_capacity), alignment)
     }
 
-    /// `swap_remove` removes the element located at `index` and replaces it with the last value
-    /// in the vector, returning the removed element to the caller.
+    /// `try_reserve_exact` is the fallible counterpart to [`reserve_exact`](MiniVec::reserve_exact).
+    ///
+    /// # Errors
     ///
-    /// Note: panics if `index >= len()`.
+    /// Returns a [`TryReserveError`](TryReserveError) if `len() + additional` overflows or the
+    /// allocator reports a failure. The vector is left unmodified in both cases.
     ///
     /// # Example
     ///
-    /// ```
-    /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::MiniVec::<i32>::new();
+    /// vec.try_reserve_exact(57).unwrap();
     ///
-    /// let num = vec.swap_remove(0);
-    /// assert_eq!(num, 1);
-    /// assert_eq!(vec, [4, 2, 3]);
+    /// assert_eq!(vec.capacity(), 57);
     /// ```
     ///
-    pub fn swap_remove(&mut self, index: usize) -> T {
-        let len = self.len();
-        if index >= len {
-            panic!(
-                "swap_remove index (is {}) should be < len (is {})",
-                index, len
-            );
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.unshare();
+
+        let capacity = self.capacity();
+        let total_required = self
+            .len()
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+
+        if capacity >= total_required {
+            return Ok(());
         }
 
-        let src = unsafe { core::ptr::read(self.as_ptr().add(len - 1)) };
-        self.header_mut().len -= 1;
+        let alignment = self.alignment();
+        if total_required > self.capacity_limit(alignment) {
+            return Err(TryReserveError::CapacityOverflow);
+        }
 
-        let dst = unsafe { self.as_mut_ptr().add(index) };
-        unsafe { core::ptr::replace(dst, src) }
+        self.try_grow(total_required, alignment)
     }
 
-    /// `truncate` adjusts the length of the vector to be `len`. If `len` is greater than or equal
-    /// to the current length no operation is performed. Otherwise, the vector's length is
-    /// readjusted to `len` and any remaining elements to the right of `len` are dropped.
+    /// `with_ext_in` is the allocator-aware version of [`with_ext`](MiniVec::with_ext).
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `T` or `H` is a zero-sized type.
+    ///
+    #[must_use]
+    pub fn with_ext_in(ext: H, allocator: A) -> MiniVec<T, A, H> {
+        let mut v = MiniVec::empty_in(allocator);
+        v.set_ext(ext);
+        v
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn unsafe_write(&mut self, idx: usize, elem: T) {
+        self.unshare();
+
+        self.data().add(idx).write(elem);
+    }
+}
+
+impl<T: Clone, A: Allocator, H> MiniVec<T, A, H> {
+    /// `clone_with_alignment` clones the vector into a new allocation that is aligned to
+    /// `alignment` instead of the alignment of `self`.
+    ///
+    /// The supplied alignment has the same requirements as the one passed to [`with_alignment`](MiniVec::with_alignment).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `LayoutErr` if `alignment` is rejected.
     ///
     /// # Example
     ///
-    /// ```
-    /// let mut vec = minivec::mini_vec![1, 2, 3, 4, 5];
-    /// vec.truncate(2);
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let vec = minivec::mini_vec![1_u8, 2, 3, 4];
+    /// let aligned = vec.clone_with_alignment(128).unwrap();
     ///
-    /// assert_eq!(vec, [1, 2]);
+    /// assert_eq!(aligned, vec);
+    /// assert_eq!(aligned.as_ptr() as usize % 128, 0);
+    ///
+    /// // clones of an over-aligned vector are over-aligned as well
+    /// //
+    /// assert_eq!(aligned.clone().as_ptr() as usize % 128, 0);
     /// ```
     ///
-    pub fn truncate(&mut self, len: usize) {
-        let self_len = self.len();
+    pub fn clone_with_alignment(&self, alignment: usize) -> Result<MiniVec<T, A, H>, LayoutErr>
+    where
+        A: Clone,
+        H: Clone,
+    {
+        validate_alignment::<T, H>(alignment)?;
 
-        if len >= self_len {
-            return;
+        let mut copy = MiniVec::empty_in(self.allocator.clone());
+        match copy.try_grow(self.len(), alignment) {
+            Ok(()) => {}
+            Err(TryReserveError::CapacityOverflow) => return Err(LayoutErr::SizeOverflow),
+            Err(err) => handle_reserve_error(err),
         }
+        copy.clone_ext_from(self);
+        copy.extend_from_slice(self);
+        Ok(copy)
+    }
 
-        self.header_mut().len = len;
+    // clones `elems` into the spare capacity, which the caller must have reserved, committing the
+    // length once at the end rather than after every element
+    //
+    fn extend_cloned(&mut self, elems: &[T]) {
+        debug_assert!(self.capacity() - self.len() >= elems.len());
 
-        if !core::mem::needs_drop::<T>() {
+        if elems.is_empty() {
             return;
         }
 
-        let s = unsafe { core::slice::from_raw_parts_mut(self.data().add(len), self_len - len) };
+        let len = self.len();
+        let dst = unsafe { self.data().add(len) };
+        let mut guard = SetLenOnDrop { vec: self, len };
 
-        unsafe { core::ptr::drop_in_place(s) };
+        for (idx, x) in elems.iter().enumerate() {
+            unsafe { dst.add(idx).write(x.clone()) };
+            guard.len = len + idx + 1;
+        }
     }
 
-    /// `with_alignment` is similar to its counterpart [`with_capacity`](MiniVec::with_capacity)
-    /// except it takes an additional argument: the alignment to use for the allocation.
-    ///
-    /// The supplied alignment must be a number divisible by 2 and larger than or equal to the
-    /// result of `core::mem::align_of::<*const ()>()`.
+    /// `extend_from_slice` will append each element from `elems` in a left-to-right order, cloning
+    /// each value in `elems`.
     ///
-    /// The internal allocation used to store the header information for `MiniVec` is aligned to the
-    /// supplied value and then sufficient padding is inserted such that the result of [`as_ptr()`](MiniVec::as_ptr)
-    /// will always be aligned as well.
+    /// # Example
     ///
-    /// This is useful for creating over-aligned allocations for primitive types such as when using
-    /// `SIMD` intrinsics. For example, some vectorized floating point loads and stores _must_ be
-    /// aligned on a 32 byte boundary. `with_alignment` is intended to make this possible with a
-    /// `Vec`-like container.
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1, 2];
     ///
-    /// # Errors
+    /// let s : &[i32] = &[3, 4];
     ///
-    /// Returns a `Result` that contains either `MiniVec<T>` or a `LayoutErr`.
+    /// vec.extend_from_slice(s);
     ///
-    /// # Example
+    /// assert_eq!(vec, [1, 2, 3, 4]);
     /// ```
-    /// #[cfg(target_arch = "x86")]
-    /// use std::arch::x86::*;
-    /// #[cfg(target_arch = "x86_64")]
-    /// use std::arch::x86_64::*;
-    ///
-    /// let alignment = 32;
-    /// let num_elems = 2048;
-    /// let mut v1 = minivec::MiniVec::<f32>::with_alignment(num_elems, alignment).unwrap();
-    /// let mut v2 = minivec::MiniVec::<f32>::with_alignment(num_elems, alignment).unwrap();
     ///
-    /// v1
-    ///     .spare_capacity_mut()
-    ///     .iter_mut()
-    ///     .zip(v2.spare_capacity_mut().iter_mut())
-    ///     .enumerate()
-    ///     .for_each(|(idx, (x1, x2))| {
-    ///         *x1 = core::mem::MaybeUninit::new(idx as f32);
-    ///         *x2 = core::mem::MaybeUninit::new(idx as f32);
-    ///     });
+    pub fn extend_from_slice(&mut self, elems: &[T]) {
+        self.reserve(elems.len());
+        self.extend_cloned(elems);
+    }
+
+    /// `extend_from_within` clones the elements in `src` and appends them to the end of the
+    /// vector.
     ///
-    /// unsafe {
-    ///     v1.set_len(num_elems);
-    ///     v2.set_len(num_elems);
+    /// # Panics
     ///
-    ///     // use vectorization to speed up the summation of two vectors
-    ///     //
-    ///     for idx in 0..(num_elems / 8) {
-    ///         let offset = idx * 8;
+    /// Panics if `src` is out of bounds or if its start is greater than its end.
     ///
-    ///         let p = v1.as_mut_ptr().add(offset);
-    ///         let q = v2.as_mut_ptr().add(offset);
+    /// # Example
     ///
-    ///         let r1 = _mm256_load_ps(p);
-    ///         let r2 = _mm256_load_ps(q);
-    ///         let r3 = _mm256_add_ps(r1, r2);
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
     ///
-    ///         _mm256_store_ps(p, r3);
-    ///     }
-    /// }
+    /// vec.extend_from_within(1..3);
+    /// vec.extend_from_within(..1);
     ///
-    /// v1
-    ///     .iter()
-    ///     .enumerate()
-    ///     .for_each(|(idx, v)| {
-    ///         assert_eq!(*v, idx as f32 * 2.0);
-    ///     });
+    /// assert_eq!(vec, [1, 2, 3, 4, 2, 3, 1]);
     /// ```
     ///
-    pub fn with_alignment(capacity: usize, alignment: usize) -> Result<MiniVec<T>, LayoutErr> {
-        if alignment < max_align::<T>() {
-            return Err(LayoutErr::AlignmentTooSmall);
-        }
+    pub fn extend_from_within<R>(&mut self, src: R)
+    where
+        R: core::ops::RangeBounds<usize>,
+    {
+        let start = match src.start_bound() {
+            core::ops::Bound::Included(&n) => n,
+            core::ops::Bound::Excluded(&n) => n + 1,
+            core::ops::Bound::Unbounded => 0,
+        };
 
-        if alignment % 2 > 0 {
-            return Err(LayoutErr::AlignmentNotDivisibleByTwo);
-        }
+        let end = match src.end_bound() {
+            core::ops::Bound::Included(&n) => n + 1,
+            core::ops::Bound::Excluded(&n) => n,
+            core::ops::Bound::Unbounded => self.len(),
+        };
 
-        let mut v = MiniVec::new();
-        v.grow(capacity, alignment);
-        Ok(v)
+        let count = self.as_slice()[start..end].len();
+        self.reserve(count);
+
+        // reserving may have moved the elements so the source is only located afterwards. It
+        // never overlaps with the spare capacity being written to
+        //
+        let elems = unsafe { core::slice::from_raw_parts(self.data().add(start), count) };
+        self.extend_cloned(elems);
     }
 
-    /// `with_capacity` is a static factory function that returns a `MiniVec` that contains space
-    /// for `capacity` elements.
-    ///
-    /// This function is logically equivalent to calling [`.reserve_exact()`](MiniVec::reserve_exact)
-    /// on a vector with `0` capacity.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// let mut vec = minivec::MiniVec::<i32>::with_capacity(128);
+    /// `try_extend_from_slice` is the fallible counterpart to [`extend_from_slice`](MiniVec::extend_from_slice).
+    /// Space for every element in `elems` is reserved up front so the vector is left unmodified
+    /// if an error is returned.
     ///
-    /// assert_eq!(vec.len(), 0);
-    /// assert_eq!(vec.capacity(), 128);
-    /// ```
+    /// # Errors
     ///
-    #[must_use]
-    pub fn with_capacity(capacity: usize) -> MiniVec<T> {
-        let mut v = MiniVec::new();
-        v.reserve_exact(capacity);
-        v
-    }
-
-    #[doc(hidden)]
-    pub unsafe fn unsafe_write(&mut self, idx: usize, elem: T) {
-        self.data().add(idx).write(elem);
-    }
-}
-
-impl<T: Clone> MiniVec<T> {
-    /// `extend_from_slice` will append each element from `elems` in a left-to-right order, cloning
-    /// each value in `elems`.
+    /// Returns a [`TryReserveError`](TryReserveError) if the required capacity overflows or the
+    /// allocator reports a failure.
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
     /// let mut vec = minivec::mini_vec![1, 2];
     ///
-    /// let s : &[i32] = &[3, 4];
-    ///
-    /// vec.extend_from_slice(s);
+    /// vec.try_extend_from_slice(&[3, 4]).unwrap();
     ///
     /// assert_eq!(vec, [1, 2, 3, 4]);
     /// ```
     ///
-    pub fn extend_from_slice(&mut self, elems: &[T]) {
-        self.reserve(elems.len());
-        for x in elems {
-            self.push((*x).clone());
+    pub fn try_extend_from_slice(&mut self, elems: &[T]) -> Result<(), TryReserveError> {
+        self.try_reserve(elems.len())?;
+        self.extend_cloned(elems);
+
+        Ok(())
+    }
+}
+
+fn handle_reserve_error(err: TryReserveError) -> ! {
+    match err {
+        TryReserveError::CapacityOverflow => panic!("capacity overflow"),
+        #[cfg(feature = "alloc")]
+        TryReserveError::AllocError { layout } => alloc::alloc::handle_alloc_error(layout),
+        // without `alloc` there's no allocation error hook to defer to
+        //
+        #[cfg(not(feature = "alloc"))]
+        TryReserveError::AllocError { layout } => {
+            panic!("memory allocation of {} bytes failed", layout.size())
         }
+        TryReserveError::InvalidAlignment(err) => panic!("invalid alignment: {:?}", err),
     }
 }
 
-unsafe impl<T: core::marker::Send> core::marker::Send for MiniVec<T> {}
-unsafe impl<T: core::marker::Sync> core::marker::Sync for MiniVec<T> {}
+unsafe impl<T, A, H> core::marker::Send for MiniVec<T, A, H>
+where
+    T: core::marker::Send,
+    A: Allocator + core::marker::Send,
+    H: core::marker::Send,
+{
+}
+unsafe impl<T, A, H> core::marker::Sync for MiniVec<T, A, H>
+where
+    T: core::marker::Sync,
+    A: Allocator + core::marker::Sync,
+    H: core::marker::Sync,
+{
+}
 
 /// `mini_vec!` is a macro similar in spirit to the stdlib's `vec!`.
 ///
@@ -1470,25 +3267,62 @@ unsafe impl<T: core::marker::Sync> core::marker::Sync for MiniVec<T> {}
 /// * `mini_vec!()`
 /// * `mini_vec![val1, val2, val3, ...]`
 /// * `mini_vec![val; num_elems]`
+/// * `mini_vec![align = alignment; val1, val2, val3, ...]`
+/// * `mini_vec![align = alignment; val; num_elems]`
+///
+/// The repeat forms evaluate `val` once, cloning it into all but the last slot which it's moved
+/// into.
+///
+/// The `align = ` forms return a vector whose allocation is aligned to `alignment`, as if created
+/// by [`with_alignment`](MiniVec::with_alignment). A literal alignment that isn't a power of two is
+/// rejected at compile time, other invalid alignments panic.
+///
+/// # Example
+///
+/// ```
+/// use minivec::mini_vec;
 ///
+/// let zeroes = mini_vec![align = 32; 0.0f32; 1024];
+/// assert_eq!(zeroes.alignment(), 32);
+/// assert_eq!(zeroes.as_ptr() as usize % 32, 0);
+/// assert_eq!(zeroes.len(), 1024);
+///
+/// let abc = mini_vec![align = 64; 'a', 'b', 'c'];
+/// assert_eq!(abc.alignment(), 64);
+/// assert_eq!(abc, ['a', 'b', 'c']);
+/// ```
+///
+/// ```compile_fail
+/// let v = minivec::mini_vec![align = 24; 0u8; 16];
+/// ```
+///
+#[cfg(feature = "alloc")]
 #[macro_export]
 macro_rules! mini_vec {
     () => (
         $crate::MiniVec::new()
     );
-    ($elem:expr; $n:expr) => {
+    (align = $align:literal; $elem:expr; $n:expr) => {
         {
-            let mut tmp = $crate::MiniVec::with_capacity($n);
-
-            for idx in 0..$n {
-                unsafe { tmp.unsafe_write(idx, $elem.clone()) };
-            }
-
-            unsafe { tmp.set_len($n) };
-
-            tmp
+            const { ::core::assert!(($align as usize).is_power_of_two(), "alignment must be a power of two") };
+            $crate::MiniVec::from_elem_aligned($elem, $n, $align)
+        }
+    };
+    (align = $align:expr; $elem:expr; $n:expr) => {
+        $crate::MiniVec::from_elem_aligned($elem, $n, $align)
+    };
+    (align = $align:literal; $($x:expr),* $(,)?) => {
+        {
+            const { ::core::assert!(($align as usize).is_power_of_two(), "alignment must be a power of two") };
+            $crate::MiniVec::from_array_aligned([$($x),*], $align)
         }
-     };
+    };
+    (align = $align:expr; $($x:expr),* $(,)?) => {
+        $crate::MiniVec::from_array_aligned([$($x),*], $align)
+    };
+    ($elem:expr; $n:expr) => {
+        $crate::MiniVec::from_elem($elem, $n)
+    };
     ($($x:expr),+ $(,)?) => {
         {
             let mut tmp = $crate::MiniVec::new();
@@ -1499,3 +3333,61 @@ macro_rules! mini_vec {
         }
     };
 }
+
+/// `static_mini_vec!` creates a `MiniVec` whose header and elements live in static memory,
+/// making it usable in `static` and `const` items.
+///
+/// The elements must be `Copy` and no more aligned than a `usize`. The resulting vector never
+/// frees its static storage and copies the elements into an allocation of its own the first time
+/// it's mutated, so every copy of it starts out with the same contents.
+///
+/// # Example
+///
+/// ```
+/// use minivec::{static_mini_vec, MiniVec};
+///
+/// static PRIMES: MiniVec<u32> = static_mini_vec![2, 3, 5, 7];
+/// assert_eq!(PRIMES.capacity(), 4);
+///
+/// let mut primes = PRIMES.clone();
+/// primes.push(11);
+///
+/// assert_eq!(PRIMES, [2, 3, 5, 7]);
+/// assert_eq!(primes, [2, 3, 5, 7, 11]);
+///
+/// const EMPTY: MiniVec<u32> = static_mini_vec![];
+/// assert!(EMPTY.is_empty());
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! static_mini_vec {
+    ($($x:expr),* $(,)?) => {
+        $crate::MiniVec::from_static(&const { $crate::StaticStorage::new([$($x),*]) })
+    };
+}
+
+/// `mini_format!` is a macro similar in spirit to the stdlib's `format!` which produces a
+/// [`MiniString`](crate::MiniString).
+///
+/// # Example
+///
+/// ```
+/// let name = "world";
+/// let s = minivec::mini_format!("hello, {}! {}", name, 42);
+///
+/// assert_eq!(s, "hello, world! 42");
+/// ```
+///
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! mini_format {
+    ($($arg:tt)*) => {
+        {
+            let mut tmp = $crate::MiniString::new();
+            ::core::fmt::Write::write_fmt(&mut tmp, ::core::format_args!($($arg)*))
+                .expect("a formatting trait implementation returned an error");
+            tmp
+        }
+    };
+}
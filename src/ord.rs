@@ -1,9 +1,10 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T: Ord> core::cmp::Ord for MiniVec<T> {
+impl<T: Ord, A: Allocator, H> core::cmp::Ord for MiniVec<T, A, H> {
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
-        let x: &[T] = &**self;
-        let y: &[T] = &**other;
+        let x: &[T] = self;
+        let y: &[T] = other;
 
         x.cmp(y)
     }
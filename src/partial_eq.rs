@@ -1,27 +1,77 @@
+use crate::Allocator;
 use crate::MiniVec;
 
-impl<T, V> PartialEq<V> for MiniVec<T>
+impl<T, A: Allocator, H, V> PartialEq<V> for MiniVec<T, A, H>
 where
     V: AsRef<[T]>,
     T: PartialEq,
 {
     fn eq(&self, other: &V) -> bool {
-        let lhs: &[T] = &*self;
+        let lhs: &[T] = self;
         let rhs: &[T] = other.as_ref();
 
         lhs == rhs
     }
 }
 
-impl<T, V> PartialOrd<V> for MiniVec<T>
+impl<T, A: Allocator, H, V> PartialOrd<V> for MiniVec<T, A, H>
 where
     V: AsRef<[T]>,
     T: PartialOrd,
 {
     fn partial_cmp(&self, other: &V) -> Option<core::cmp::Ordering> {
-        let x: &[T] = &**self;
+        let x: &[T] = self;
         let y: &[T] = other.as_ref();
 
-        x.partial_cmp(&y)
+        x.partial_cmp(y)
+    }
+}
+
+// the impls above only cover `MiniVec` on the left-hand side so we provide the mirrored
+// comparisons for the common slice-like types
+//
+#[cfg(feature = "alloc")]
+impl<T, A: Allocator, H> PartialEq<MiniVec<T, A, H>> for alloc::vec::Vec<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &MiniVec<T, A, H>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T, A: Allocator, H> PartialEq<MiniVec<T, A, H>> for [T]
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &MiniVec<T, A, H>) -> bool {
+        self == other.as_slice()
+    }
+}
+
+impl<T, A: Allocator, H> PartialEq<MiniVec<T, A, H>> for &[T]
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &MiniVec<T, A, H>) -> bool {
+        *self == other.as_slice()
+    }
+}
+
+impl<T, A: Allocator, H> PartialEq<MiniVec<T, A, H>> for &mut [T]
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &MiniVec<T, A, H>) -> bool {
+        *self == other.as_slice()
+    }
+}
+
+impl<T, A: Allocator, H, const N: usize> PartialEq<MiniVec<T, A, H>> for [T; N]
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &MiniVec<T, A, H>) -> bool {
+        self.as_slice() == other.as_slice()
     }
 }
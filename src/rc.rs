@@ -0,0 +1,37 @@
+use crate::shared::{shared_slice, RawShared};
+use crate::{Allocator, FrozenSlice, Global, MiniVec};
+
+use core::cell::Cell;
+
+shared_slice! {
+    /// `MiniRc` is a single-threaded, reference-counted, single-pointer shared slice.
+    ///
+    /// `MiniRc` is the non-atomic counterpart of [`MiniArc`](crate::MiniArc), storing a plain
+    /// reference count right next to the length and capacity in the header of a single allocation.
+    ///
+    /// A `MiniRc` is created from a [`MiniVec`](crate::MiniVec), adopting its allocation and
+    /// growing it in place if the reference count doesn't fit in front of the elements yet. A
+    /// unique `MiniRc` can be turned back into a `MiniVec` with its original alignment, again
+    /// without copying, via [`try_unwrap`](MiniRc::try_unwrap).
+    ///
+    /// Like the stdlib's `Rc`, all of the functionality specific to `MiniRc` is exposed through
+    /// associated functions so as not to clash with the methods of the slice it dereferences to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minivec::{mini_vec, MiniRc};
+    ///
+    /// let shared = MiniRc::from(mini_vec![1, 2, 3]);
+    /// assert_eq!(core::mem::size_of_val(&shared), core::mem::size_of::<usize>());
+    ///
+    /// let other = shared.clone();
+    /// assert!(MiniRc::ptr_eq(&shared, &other));
+    /// assert_eq!(other.iter().sum::<i32>(), 6);
+    ///
+    /// drop(other);
+    /// assert_eq!(MiniRc::try_unwrap(shared).unwrap(), [1, 2, 3]);
+    /// ```
+    ///
+    MiniRc, Cell<usize>
+}
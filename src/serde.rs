@@ -1,19 +1,23 @@
+use crate::Allocator;
+use crate::FrozenSlice;
+use crate::MiniBox;
+use crate::MiniString;
 use crate::MiniVec;
 
 use serde::ser::{Serialize, Serializer};
-use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor, DeserializeSeed};
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor, DeserializeSeed, Error, Unexpected};
 
 use core::{cmp, fmt};
 use core::marker::PhantomData;
 
-impl<T: Serialize> Serialize for MiniVec<T> {
+impl<T: Serialize, A: Allocator> Serialize for MiniVec<T, A> {
     #[inline]
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         serializer.collect_seq(self)
     }
 }
 
-#[inline(always)]
+#[inline]
 fn map_size_hint(hint: Option<usize>) -> usize {
     match hint {
         Some(hint) => cmp::min(hint, 1024),
@@ -26,7 +30,7 @@ fn map_size_hint(hint: Option<usize>) -> usize {
 //Taken from serde
 struct InPlaceSeed<'a, T: 'a>(pub &'a mut T);
 
-impl<'a, 'de, T: Deserialize<'de>> DeserializeSeed<'de> for InPlaceSeed<'a, T> {
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for InPlaceSeed<'_, T> {
     type Value = ();
 
     #[inline]
@@ -71,7 +75,7 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for MiniVec<T> {
     fn deserialize_in_place<D: Deserializer<'de>>(deserializer: D, place: &mut Self) -> Result<(), D::Error> {
         struct VecInPlaceVisitor<'a, T: 'a>(&'a mut MiniVec<T>);
 
-        impl<'a, 'de, T: Deserialize<'de>> Visitor<'de> for VecInPlaceVisitor<'a, T> {
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for VecInPlaceVisitor<'_, T> {
             type Value = ();
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -107,27 +111,106 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for MiniVec<T> {
     }
 }
 
+impl<A: Allocator> Serialize for MiniString<A> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for MiniString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StringVisitor;
+
+        impl Visitor<'_> for StringVisitor {
+            type Value = MiniString;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(MiniString::from(v))
+            }
+
+            fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                match core::str::from_utf8(v) {
+                    Ok(s) => Ok(MiniString::from(s)),
+                    Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_string(StringVisitor)
+    }
+}
+
+impl<T: ?Sized + FrozenSlice + Serialize, A: Allocator> Serialize for MiniBox<T, A> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for MiniBox<[T]> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        MiniVec::<T>::deserialize(deserializer).map(MiniVec::into_frozen)
+    }
+}
+
+impl<'de> Deserialize<'de> for MiniBox<str> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        MiniString::deserialize(deserializer).map(MiniString::into_frozen)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::MiniVec;
+    use crate::{MiniBox, MiniString, MiniVec};
 
     use serde::de::{Deserialize};
-    use serde::de::value::{SeqDeserializer, Error as ValueError};
+    use serde::de::value::{SeqDeserializer, StrDeserializer, BytesDeserializer, Error as ValueError};
 
     #[test]
     fn should_deserialize() {
         let input = [1u32, 2, 3, 10, 5];
-        let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().cloned());
+        let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
         let result = MiniVec::<u32>::deserialize(deserializer).expect("To deserialize");
         assert_eq!(result, input);
 
-        let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().cloned());
+        let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
         let mut vec = MiniVec::<u32>::new();
         MiniVec::<u32>::deserialize_in_place(deserializer, &mut vec).expect("To deserialize");
         assert_eq!(vec, input);
 
-        let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().cloned());
+        let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
         MiniVec::<u32>::deserialize_in_place(deserializer, &mut vec).expect("To deserialize");
         assert_eq!(vec, input);
     }
+
+    #[test]
+    fn should_deserialize_string() {
+        use serde::de::IntoDeserializer;
+
+        let deserializer: StrDeserializer<'_, ValueError> = "hello".into_deserializer();
+        let result = MiniString::deserialize(deserializer).expect("To deserialize");
+        assert_eq!(result, "hello");
+
+        let deserializer: BytesDeserializer<'_, ValueError> = BytesDeserializer::new(b"\xff");
+        assert!(MiniString::deserialize(deserializer).is_err());
+    }
+
+    #[test]
+    fn should_deserialize_frozen() {
+        use serde::de::IntoDeserializer;
+
+        let input = [1u32, 2, 3];
+        let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
+        let result = MiniBox::<[u32]>::deserialize(deserializer).expect("To deserialize");
+        assert_eq!(result, input);
+
+        let deserializer: StrDeserializer<'_, ValueError> = "frozen".into_deserializer();
+        let result = MiniBox::<str>::deserialize(deserializer).expect("To deserialize");
+        assert_eq!(result, "frozen");
+    }
 }
@@ -0,0 +1,707 @@
+use crate::r#impl::helpers::{data_offset, make_layout, max_align, next_aligned};
+use crate::{empty_header, write_header, zst_buf, Header};
+use crate::{Allocator, MiniVec};
+
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::ptr::NonNull;
+use core::sync::atomic::{self, AtomicUsize, Ordering};
+
+// the reference count used by `MiniRc` and `MiniArc` respectively
+//
+pub trait RefCount {
+    fn one() -> Self;
+
+    fn get(&self) -> usize;
+
+    fn increment(&self);
+
+    // returns `true` when the last reference was released
+    //
+    fn decrement(&self) -> bool;
+
+    // returns `true` when the caller holds the only reference, in which case the count is left in
+    // an unspecified state as the caller is about to take ownership of the allocation
+    //
+    fn try_take(&self) -> bool;
+}
+
+impl RefCount for Cell<usize> {
+    fn one() -> Self {
+        Cell::new(1)
+    }
+
+    fn get(&self) -> usize {
+        Cell::get(self)
+    }
+
+    fn increment(&self) {
+        let count = self.get();
+        assert!(count < isize::MAX as usize, "reference count overflow");
+        self.set(count + 1);
+    }
+
+    fn decrement(&self) -> bool {
+        let count = self.get() - 1;
+        self.set(count);
+        count == 0
+    }
+
+    fn try_take(&self) -> bool {
+        self.get() == 1
+    }
+}
+
+impl RefCount for AtomicUsize {
+    fn one() -> Self {
+        AtomicUsize::new(1)
+    }
+
+    fn get(&self) -> usize {
+        self.load(Ordering::Acquire)
+    }
+
+    fn increment(&self) {
+        // mirrors the stdlib's `Arc`, a new reference can only be made from an existing one so
+        // there's nothing to synchronize with
+        //
+        let count = self.fetch_add(1, Ordering::Relaxed);
+        assert!(count < isize::MAX as usize, "reference count overflow");
+    }
+
+    fn decrement(&self) -> bool {
+        if self.fetch_sub(1, Ordering::Release) != 1 {
+            return false;
+        }
+
+        atomic::fence(Ordering::Acquire);
+        true
+    }
+
+    fn try_take(&self) -> bool {
+        self.compare_exchange(1, 0, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+}
+
+// a shared allocation uses the same layout as a `MiniVec` with the reference count stored in the
+// word following the regular header
+//
+// as the larger header pushes the data back by a word for default-aligned vectors, adopting or
+// handing back such an allocation grows or shrinks it in place and shifts the elements over,
+// just like converting between a `MiniVec` and a `MiniBox`
+//
+#[repr(C)]
+struct SharedHeader<C> {
+    len: usize,
+    cap: usize,
+    alignment: usize,
+    count: C,
+}
+
+fn shared_offset<C>(alignment: usize) -> usize {
+    next_aligned(core::mem::size_of::<SharedHeader<C>>(), alignment)
+}
+
+fn shared_layout<T, C>(capacity: usize, alignment: usize) -> Layout {
+    let layout = make_layout::<T, ()>(capacity, alignment);
+    let extra = shared_offset::<C>(alignment) - data_offset::<()>(alignment);
+
+    layout
+        .size()
+        .checked_add(extra)
+        .and_then(|size| Layout::from_size_align(size, alignment).ok())
+        .expect("capacity overflow")
+}
+
+// `RawShared` implements the reference counting shared by `MiniRc` and `MiniArc`
+//
+// unallocated instances point at the same empty header sentinel as `MiniVec` and are never
+// reference counted. Zero-sized types always allocate a header when non-empty so that their
+// elements are only dropped once
+//
+pub struct RawShared<T, A: Allocator, C: RefCount> {
+    buf: NonNull<u8>,
+    allocator: A,
+    phantom: core::marker::PhantomData<(T, C)>,
+}
+
+impl<T, A: Allocator, C: RefCount> RawShared<T, A, C> {
+    fn is_allocated(&self) -> bool {
+        self.buf != empty_header()
+    }
+
+    fn header(&self) -> &SharedHeader<C> {
+        debug_assert!(self.is_allocated());
+
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            self.buf.cast::<SharedHeader<C>>().as_ref()
+        }
+    }
+
+    fn data(&self) -> *mut T {
+        if core::mem::size_of::<T>() == 0 || !self.is_allocated() {
+            return NonNull::dangling().as_ptr();
+        }
+
+        let offset = shared_offset::<C>(self.header().alignment);
+        unsafe { self.buf.as_ptr().add(offset).cast::<T>() }
+    }
+
+    fn layout(&self) -> Layout {
+        let header = self.header();
+        shared_layout::<T, C>(header.cap, header.alignment)
+    }
+
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.data(), self.len()) }
+    }
+
+    pub fn get_mut(&mut self) -> Option<&mut [T]> {
+        if self.is_unique() {
+            Some(unsafe { core::slice::from_raw_parts_mut(self.data(), self.len()) })
+        } else {
+            None
+        }
+    }
+
+    pub fn is_unique(&self) -> bool {
+        !self.is_allocated() || self.header().count.get() == 1
+    }
+
+    pub fn len(&self) -> usize {
+        if self.is_allocated() {
+            self.header().len
+        } else {
+            0
+        }
+    }
+
+    pub fn make_mut(&mut self) -> &mut [T]
+    where
+        T: Clone,
+        A: Clone,
+    {
+        if !self.is_unique() {
+            let mut copy = MiniVec::new_in(self.allocator.clone());
+            copy.grow(self.len(), self.header().alignment);
+            copy.extend_from_slice(self.as_slice());
+
+            *self = RawShared::from(copy);
+        }
+
+        unsafe { core::slice::from_raw_parts_mut(self.data(), self.len()) }
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.buf == other.buf
+    }
+
+    pub fn strong_count(&self) -> usize {
+        if self.is_allocated() {
+            self.header().count.get()
+        } else {
+            1
+        }
+    }
+
+    pub fn try_unwrap(self) -> Result<MiniVec<T, A>, Self> {
+        if self.is_allocated() && !self.header().count.try_take() {
+            return Err(self);
+        }
+
+        let this = core::mem::ManuallyDrop::new(self);
+        let allocator = unsafe { core::ptr::read(core::ptr::addr_of!(this.allocator)) };
+
+        let mut vec = MiniVec::new_in(allocator);
+        if !this.is_allocated() {
+            return Ok(vec);
+        }
+
+        let len = this.len();
+
+        if core::mem::size_of::<T>() == 0 {
+            unsafe { vec.allocator.deallocate(this.buf, this.layout()) };
+            vec.buf = zst_buf(len);
+            return Ok(vec);
+        }
+
+        let (cap, alignment) = (this.header().cap, this.header().alignment);
+        let old_layout = this.layout();
+        let new_layout = make_layout::<T, ()>(cap, alignment);
+
+        let buf = if old_layout.size() == new_layout.size() {
+            this.buf
+        } else {
+            unsafe {
+                let src = this.data();
+                let dst = this
+                    .buf
+                    .as_ptr()
+                    .add(data_offset::<()>(alignment))
+                    .cast::<T>();
+                core::ptr::copy(src, dst, len);
+
+                match vec.allocator.shrink(this.buf, old_layout, new_layout) {
+                    Ok(p) => p.cast::<u8>(),
+                    Err(_) => alloc::alloc::handle_alloc_error(new_layout),
+                }
+            }
+        };
+
+        let header = Header {
+            len,
+            cap,
+            alignment,
+        };
+
+        unsafe { write_header::<()>(buf, header) };
+        vec.buf = buf;
+        Ok(vec)
+    }
+}
+
+impl<T, A: Allocator + Clone, C: RefCount> Clone for RawShared<T, A, C> {
+    fn clone(&self) -> Self {
+        if self.is_allocated() {
+            self.header().count.increment();
+        }
+
+        RawShared {
+            buf: self.buf,
+            allocator: self.allocator.clone(),
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, A: Allocator, C: RefCount> Drop for RawShared<T, A, C> {
+    fn drop(&mut self) {
+        if !self.is_allocated() || !self.header().count.decrement() {
+            return;
+        }
+
+        unsafe {
+            core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(self.data(), self.len()));
+
+            self.allocator.deallocate(self.buf, self.layout());
+        }
+    }
+}
+
+impl<T, A: Allocator, C: RefCount> From<MiniVec<T, A>> for RawShared<T, A, C> {
+    fn from(vec: MiniVec<T, A>) -> Self {
+        let mut vec = vec;
+        vec.unshare();
+
+        let len = vec.len();
+        let vec = core::mem::ManuallyDrop::new(vec);
+        let allocator = unsafe { core::ptr::read(core::ptr::addr_of!(vec.allocator)) };
+
+        let alloc_or_abort = |result: Result<NonNull<[u8]>, _>, layout| match result {
+            Ok(p) => p.cast::<u8>(),
+            Err(_) => alloc::alloc::handle_alloc_error(layout),
+        };
+
+        if len == 0 {
+            if vec.is_allocated() {
+                let layout = make_layout::<T, ()>(vec.capacity(), vec.layout_alignment());
+                unsafe { allocator.deallocate(vec.buf, layout) };
+            }
+
+            return RawShared {
+                buf: empty_header(),
+                allocator,
+                phantom: core::marker::PhantomData,
+            };
+        }
+
+        let (buf, cap, alignment) = if core::mem::size_of::<T>() == 0 {
+            let alignment = max_align::<T>();
+            let layout = shared_layout::<T, C>(0, alignment);
+            (
+                alloc_or_abort(allocator.allocate(layout), layout),
+                0,
+                alignment,
+            )
+        } else {
+            // the allocation is always adopted, growing it to make room for the reference count
+            // when the elements don't already start far enough into it
+            //
+            let (cap, alignment) = (vec.capacity(), vec.layout_alignment());
+            let old_layout = make_layout::<T, ()>(cap, alignment);
+            let new_layout = shared_layout::<T, C>(cap, alignment);
+
+            let buf = if old_layout.size() == new_layout.size() {
+                vec.buf
+            } else {
+                let buf = alloc_or_abort(
+                    unsafe { allocator.grow(vec.buf, old_layout, new_layout) },
+                    new_layout,
+                );
+
+                unsafe {
+                    let src = buf.as_ptr().add(data_offset::<()>(alignment)).cast::<T>();
+                    let dst = buf.as_ptr().add(shared_offset::<C>(alignment)).cast::<T>();
+                    core::ptr::copy(src, dst, len);
+                }
+
+                buf
+            };
+
+            (buf, cap, alignment)
+        };
+
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            core::ptr::write(
+                buf.cast::<SharedHeader<C>>().as_ptr(),
+                SharedHeader {
+                    len,
+                    cap,
+                    alignment,
+                    count: C::one(),
+                },
+            );
+        }
+
+        RawShared {
+            buf,
+            allocator,
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+// `MiniRc` and `MiniArc` only differ in their reference count and in being thread-safe so both
+// are generated from this single definition. It takes the attributes and name of the struct along
+// with the type of its reference count and expects the invoking module to import the types used
+//
+macro_rules! shared_slice {
+    ($(#[$attr:meta])* $name:ident, $count:ty) => {
+        $(#[$attr])*
+        pub struct $name<T: ?Sized + FrozenSlice, A: Allocator = Global> {
+            raw: RawShared<T::Elem, A, $count>,
+        }
+
+        impl<T, A: Allocator> $name<[T], A> {
+            /// `allocator` returns a reference to the allocator backing the shared slice.
+            ///
+            #[must_use]
+            pub fn allocator(this: &Self) -> &A {
+                this.raw.allocator()
+            }
+
+            /// `get_mut` returns a mutable reference to the slice if `this` is the only
+            /// reference to it.
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!(
+                " let mut shared = minivec::",
+                stringify!($name),
+                "::from(minivec::mini_vec![1, 2, 3]);"
+            )]
+            #[doc = concat!(
+                " minivec::",
+                stringify!($name),
+                "::get_mut(&mut shared).unwrap()[0] = 4;"
+            )]
+            ///
+            /// let other = shared.clone();
+            #[doc = concat!(
+                " assert!(minivec::",
+                stringify!($name),
+                "::get_mut(&mut shared).is_none());"
+            )]
+            ///
+            /// assert_eq!(other, [4, 2, 3]);
+            /// ```
+            ///
+            pub fn get_mut(this: &mut Self) -> Option<&mut [T]> {
+                this.raw.get_mut()
+            }
+
+            /// `make_mut` returns a mutable reference to the slice, first cloning the elements
+            #[doc = concat!(
+                " into a new allocation should `this` share them with any other `",
+                stringify!($name),
+                "`."
+            )]
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!(
+                " let mut shared = minivec::",
+                stringify!($name),
+                "::from(minivec::mini_vec![1, 2, 3]);"
+            )]
+            /// let other = shared.clone();
+            ///
+            #[doc = concat!(" minivec::", stringify!($name), "::make_mut(&mut shared)[0] = 4;")]
+            ///
+            /// assert_eq!(shared, [4, 2, 3]);
+            /// assert_eq!(other, [1, 2, 3]);
+            /// ```
+            ///
+            pub fn make_mut(this: &mut Self) -> &mut [T]
+            where
+                T: Clone,
+                A: Clone,
+            {
+                this.raw.make_mut()
+            }
+
+            #[doc = concat!(
+                " `ptr_eq` returns whether or not both `",
+                stringify!($name),
+                "`s point to the same allocation."
+            )]
+            ///
+            /// Note: empty slices never allocate so all of them compare equal.
+            ///
+            #[must_use]
+            pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+                this.raw.ptr_eq(&other.raw)
+            }
+
+            #[doc = concat!(
+                " `strong_count` returns the number of `",
+                stringify!($name),
+                "`s pointing to this allocation."
+            )]
+            ///
+            /// Note: empty slices aren't reference counted and always report a count of 1.
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!(
+                " let shared = minivec::",
+                stringify!($name),
+                "::from(minivec::mini_vec![1, 2, 3]);"
+            )]
+            /// let other = shared.clone();
+            ///
+            #[doc = concat!(
+                " assert_eq!(minivec::",
+                stringify!($name),
+                "::strong_count(&shared), 2);"
+            )]
+            /// drop(other);
+            #[doc = concat!(
+                " assert_eq!(minivec::",
+                stringify!($name),
+                "::strong_count(&shared), 1);"
+            )]
+            /// ```
+            ///
+            #[must_use]
+            pub fn strong_count(this: &Self) -> usize {
+                this.raw.strong_count()
+            }
+
+            /// `try_unwrap` returns the underlying [`MiniVec`](crate::MiniVec) if `this` is the
+            /// only reference to it. The allocation is handed back without copying any elements.
+            ///
+            /// # Errors
+            ///
+            #[doc = concat!(
+                " Returns `this` unchanged if the slice is shared with another `",
+                stringify!($name),
+                "`."
+            )]
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!(
+                " let shared = minivec::",
+                stringify!($name),
+                "::from(minivec::mini_vec![1, 2, 3]);"
+            )]
+            /// let other = shared.clone();
+            ///
+            #[doc = concat!(
+                " let shared = minivec::",
+                stringify!($name),
+                "::try_unwrap(shared).unwrap_err();"
+            )]
+            /// drop(other);
+            ///
+            #[doc = concat!(
+                " let mut vec = minivec::",
+                stringify!($name),
+                "::try_unwrap(shared).unwrap();"
+            )]
+            /// vec.push(4);
+            /// assert_eq!(vec, [1, 2, 3, 4]);
+            /// ```
+            ///
+            pub fn try_unwrap(this: Self) -> Result<MiniVec<T, A>, Self> {
+                this.raw.try_unwrap().map_err(|raw| $name { raw })
+            }
+
+            /// `unwrap_or_clone` returns the underlying [`MiniVec`](crate::MiniVec) if `this` is
+            /// the only reference to it and otherwise clones the elements into a new, unique
+            /// `MiniVec`.
+            ///
+            /// # Example
+            ///
+            /// ```
+            #[doc = concat!(
+                " let shared = minivec::",
+                stringify!($name),
+                "::from(minivec::mini_vec![1, 2, 3]);"
+            )]
+            /// let other = shared.clone();
+            ///
+            #[doc = concat!(
+                " let mut vec = minivec::",
+                stringify!($name),
+                "::unwrap_or_clone(shared);"
+            )]
+            /// vec.push(4);
+            ///
+            /// assert_eq!(vec, [1, 2, 3, 4]);
+            /// assert_eq!(other, [1, 2, 3]);
+            /// ```
+            ///
+            #[must_use]
+            pub fn unwrap_or_clone(this: Self) -> MiniVec<T, A>
+            where
+                T: Clone,
+                A: Clone,
+            {
+                let mut this = this;
+                Self::make_mut(&mut this);
+
+                match Self::try_unwrap(this) {
+                    Ok(vec) => vec,
+                    Err(_) => unreachable!(),
+                }
+            }
+        }
+
+        impl<T, A: Allocator + Clone> Clone for $name<[T], A> {
+            fn clone(&self) -> Self {
+                $name {
+                    raw: self.raw.clone(),
+                }
+            }
+        }
+
+        impl<T, A: Allocator> core::ops::Deref for $name<[T], A> {
+            type Target = [T];
+
+            fn deref(&self) -> &[T] {
+                self.raw.as_slice()
+            }
+        }
+
+        impl<T, A: Allocator> AsRef<[T]> for $name<[T], A> {
+            fn as_ref(&self) -> &[T] {
+                self
+            }
+        }
+
+        impl<T, A: Allocator> core::borrow::Borrow<[T]> for $name<[T], A> {
+            fn borrow(&self) -> &[T] {
+                self
+            }
+        }
+
+        impl<T: core::fmt::Debug, A: Allocator> core::fmt::Debug for $name<[T], A> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let this: &[T] = self;
+
+                this.fmt(f)
+            }
+        }
+
+        impl<T> Default for $name<[T]> {
+            fn default() -> Self {
+                $name::from(MiniVec::new())
+            }
+        }
+
+        impl<T: core::hash::Hash, A: Allocator> core::hash::Hash for $name<[T], A> {
+            fn hash<H>(&self, state: &mut H)
+            where
+                H: core::hash::Hasher,
+            {
+                let this: &[T] = self;
+                core::hash::Hash::hash(this, state);
+            }
+        }
+
+        impl<T: Eq, A: Allocator> Eq for $name<[T], A> {}
+
+        impl<T: Ord, A: Allocator> Ord for $name<[T], A> {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                let x: &[T] = self;
+                let y: &[T] = other;
+
+                x.cmp(y)
+            }
+        }
+
+        impl<T, A: Allocator, V> PartialEq<V> for $name<[T], A>
+        where
+            V: AsRef<[T]>,
+            T: PartialEq,
+        {
+            fn eq(&self, other: &V) -> bool {
+                let lhs: &[T] = self;
+                let rhs: &[T] = other.as_ref();
+
+                lhs == rhs
+            }
+        }
+
+        impl<T, A: Allocator, V> PartialOrd<V> for $name<[T], A>
+        where
+            V: AsRef<[T]>,
+            T: PartialOrd,
+        {
+            fn partial_cmp(&self, other: &V) -> Option<core::cmp::Ordering> {
+                let x: &[T] = self;
+                let y: &[T] = other.as_ref();
+
+                x.partial_cmp(y)
+            }
+        }
+
+        impl<T, A: Allocator> From<MiniVec<T, A>> for $name<[T], A> {
+            fn from(vec: MiniVec<T, A>) -> Self {
+                $name {
+                    raw: RawShared::from(vec),
+                }
+            }
+        }
+
+        impl<T: Clone> From<&[T]> for $name<[T]> {
+            fn from(s: &[T]) -> Self {
+                $name::from(MiniVec::from(s))
+            }
+        }
+
+        impl<T> core::iter::FromIterator<T> for $name<[T]> {
+            fn from_iter<I>(iter: I) -> Self
+            where
+                I: IntoIterator<Item = T>,
+            {
+                $name::from(iter.into_iter().collect::<MiniVec<T>>())
+            }
+        }
+    };
+}
+
+pub(crate) use shared_slice;
@@ -0,0 +1,1347 @@
+use crate::r#impl::helpers::{next_aligned, next_capacity, validate_alignment};
+use crate::{
+    handle_reserve_error, Drain, DrainFilter, LayoutErr, MiniVec, Splice, TryReserveError,
+};
+
+use core::mem::{ManuallyDrop, MaybeUninit};
+
+const WORD: usize = core::mem::size_of::<usize>();
+
+// the byte holding the least significant bits of the pointer, which are always zero for a
+// `MiniVec` as both its allocations and the empty header sentinel are at least word-aligned
+//
+const TAG: usize = if cfg!(target_endian = "little") {
+    0
+} else {
+    WORD - 1
+};
+
+// where the inline elements start within the word, they're placed around the tag byte such that
+// each element is suitably aligned
+//
+fn inline_offset<T>() -> usize {
+    if TAG == 0 {
+        next_aligned(1, core::mem::align_of::<T>())
+    } else {
+        0
+    }
+}
+
+fn inline_capacity<T>() -> usize {
+    let size = core::mem::size_of::<T>();
+    if size == 0 || core::mem::align_of::<T>() > core::mem::align_of::<usize>() {
+        return 0;
+    }
+
+    let end = if TAG == 0 { WORD } else { TAG };
+    end.saturating_sub(inline_offset::<T>()) / size
+}
+
+// when the low bit of the tag byte is set, the word stores `len << 1 | 1` in the tag byte and the
+// elements themselves in the remaining bytes. Otherwise, the word is a regular `MiniVec`
+//
+union Repr<T> {
+    heap: ManuallyDrop<MiniVec<T>>,
+    inline: [MaybeUninit<u8>; WORD],
+}
+
+fn inline_repr<T>(len: usize) -> Repr<T> {
+    debug_assert!(len <= inline_capacity::<T>());
+
+    #[allow(clippy::cast_possible_truncation)]
+    let tag = (len << 1 | 1) as u8;
+
+    let mut inline = [MaybeUninit::uninit(); WORD];
+    inline[TAG] = MaybeUninit::new(tag);
+    Repr { inline }
+}
+
+/// `MiniSmallVec` is a variant of [`MiniVec`](crate::MiniVec) which stores a handful of
+/// elements inline, in the pointer-sized word itself, only allocating once it outgrows them.
+///
+/// A `MiniVec` allocation is always at least word-aligned, leaving the low bits of its pointer
+/// free. `MiniSmallVec` uses the lowest one as a tag: when set, the rest of the tag byte holds the
+/// length and the remaining bytes of the word hold the elements. When the inline storage runs
+/// out, the elements are spilled into a regular `MiniVec` allocation.
+///
+/// The number of elements that fit inline is given by
+/// [`inline_capacity`](MiniSmallVec::inline_capacity) and depends on both the size and the
+/// alignment of `T`. On 64-bit targets, it's 7 for `u8`, 3 for `u16` and 1 for `u32`. Types that
+/// are larger or more aligned than that are always stored on the heap.
+///
+/// `MiniSmallVec` mirrors the API of `MiniVec`, save for the parts that need room in the word: it
+/// always uses the global allocator, so there are no `*_in` constructors, and it has no header
+/// extension. Operations that only `MiniVec` implements, such as [`splice`](MiniSmallVec::splice)
+/// and [`realign`](MiniSmallVec::realign), move inline elements onto the heap first.
+///
+/// # Example
+///
+/// ```
+/// use minivec::{MiniSmallVec, MiniVec};
+///
+/// let mut vec = MiniSmallVec::<u8>::new();
+/// vec.extend_from_slice(b"inline");
+///
+/// assert!(!vec.spilled());
+/// assert_eq!(core::mem::size_of_val(&vec), core::mem::size_of::<usize>());
+///
+/// vec.extend_from_slice(b" no more");
+/// assert!(vec.spilled());
+///
+/// let vec: MiniVec<u8> = vec.into();
+/// assert_eq!(vec, b"inline no more");
+/// ```
+///
+pub struct MiniSmallVec<T> {
+    repr: Repr<T>,
+}
+
+struct SetLenOnDrop<'a, T> {
+    vec: &'a mut MiniSmallVec<T>,
+    len: usize,
+}
+
+impl<T> Drop for SetLenOnDrop<'_, T> {
+    fn drop(&mut self) {
+        unsafe { self.vec.set_len(self.len) };
+    }
+}
+
+impl<T> MiniSmallVec<T> {
+    fn is_inline(&self) -> bool {
+        inline_capacity::<T>() > 0 && unsafe { self.repr.inline[TAG].assume_init() } & 1 == 1
+    }
+
+    fn from_heap(vec: MiniVec<T>) -> MiniSmallVec<T> {
+        let mut vec = vec;
+        vec.unshare();
+
+        MiniSmallVec {
+            repr: Repr {
+                heap: ManuallyDrop::new(vec),
+            },
+        }
+    }
+
+    fn heap(&self) -> &MiniVec<T> {
+        debug_assert!(!self.is_inline());
+        unsafe { &self.repr.heap }
+    }
+
+    fn heap_mut(&mut self) -> &mut MiniVec<T> {
+        debug_assert!(!self.is_inline());
+        unsafe { &mut self.repr.heap }
+    }
+
+    fn data(&self) -> *const T {
+        if !self.is_inline() {
+            return self.heap().data();
+        }
+
+        unsafe {
+            core::ptr::addr_of!(self.repr)
+                .cast::<u8>()
+                .add(inline_offset::<T>())
+                .cast::<T>()
+        }
+    }
+
+    fn data_mut(&mut self) -> *mut T {
+        if !self.is_inline() {
+            return self.heap_mut().data();
+        }
+
+        unsafe {
+            core::ptr::addr_of_mut!(self.repr)
+                .cast::<u8>()
+                .add(inline_offset::<T>())
+                .cast::<T>()
+        }
+    }
+
+    // moves the inline elements into `vec`, which must be empty and have room for all of them
+    //
+    fn spill_into(&mut self, vec: MiniVec<T>) {
+        debug_assert!(self.is_inline());
+        debug_assert!(vec.is_empty() && vec.capacity() >= self.len());
+
+        let len = self.len();
+        let mut vec = vec;
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(self.data(), vec.data(), len);
+            vec.set_len(len);
+        }
+
+        self.repr = Repr {
+            heap: ManuallyDrop::new(vec),
+        };
+    }
+
+    // moves the inline elements into a newly allocated `MiniVec` with room for `capacity`
+    // elements, leaving `self` untouched on failure
+    //
+    fn try_spill(&mut self, capacity: usize) -> Result<(), TryReserveError> {
+        let vec = MiniVec::try_with_capacity(capacity)?;
+        self.spill_into(vec);
+
+        Ok(())
+    }
+
+    // clones `count` elements into the spare capacity, which must already have room for them.
+    // `src` is handed the start of the elements and returns where to clone from, which lets the
+    // source lie within the vector itself. All pointers are derived from the guard because inline
+    // elements live inside `self`
+    //
+    unsafe fn extend_cloned<F>(&mut self, count: usize, src: F)
+    where
+        F: FnOnce(*mut T) -> *const T,
+        T: Clone,
+    {
+        debug_assert!(self.capacity() - self.len() >= count);
+
+        let len = self.len();
+        let mut guard = SetLenOnDrop { vec: self, len };
+
+        let data = guard.vec.data_mut();
+        let src = src(data);
+        let dst = data.add(len);
+
+        for idx in 0..count {
+            dst.add(idx).write((*src.add(idx)).clone());
+            guard.len = len + idx + 1;
+        }
+    }
+
+    // moves the inline elements, if any, onto the heap for the operations that only `MiniVec`
+    // implements
+    //
+    fn spill(&mut self) -> &mut MiniVec<T> {
+        if self.is_inline() {
+            if let Err(err) = self.try_spill(self.len()) {
+                handle_reserve_error(err);
+            }
+        }
+
+        self.heap_mut()
+    }
+
+    fn try_reserve_inner(&mut self, additional: usize, exact: bool) -> Result<(), TryReserveError> {
+        if !self.is_inline() {
+            let vec = self.heap_mut();
+            return if exact {
+                vec.try_reserve_exact(additional)
+            } else {
+                vec.try_reserve(additional)
+            };
+        }
+
+        let required = self
+            .len()
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+
+        if required <= inline_capacity::<T>() {
+            return Ok(());
+        }
+
+        let capacity = if exact {
+            required
+        } else {
+            core::cmp::max(required, next_capacity::<T>(inline_capacity::<T>()))
+        };
+
+        self.try_spill(capacity)
+    }
+
+    /// `alignment` returns the alignment of the vector's heap allocation, see
+    /// [`MiniVec::alignment`](crate::MiniVec::alignment).
+    ///
+    /// Inline vectors report the default alignment, like a `MiniVec` that hasn't allocated. Their
+    /// elements are only aligned to `T`'s own alignment.
+    ///
+    #[must_use]
+    pub fn alignment(&self) -> usize {
+        if self.is_inline() {
+            MiniVec::<T>::new().alignment()
+        } else {
+            self.heap().alignment()
+        }
+    }
+
+    /// `append` moves every element out of `other` and onto the end of `self`, leaving `other`
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniSmallVec::from(&[1_u8, 2][..]);
+    /// let mut other = minivec::MiniSmallVec::from(&[3_u8, 4][..]);
+    ///
+    /// vec.append(&mut other);
+    ///
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    /// assert!(other.is_empty());
+    /// ```
+    ///
+    pub fn append(&mut self, other: &mut MiniSmallVec<T>) {
+        let len = self.len();
+        let count = other.len();
+
+        self.reserve(count);
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(other.data(), self.data_mut().add(len), count);
+            other.set_len(0);
+            self.set_len(len + count);
+        }
+    }
+
+    /// `as_mut_ptr` returns a `*mut T` to the first element, wherever it's stored.
+    ///
+    /// Note: unlike [`MiniVec::as_mut_ptr`](crate::MiniVec::as_mut_ptr), the pointer is never null
+    /// but it is invalidated by moving the vector while its elements are stored inline.
+    ///
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.data_mut()
+    }
+
+    /// `as_mut_slice` obtains a mutable reference to the elements as a slice.
+    ///
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    /// `as_ptr` returns a `*const T` to the first element, wherever it's stored.
+    ///
+    /// Note: unlike [`MiniVec::as_ptr`](crate::MiniVec::as_ptr), the pointer is never null but it
+    /// is invalidated by moving the vector while its elements are stored inline.
+    ///
+    #[must_use]
+    pub fn as_ptr(&self) -> *const T {
+        self.data()
+    }
+
+    /// `as_slice` obtains a reference to the elements as a slice.
+    ///
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    /// `capacity` returns the number of elements the vector can hold without allocating, or
+    /// reallocating once spilled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniSmallVec::<u8>::new();
+    /// assert_eq!(vec.capacity(), minivec::MiniSmallVec::<u8>::inline_capacity());
+    ///
+    /// let vec = minivec::MiniSmallVec::<u8>::with_capacity(128);
+    /// assert_eq!(vec.capacity(), 128);
+    /// ```
+    ///
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        if self.is_inline() {
+            inline_capacity::<T>()
+        } else {
+            self.heap().capacity()
+        }
+    }
+
+    /// `clear` drops every element of the vector without affecting its capacity.
+    ///
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// `dedup` "de-duplicates" all adjacent identical values in the vector.
+    ///
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|x, y| x == y);
+    }
+
+    /// `dedup_by` "de-duplicates" all adjacent elements for which `pred` returns true.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniSmallVec::from(&[1_u8, 1, 2, 3, 3, 3, 1][..]);
+    ///
+    /// vec.dedup_by(|x, y| x == y);
+    ///
+    /// assert_eq!(vec, [1, 2, 3, 1]);
+    /// ```
+    ///
+    pub fn dedup_by<F>(&mut self, mut pred: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.len();
+        if len < 2 {
+            return;
+        }
+
+        let data = self.data_mut();
+
+        let mut read = 1;
+        let mut write = 1;
+
+        while read < len {
+            let matches = unsafe { pred(&mut *data.add(read), &mut *data.add(write - 1)) };
+            if !matches {
+                if read != write {
+                    unsafe { core::ptr::swap(data.add(read), data.add(write)) };
+                }
+                write += 1;
+            }
+
+            read += 1;
+        }
+
+        self.truncate(write);
+    }
+
+    /// `dedup_by_key` "de-duplicates" all adjacent elements where `key(elem1) == key(elem2)`.
+    ///
+    pub fn dedup_by_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq<K>,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// `drain` removes the elements in `range` from the vector and returns them through an
+    /// iterator, see [`MiniVec::drain`](crate::MiniVec::drain).
+    ///
+    /// Note: a [`Drain`](crate::Drain) always operates on a `MiniVec` so inline elements are
+    /// first spilled onto the heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniSmallVec::from(&[1_u8, 2, 3, 4][..]);
+    ///
+    /// let drained: Vec<_> = vec.drain(1..3).collect();
+    ///
+    /// assert_eq!(drained, [2, 3]);
+    /// assert_eq!(vec, [1, 4]);
+    /// ```
+    ///
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: core::ops::RangeBounds<usize>,
+    {
+        self.spill().drain(range)
+    }
+
+    /// `drain_filter` removes every element for which `pred` returns `true` and yields them, see
+    /// [`MiniVec::drain_filter`](crate::MiniVec::drain_filter).
+    ///
+    /// Note: inline elements are moved onto the heap first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniSmallVec::from(&[1_u8, 2, 3, 4][..]);
+    ///
+    /// let evens: minivec::MiniVec<_> = vec.drain_filter(|x| *x % 2 == 0).collect();
+    ///
+    /// assert_eq!(evens, [2, 4]);
+    /// assert_eq!(vec, [1, 3]);
+    /// ```
+    ///
+    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, T, F>
+    where
+        F: core::ops::FnMut(&mut T) -> bool,
+    {
+        self.spill().drain_filter(pred)
+    }
+
+    /// `extend_from_slice` clones every element of `elems` onto the end of the vector.
+    ///
+    pub fn extend_from_slice(&mut self, elems: &[T])
+    where
+        T: Clone,
+    {
+        self.reserve(elems.len());
+        unsafe { self.extend_cloned(elems.len(), |_| elems.as_ptr()) };
+    }
+
+    /// `extend_from_within` clones the elements in `src` onto the end of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` is out of bounds or if its start is greater than its end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniSmallVec::from(&[1_u8, 2][..]);
+    ///
+    /// vec.extend_from_within(..);
+    /// vec.extend_from_within(1..=1);
+    ///
+    /// assert_eq!(vec, [1, 2, 1, 2, 2]);
+    /// ```
+    ///
+    pub fn extend_from_within<R>(&mut self, src: R)
+    where
+        R: core::ops::RangeBounds<usize>,
+        T: Clone,
+    {
+        let start = match src.start_bound() {
+            core::ops::Bound::Included(&n) => n,
+            core::ops::Bound::Excluded(&n) => n + 1,
+            core::ops::Bound::Unbounded => 0,
+        };
+
+        let end = match src.end_bound() {
+            core::ops::Bound::Included(&n) => n + 1,
+            core::ops::Bound::Excluded(&n) => n,
+            core::ops::Bound::Unbounded => self.len(),
+        };
+
+        let count = self.as_slice()[start..end].len();
+        self.reserve(count);
+
+        // reserving may have moved the elements, possibly out of line, so the source is only
+        // located afterwards. It never overlaps with the spare capacity being written to
+        //
+        unsafe { self.extend_cloned(count, |data| data.add(start)) };
+    }
+
+    /// `inline_capacity` returns the number of elements that fit inside the `MiniSmallVec`
+    /// itself, without allocating.
+    ///
+    /// Zero-sized types never allocate to begin with so they always report an inline capacity of
+    /// 0, as do types too large or too aligned to fit the word.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minivec::MiniSmallVec;
+    ///
+    /// let word = core::mem::size_of::<usize>();
+    ///
+    /// assert_eq!(MiniSmallVec::<u8>::inline_capacity(), word - 1);
+    /// assert_eq!(MiniSmallVec::<usize>::inline_capacity(), 0);
+    /// assert_eq!(MiniSmallVec::<()>::inline_capacity(), 0);
+    /// ```
+    ///
+    #[must_use]
+    pub fn inline_capacity() -> usize {
+        inline_capacity::<T>()
+    }
+
+    /// `insert` places `element` at `index`, shifting every element after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len()`.
+    ///
+    pub fn insert(&mut self, index: usize, element: T) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+
+        self.reserve(1);
+
+        unsafe {
+            let p = self.data_mut().add(index);
+            core::ptr::copy(p, p.add(1), len - index);
+            core::ptr::write(p, element);
+            self.set_len(len + 1);
+        }
+    }
+
+    /// `into_raw_parts` decomposes the vector into the raw parts of a
+    /// [`MiniVec`](crate::MiniVec), see [`MiniVec::into_raw_parts`](crate::MiniVec::into_raw_parts).
+    ///
+    /// Note: inline elements are moved onto the heap first, the parts can only be reassembled via
+    /// [`MiniVec::from_raw_parts`](crate::MiniVec::from_raw_parts).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniSmallVec::from(&[1_u8, 2, 3][..]);
+    ///
+    /// let (ptr, len, cap) = vec.into_raw_parts();
+    /// let vec = unsafe { minivec::MiniVec::from_raw_parts(ptr, len, cap) };
+    ///
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    ///
+    #[must_use]
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        self.into_vec().into_raw_parts()
+    }
+
+    /// `into_vec` converts the vector into a [`MiniVec`](crate::MiniVec), allocating only if the
+    /// elements are currently stored inline.
+    ///
+    #[must_use]
+    pub fn into_vec(self) -> MiniVec<T> {
+        MiniVec::from(self)
+    }
+
+    /// `is_empty` returns whether or not the vector has a length of zero.
+    ///
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `leak` moves the elements onto the heap, if they aren't there already, and leaks them.
+    /// See [`MiniVec::leak`](crate::MiniVec::leak).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniSmallVec::from(&[1_u8, 2, 3][..]);
+    ///
+    /// let leaked: &'static mut [u8] = minivec::MiniSmallVec::leak(vec);
+    /// leaked[0] += 1;
+    ///
+    /// assert_eq!(leaked, [2, 2, 3]);
+    /// ```
+    ///
+    #[must_use]
+    pub fn leak<'a>(vec: MiniSmallVec<T>) -> &'a mut [T]
+    where
+        T: 'a,
+    {
+        MiniVec::leak(vec.into_vec())
+    }
+
+    /// `len` returns the number of elements in the vector.
+    ///
+    #[must_use]
+    pub fn len(&self) -> usize {
+        if self.is_inline() {
+            usize::from(unsafe { self.repr.inline[TAG].assume_init() } >> 1)
+        } else {
+            self.heap().len()
+        }
+    }
+
+    /// `new` constructs an empty `MiniSmallVec`.
+    ///
+    /// Note: does not allocate any memory.
+    ///
+    #[must_use]
+    pub fn new() -> MiniSmallVec<T> {
+        if inline_capacity::<T>() == 0 {
+            MiniSmallVec::from_heap(MiniVec::new())
+        } else {
+            MiniSmallVec {
+                repr: inline_repr(0),
+            }
+        }
+    }
+
+    /// `pop` removes the last element from the vector, should it exist, and returns it.
+    ///
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+
+        unsafe {
+            self.set_len(len - 1);
+            Some(core::ptr::read(self.data().add(len - 1)))
+        }
+    }
+
+    /// `push` appends `value` to the end of the vector, spilling onto the heap once the inline
+    /// storage is full.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniSmallVec::<u32>::new();
+    ///
+    /// vec.push(1);
+    /// assert_eq!(vec.spilled(), minivec::MiniSmallVec::<u32>::inline_capacity() == 0);
+    ///
+    /// vec.push(2);
+    /// assert!(vec.spilled());
+    ///
+    /// assert_eq!(vec, [1, 2]);
+    /// ```
+    ///
+    pub fn push(&mut self, value: T) {
+        if let Err(err) = self.try_push(value) {
+            handle_reserve_error(err);
+        }
+    }
+
+    /// `realign` moves the elements into a heap allocation aligned to at least `new_alignment`, see
+    /// [`MiniVec::realign`](crate::MiniVec::realign).
+    ///
+    /// Inline elements are always moved onto the heap as the word itself is never over-aligned.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `LayoutErr` if `new_alignment` is rejected, in which case the vector is unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniSmallVec::from(&[1_u8, 2, 3][..]);
+    ///
+    /// vec.realign(32).unwrap();
+    ///
+    /// assert!(vec.spilled());
+    /// assert_eq!(vec.alignment(), 32);
+    /// assert_eq!(vec.as_ptr() as usize % 32, 0);
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    ///
+    pub fn realign(&mut self, new_alignment: usize) -> Result<(), LayoutErr> {
+        match self.try_realign(new_alignment) {
+            Ok(()) => Ok(()),
+            Err(TryReserveError::InvalidAlignment(err)) => Err(err),
+            Err(TryReserveError::CapacityOverflow) => Err(LayoutErr::SizeOverflow),
+            Err(err) => handle_reserve_error(err),
+        }
+    }
+
+    /// `remove` removes the element at `index`, shifting every element after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    ///
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "removal index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+
+        unsafe {
+            let p = self.data_mut().add(index);
+            let x = core::ptr::read(p);
+            core::ptr::copy(p.add(1), p, len - index - 1);
+            self.set_len(len - 1);
+            x
+        }
+    }
+
+    /// `reserve` ensures there is room for at least `additional` more elements.
+    ///
+    pub fn reserve(&mut self, additional: usize) {
+        if let Err(err) = self.try_reserve(additional) {
+            handle_reserve_error(err);
+        }
+    }
+
+    /// `reserve_exact` ensures there is room for exactly `additional` more elements.
+    ///
+    pub fn reserve_exact(&mut self, additional: usize) {
+        if let Err(err) = self.try_reserve_exact(additional) {
+            handle_reserve_error(err);
+        }
+    }
+
+    /// `resize` truncates or extends the vector to `new_len`, filling any new slots with clones
+    /// of `value`.
+    ///
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+            return;
+        }
+
+        self.reserve(new_len - len);
+        for _ in len..new_len {
+            self.push(value.clone());
+        }
+    }
+
+    /// `retain` removes every element for which `f` returns false, preserving the order of the
+    /// remaining ones.
+    ///
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        let data = self.data_mut();
+
+        // unlike `MiniVec::retain`, which swaps the kept elements forward and truncates once
+        // done, rejected elements are dropped as they're found. The length is zeroed up-front so
+        // a panicking predicate can only ever leak elements
+        //
+        unsafe { self.set_len(0) };
+
+        let mut kept = 0;
+        for idx in 0..len {
+            unsafe {
+                let p = data.add(idx);
+                if f(&*p) {
+                    core::ptr::copy(p, data.add(kept), 1);
+                    kept += 1;
+                } else {
+                    core::ptr::drop_in_place(p);
+                }
+            }
+        }
+
+        unsafe { self.set_len(kept) };
+    }
+
+    /// `set_len` reassigns the length of the vector without touching any of its elements.
+    ///
+    /// # Safety
+    ///
+    /// `len` must not exceed [`capacity()`](MiniSmallVec::capacity) and the first `len` elements
+    /// must be initialized.
+    ///
+    pub unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= self.capacity());
+
+        if self.is_inline() {
+            #[allow(clippy::cast_possible_truncation)]
+            let tag = (len << 1 | 1) as u8;
+
+            self.repr.inline[TAG] = MaybeUninit::new(tag);
+        } else {
+            self.heap_mut().set_len(len);
+        }
+    }
+
+    /// `shrink_to` reduces the capacity of the vector to at least `min_capacity`, moving the
+    /// elements back inline if both they and `min_capacity` fit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_capacity` is larger than the current capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniSmallVec::<u8>::with_capacity(64);
+    /// vec.push(1);
+    ///
+    /// vec.shrink_to(32);
+    /// assert_eq!(vec.capacity(), 32);
+    ///
+    /// vec.shrink_to(0);
+    /// assert!(!vec.spilled());
+    /// assert_eq!(vec, [1]);
+    /// ```
+    ///
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        if !self.is_inline() && core::cmp::max(self.len(), min_capacity) > inline_capacity::<T>() {
+            self.heap_mut().shrink_to(min_capacity);
+            return;
+        }
+
+        assert!(
+            self.capacity() >= min_capacity,
+            "Tried to shrink to a larger capacity"
+        );
+
+        self.shrink_to_fit();
+    }
+
+    /// `shrink_to_fit` reduces the capacity of the vector as much as possible, moving the elements
+    /// back inline if they fit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniSmallVec::<u8>::with_capacity(64);
+    /// vec.push(1);
+    /// assert!(vec.spilled());
+    ///
+    /// vec.shrink_to_fit();
+    /// assert!(!vec.spilled());
+    /// assert_eq!(vec, [1]);
+    /// ```
+    ///
+    pub fn shrink_to_fit(&mut self) {
+        if self.is_inline() {
+            return;
+        }
+
+        let len = self.len();
+        if len > inline_capacity::<T>() {
+            self.heap_mut().shrink_to_fit();
+            return;
+        }
+
+        let mut vec = unsafe { ManuallyDrop::take(&mut self.repr.heap) };
+        self.repr = inline_repr(len);
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(vec.data(), self.data_mut(), len);
+            vec.set_len(0);
+        }
+    }
+
+    /// `spilled` returns whether or not the elements are stored on the heap.
+    ///
+    #[must_use]
+    pub fn spilled(&self) -> bool {
+        !self.is_inline()
+    }
+
+    /// `splice` replaces the elements in `range` with `replace_with` and yields the removed ones,
+    /// see [`MiniVec::splice`](crate::MiniVec::splice).
+    ///
+    /// Note: inline elements are moved onto the heap first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the supplied `range` is outside of the vector's bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::MiniSmallVec::from(&[1_u8, 2, 3, 4][..]);
+    ///
+    /// let removed: minivec::MiniVec<_> = vec.splice(1..3, [7, 8, 9]).collect();
+    ///
+    /// assert_eq!(removed, [2, 3]);
+    /// assert_eq!(vec, [1, 7, 8, 9, 4]);
+    /// ```
+    ///
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter>
+    where
+        I: IntoIterator<Item = T>,
+        R: core::ops::RangeBounds<usize>,
+    {
+        self.spill().splice(range, replace_with)
+    }
+
+    /// `split_off` moves the elements starting at index `at` into a new `MiniSmallVec`, leaving
+    /// the first `at` elements in `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len()`.
+    ///
+    #[must_use = "use `.truncate()` if you don't need the other half"]
+    pub fn split_off(&mut self, at: usize) -> MiniSmallVec<T> {
+        let len = self.len();
+        assert!(
+            at <= len,
+            "`at` split index (is {}) should be <= len (is {})",
+            at,
+            len
+        );
+
+        let count = len - at;
+        let mut other = MiniSmallVec::with_capacity(count);
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(self.data().add(at), other.data_mut(), count);
+            self.set_len(at);
+            other.set_len(count);
+        }
+
+        other
+    }
+
+    /// `swap_remove` removes the element at `index` and replaces it with the last element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    ///
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "swap_remove index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+
+        self.swap(index, len - 1);
+        self.pop().unwrap()
+    }
+
+    /// `truncate` drops every element past `len`. Has no effect if `len` is greater than the
+    /// vector's current length.
+    ///
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+
+        unsafe {
+            self.set_len(len);
+
+            let tail = core::ptr::slice_from_raw_parts_mut(self.data_mut().add(len), old_len - len);
+            core::ptr::drop_in_place(tail);
+        }
+    }
+
+    /// `try_push` is the fallible counterpart to [`push`](MiniSmallVec::push).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](crate::TryReserveError) if spilling or growing the heap
+    /// allocation fails. The vector is left unchanged.
+    ///
+    pub fn try_push(&mut self, value: T) -> Result<(), TryReserveError> {
+        self.try_reserve(1)?;
+
+        let len = self.len();
+        unsafe {
+            core::ptr::write(self.data_mut().add(len), value);
+            self.set_len(len + 1);
+        }
+
+        Ok(())
+    }
+
+    /// `try_realign` is the fallible counterpart to [`realign`](MiniSmallVec::realign).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError::InvalidAlignment`](crate::TryReserveError::InvalidAlignment) if
+    /// the supplied alignment is rejected and any other [`TryReserveError`](crate::TryReserveError)
+    /// if the new allocation cannot be made. The vector is unchanged in either case.
+    ///
+    pub fn try_realign(&mut self, new_alignment: usize) -> Result<(), TryReserveError> {
+        if !self.is_inline() {
+            return self.heap_mut().try_realign(new_alignment);
+        }
+
+        validate_alignment::<T, ()>(new_alignment).map_err(TryReserveError::InvalidAlignment)?;
+
+        let vec = MiniVec::try_with_alignment(self.len(), new_alignment)?;
+        self.spill_into(vec);
+
+        Ok(())
+    }
+
+    /// `try_reserve` is the fallible counterpart to [`reserve`](MiniSmallVec::reserve).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](crate::TryReserveError) if the new capacity overflows or the
+    /// allocation fails. The vector is left unchanged.
+    ///
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.try_reserve_inner(additional, false)
+    }
+
+    /// `try_reserve_exact` is the fallible counterpart to
+    /// [`reserve_exact`](MiniSmallVec::reserve_exact).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](crate::TryReserveError) if the new capacity overflows or the
+    /// allocation fails. The vector is left unchanged.
+    ///
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.try_reserve_inner(additional, true)
+    }
+
+    /// `try_with_alignment` is the fallible counterpart to
+    /// [`with_alignment`](MiniSmallVec::with_alignment).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError::InvalidAlignment`](crate::TryReserveError::InvalidAlignment) if
+    /// the supplied alignment is rejected and any other [`TryReserveError`](crate::TryReserveError)
+    /// if the allocation itself cannot be made.
+    ///
+    pub fn try_with_alignment(
+        capacity: usize,
+        alignment: usize,
+    ) -> Result<MiniSmallVec<T>, TryReserveError> {
+        MiniVec::try_with_alignment(capacity, alignment).map(MiniSmallVec::from_heap)
+    }
+
+    /// `try_with_capacity` is the fallible counterpart to
+    /// [`with_capacity`](MiniSmallVec::with_capacity).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](crate::TryReserveError) if the elements don't fit inline and
+    /// the allocation for `capacity` of them cannot be made.
+    ///
+    pub fn try_with_capacity(capacity: usize) -> Result<MiniSmallVec<T>, TryReserveError> {
+        if capacity <= inline_capacity::<T>() {
+            Ok(MiniSmallVec::new())
+        } else {
+            MiniVec::try_with_capacity(capacity).map(MiniSmallVec::from_heap)
+        }
+    }
+
+    /// `with_alignment` constructs an empty, spilled `MiniSmallVec` whose allocation is aligned to
+    /// `alignment`, see [`MiniVec::with_alignment`](crate::MiniVec::with_alignment).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `LayoutErr` if `alignment` is rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniSmallVec::<u8>::with_alignment(4, 64).unwrap();
+    ///
+    /// assert!(vec.spilled());
+    /// assert_eq!(vec.as_ptr() as usize % 64, 0);
+    /// ```
+    ///
+    pub fn with_alignment(capacity: usize, alignment: usize) -> Result<MiniSmallVec<T>, LayoutErr> {
+        MiniVec::with_alignment(capacity, alignment).map(MiniSmallVec::from_heap)
+    }
+
+    /// `with_capacity` constructs an empty `MiniSmallVec` with room for at least `capacity`
+    /// elements, only allocating if they don't fit inline.
+    ///
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> MiniSmallVec<T> {
+        if capacity <= inline_capacity::<T>() {
+            MiniSmallVec::new()
+        } else {
+            MiniSmallVec::from_heap(MiniVec::with_capacity(capacity))
+        }
+    }
+}
+
+impl<T> Drop for MiniSmallVec<T> {
+    fn drop(&mut self) {
+        if self.is_inline() {
+            unsafe { core::ptr::drop_in_place(self.as_mut_slice()) };
+        } else {
+            unsafe { ManuallyDrop::drop(&mut self.repr.heap) };
+        }
+    }
+}
+
+impl<T> core::ops::Deref for MiniSmallVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.data(), self.len()) }
+    }
+}
+
+impl<T> core::ops::DerefMut for MiniSmallVec<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        let len = self.len();
+        unsafe { core::slice::from_raw_parts_mut(self.data_mut(), len) }
+    }
+}
+
+impl<T> AsRef<[T]> for MiniSmallVec<T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> AsMut<[T]> for MiniSmallVec<T> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T> core::borrow::Borrow<[T]> for MiniSmallVec<T> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> core::borrow::BorrowMut<[T]> for MiniSmallVec<T> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: Clone> Clone for MiniSmallVec<T> {
+    fn clone(&self) -> Self {
+        let mut copy = MiniSmallVec::with_capacity(self.len());
+        copy.extend_from_slice(self);
+        copy
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for MiniSmallVec<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let this: &[T] = self;
+
+        this.fmt(f)
+    }
+}
+
+impl<T> Default for MiniSmallVec<T> {
+    fn default() -> Self {
+        MiniSmallVec::new()
+    }
+}
+
+impl<T: core::hash::Hash> core::hash::Hash for MiniSmallVec<T> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: core::hash::Hasher,
+    {
+        let this: &[T] = self;
+        core::hash::Hash::hash(this, state);
+    }
+}
+
+impl<T: Eq> Eq for MiniSmallVec<T> {}
+
+impl<T: Ord> Ord for MiniSmallVec<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let x: &[T] = self;
+        let y: &[T] = other;
+
+        x.cmp(y)
+    }
+}
+
+impl<T, V> PartialEq<V> for MiniSmallVec<T>
+where
+    V: AsRef<[T]>,
+    T: PartialEq,
+{
+    fn eq(&self, other: &V) -> bool {
+        let lhs: &[T] = self;
+        let rhs: &[T] = other.as_ref();
+
+        lhs == rhs
+    }
+}
+
+impl<T, V> PartialOrd<V> for MiniSmallVec<T>
+where
+    V: AsRef<[T]>,
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &V) -> Option<core::cmp::Ordering> {
+        let x: &[T] = self;
+        let y: &[T] = other.as_ref();
+
+        x.partial_cmp(y)
+    }
+}
+
+impl<T> Extend<T> for MiniSmallVec<T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy> Extend<&'a T> for MiniSmallVec<T> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> core::iter::FromIterator<T> for MiniSmallVec<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut v = MiniSmallVec::new();
+        v.extend(iter);
+        v
+    }
+}
+
+impl<T: Clone> From<&[T]> for MiniSmallVec<T> {
+    fn from(elems: &[T]) -> Self {
+        let mut v = MiniSmallVec::with_capacity(elems.len());
+        v.extend_from_slice(elems);
+        v
+    }
+}
+
+impl<T> From<MiniVec<T>> for MiniSmallVec<T> {
+    /// Adopts the vector's allocation as-is, use
+    /// [`shrink_to_fit`](MiniSmallVec::shrink_to_fit) to move small vectors inline.
+    ///
+    fn from(vec: MiniVec<T>) -> Self {
+        MiniSmallVec::from_heap(vec)
+    }
+}
+
+impl<T> From<MiniSmallVec<T>> for MiniVec<T> {
+    fn from(v: MiniSmallVec<T>) -> Self {
+        let mut v = ManuallyDrop::new(v);
+
+        if !v.is_inline() {
+            return unsafe { ManuallyDrop::take(&mut v.repr.heap) };
+        }
+
+        let len = v.len();
+        let mut vec = MiniVec::with_capacity(len);
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(v.data(), vec.data(), len);
+            vec.set_len(len);
+        }
+
+        vec
+    }
+}
+
+impl<T> IntoIterator for MiniSmallVec<T> {
+    type Item = T;
+    type IntoIter = crate::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MiniVec::from(self).into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a MiniSmallVec<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut MiniSmallVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+unsafe impl<T: Send> Send for MiniSmallVec<T> {}
+unsafe impl<T: Sync> Sync for MiniSmallVec<T> {}
@@ -0,0 +1,1214 @@
+use crate::{Allocator, Global, MiniBox, MiniVec, TryReserveError};
+
+use core::fmt;
+use core::ops::{Bound, RangeBounds};
+
+/// `MiniString` is a growable UTF-8 string that, like [`MiniVec`](crate::MiniVec), is only the
+/// size of a single pointer.
+///
+/// `MiniString` is a thin wrapper over a `MiniVec<u8>` that upholds the invariant that its
+/// contents are always valid UTF-8. It dereferences to `str` so all of the usual string slice
+/// methods are available.
+///
+/// # Example
+///
+/// ```
+/// use minivec::MiniString;
+///
+/// let mut s = MiniString::from("hello");
+/// s.push(',');
+/// s.push_str(" world");
+///
+/// assert_eq!(s, "hello, world");
+/// assert_eq!(s.to_uppercase(), "HELLO, WORLD");
+/// assert_eq!(core::mem::size_of::<MiniString>(), core::mem::size_of::<usize>());
+/// ```
+///
+pub struct MiniString<A: Allocator = Global> {
+    vec: MiniVec<u8, A>,
+}
+
+/// `FromUtf8Error` is the error type returned by [`MiniString::from_utf8`] when the supplied
+/// bytes are not valid UTF-8.
+///
+/// The original bytes can be recovered via [`into_bytes`](FromUtf8Error::into_bytes).
+///
+pub struct FromUtf8Error<A: Allocator = Global> {
+    bytes: MiniVec<u8, A>,
+    error: core::str::Utf8Error,
+}
+
+/// `StringDrain` is the iterator returned by [`MiniString::drain`]. It yields the removed `char`s
+/// and removes the drained range from the string when dropped.
+///
+pub struct StringDrain<'a, A: Allocator = Global> {
+    string: *mut MiniString<A>,
+    start: usize,
+    end: usize,
+    iter: core::str::Chars<'a>,
+}
+
+impl MiniString {
+    /// `from_utf8_lossy` converts a slice of bytes into a `MiniString`, replacing any invalid
+    /// UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = minivec::MiniString::from_utf8_lossy(b"Hello \xF0\x90\x80World");
+    ///
+    /// assert_eq!(s, "Hello \u{FFFD}World");
+    /// ```
+    ///
+    #[must_use]
+    pub fn from_utf8_lossy(v: &[u8]) -> MiniString {
+        let mut s = MiniString::with_capacity(v.len());
+
+        for chunk in v.utf8_chunks() {
+            s.push_str(chunk.valid());
+
+            if !chunk.invalid().is_empty() {
+                s.push(char::REPLACEMENT_CHARACTER);
+            }
+        }
+
+        s
+    }
+
+    /// `new` constructs an empty `MiniString`.
+    ///
+    /// Note: does not allocate any memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = minivec::MiniString::new();
+    ///
+    /// assert!(s.is_empty());
+    /// assert_eq!(s.capacity(), 0);
+    /// ```
+    ///
+    #[must_use]
+    pub fn new() -> MiniString {
+        MiniString {
+            vec: MiniVec::new(),
+        }
+    }
+
+    /// `with_capacity` constructs an empty `MiniString` with enough room for at least `capacity`
+    /// bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = minivec::MiniString::with_capacity(64);
+    ///
+    /// assert!(s.is_empty());
+    /// assert!(s.capacity() >= 64);
+    /// ```
+    ///
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> MiniString {
+        MiniString {
+            vec: MiniVec::with_capacity(capacity),
+        }
+    }
+}
+
+impl<A: Allocator> MiniString<A> {
+    /// `allocator` returns a reference to the allocator backing the string.
+    ///
+    #[must_use]
+    pub fn allocator(&self) -> &A {
+        self.vec.allocator()
+    }
+
+    /// `as_bytes` returns the contents of the string as a byte slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = minivec::MiniString::from("abc");
+    ///
+    /// assert_eq!(s.as_bytes(), b"abc");
+    /// ```
+    ///
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.vec
+    }
+
+    /// `as_mut_str` returns a mutable string slice over the entire string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("abc");
+    /// s.as_mut_str().make_ascii_uppercase();
+    ///
+    /// assert_eq!(s, "ABC");
+    /// ```
+    ///
+    pub fn as_mut_str(&mut self) -> &mut str {
+        unsafe { core::str::from_utf8_unchecked_mut(&mut self.vec) }
+    }
+
+    /// `as_mut_vec` returns a mutable reference to the underlying `MiniVec<u8>`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the contents are valid UTF-8 once the borrow ends.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("hello");
+    ///
+    /// unsafe { s.as_mut_vec().reverse() };
+    ///
+    /// assert_eq!(s, "olleh");
+    /// ```
+    ///
+    pub unsafe fn as_mut_vec(&mut self) -> &mut MiniVec<u8, A> {
+        &mut self.vec
+    }
+
+    /// `as_str` returns a string slice over the entire string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = minivec::MiniString::from("abc");
+    ///
+    /// assert_eq!(s.as_str(), "abc");
+    /// ```
+    ///
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(&self.vec) }
+    }
+
+    /// `capacity` returns the number of bytes the string can hold without reallocating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = minivec::MiniString::with_capacity(10);
+    ///
+    /// assert!(s.capacity() >= 10);
+    /// ```
+    ///
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// `clear` removes every character from the string, retaining its capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("abc");
+    /// s.clear();
+    ///
+    /// assert!(s.is_empty());
+    /// assert!(s.capacity() >= 3);
+    /// ```
+    ///
+    pub fn clear(&mut self) {
+        self.vec.clear();
+    }
+
+    /// `drain` removes the byte range `range` from the string and returns an iterator over the
+    /// removed `char`s.
+    ///
+    /// Note: the range is removed even if the iterator isn't fully consumed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or end of the range doesn't lie on a `char` boundary or if the range
+    /// is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("α is alpha, β is beta");
+    /// let beta_offset = s.find('β').unwrap();
+    ///
+    /// let t: minivec::MiniString = s.drain(..beta_offset).collect();
+    ///
+    /// assert_eq!(t, "α is alpha, ");
+    /// assert_eq!(s, "β is beta");
+    ///
+    /// s.drain(..);
+    /// assert_eq!(s, "");
+    /// ```
+    ///
+    pub fn drain<R>(&mut self, range: R) -> StringDrain<'_, A>
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.len();
+
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+
+        assert!(
+            start <= end,
+            "start drain index (is {}) should be <= end drain index (is {})",
+            start,
+            end
+        );
+        assert!(
+            end <= len,
+            "end drain index (is {}) should be <= len (is {})",
+            end,
+            len
+        );
+        assert!(self.is_char_boundary(start), "start is not a char boundary");
+        assert!(self.is_char_boundary(end), "end is not a char boundary");
+
+        let string: *mut MiniString<A> = self;
+        let iter = unsafe { self.get_unchecked(start..end) }.chars();
+
+        StringDrain {
+            string,
+            start,
+            end,
+            iter,
+        }
+    }
+
+    /// `from_utf8` converts a `MiniVec<u8>` into a `MiniString`, reusing its allocation.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FromUtf8Error`](crate::FromUtf8Error) holding the original bytes if `vec` is
+    /// not valid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = minivec::MiniString::from_utf8(minivec::mini_vec![240, 159, 146, 150]).unwrap();
+    /// assert_eq!(s, "💖");
+    ///
+    /// let err = minivec::MiniString::from_utf8(minivec::mini_vec![0, 159]).unwrap_err();
+    /// assert_eq!(err.utf8_error().valid_up_to(), 1);
+    /// assert_eq!(err.into_bytes(), [0, 159]);
+    /// ```
+    ///
+    pub fn from_utf8(vec: MiniVec<u8, A>) -> Result<MiniString<A>, FromUtf8Error<A>> {
+        match core::str::from_utf8(&vec) {
+            Ok(_) => Ok(unsafe { MiniString::from_utf8_unchecked(vec) }),
+            Err(error) => Err(FromUtf8Error { bytes: vec, error }),
+        }
+    }
+
+    /// `from_utf8_unchecked` converts a `MiniVec<u8>` into a `MiniString` without checking that
+    /// it contains valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// `vec` must contain valid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = unsafe { minivec::MiniString::from_utf8_unchecked(minivec::mini_vec![b'h', b'i']) };
+    ///
+    /// assert_eq!(s, "hi");
+    /// ```
+    ///
+    pub unsafe fn from_utf8_unchecked(vec: MiniVec<u8, A>) -> MiniString<A> {
+        let mut vec = vec;
+        vec.unshare();
+
+        MiniString { vec }
+    }
+
+    /// `insert` inserts the character `ch` at the byte offset `idx`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the string's length or doesn't lie on a `char` boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("ac");
+    /// s.insert(1, 'b');
+    ///
+    /// assert_eq!(s, "abc");
+    /// ```
+    ///
+    pub fn insert(&mut self, idx: usize, ch: char) {
+        let mut buf = [0_u8; 4];
+        self.insert_str(idx, ch.encode_utf8(&mut buf));
+    }
+
+    /// `insert_str` inserts the string slice `string` at the byte offset `idx`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the string's length or doesn't lie on a `char` boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("world");
+    /// s.insert_str(0, "hello ");
+    ///
+    /// assert_eq!(s, "hello world");
+    /// ```
+    ///
+    pub fn insert_str(&mut self, idx: usize, string: &str) {
+        assert!(self.is_char_boundary(idx), "index is not a char boundary");
+
+        let len = self.len();
+        let amt = string.len();
+        if amt == 0 {
+            return;
+        }
+
+        self.vec.reserve(amt);
+
+        unsafe {
+            let data = self.vec.data();
+
+            core::ptr::copy(data.add(idx), data.add(idx + amt), len - idx);
+            core::ptr::copy_nonoverlapping(string.as_ptr(), data.add(idx), amt);
+            self.vec.set_len(len + amt);
+        }
+    }
+
+    /// `into_bytes` converts the string into its underlying `MiniVec<u8>` without copying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = minivec::MiniString::from("hi");
+    ///
+    /// assert_eq!(s.into_bytes(), b"hi");
+    /// ```
+    ///
+    #[must_use]
+    pub fn into_bytes(self) -> MiniVec<u8, A> {
+        self.vec
+    }
+
+    /// `into_frozen` converts the string into a [`MiniBox<str>`](crate::MiniBox), a
+    /// single-pointer boxed string slice that only stores its length in front of the data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let frozen = minivec::MiniString::from("hello").into_frozen();
+    ///
+    /// assert_eq!(frozen, "hello");
+    /// assert_eq!(frozen.to_uppercase(), "HELLO");
+    /// ```
+    ///
+    #[must_use]
+    pub fn into_frozen(self) -> MiniBox<str, A> {
+        MiniBox::from(self)
+    }
+
+    /// `is_empty` returns whether or not the string has a length of zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::new();
+    /// assert!(s.is_empty());
+    ///
+    /// s.push('x');
+    /// assert!(!s.is_empty());
+    /// ```
+    ///
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// `len` returns the length of the string in bytes, not `char`s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = minivec::MiniString::from("ƒoo");
+    ///
+    /// assert_eq!(s.len(), 4);
+    /// assert_eq!(s.chars().count(), 3);
+    /// ```
+    ///
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// `new_in` constructs an empty `MiniString` which will use `allocator` for all of its memory.
+    ///
+    /// Note: does not allocate any memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::new_in(minivec::Global);
+    /// s.push_str("abc");
+    ///
+    /// assert_eq!(s, "abc");
+    /// ```
+    ///
+    pub fn new_in(allocator: A) -> MiniString<A> {
+        MiniString {
+            vec: MiniVec::new_in(allocator),
+        }
+    }
+
+    /// `pop` removes the last `char` from the string and returns it, should it exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("aé");
+    ///
+    /// assert_eq!(s.pop(), Some('é'));
+    /// assert_eq!(s.pop(), Some('a'));
+    /// assert_eq!(s.pop(), None);
+    /// ```
+    ///
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.chars().next_back()?;
+        let len = self.len() - ch.len_utf8();
+
+        unsafe { self.vec.set_len(len) };
+        Some(ch)
+    }
+
+    /// `push` appends the character `ch` to the end of the string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::new();
+    /// s.push('a');
+    /// s.push('ß');
+    ///
+    /// assert_eq!(s, "aß");
+    /// ```
+    ///
+    pub fn push(&mut self, ch: char) {
+        match ch.len_utf8() {
+            1 => self.vec.push(ch as u8),
+            _ => self.push_str(ch.encode_utf8(&mut [0; 4])),
+        }
+    }
+
+    /// `push_str` appends the string slice `string` to the end of the string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("foo");
+    /// s.push_str("bar");
+    ///
+    /// assert_eq!(s, "foobar");
+    /// ```
+    ///
+    pub fn push_str(&mut self, string: &str) {
+        self.vec.extend_from_slice(string.as_bytes());
+    }
+
+    /// `remove` removes the `char` at the byte offset `idx` and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is greater than or equal to the string's length or doesn't lie on a `char`
+    /// boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("abç");
+    ///
+    /// assert_eq!(s.remove(2), 'ç');
+    /// assert_eq!(s.remove(0), 'a');
+    /// assert_eq!(s, "b");
+    /// ```
+    ///
+    pub fn remove(&mut self, idx: usize) -> char {
+        let Some(ch) = self[idx..].chars().next() else {
+            panic!("cannot remove a char from the end of a string");
+        };
+
+        self.vec.drain(idx..idx + ch.len_utf8());
+        ch
+    }
+
+    /// `reserve` ensures the string has room for at least `additional` more bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::new();
+    /// s.reserve(10);
+    ///
+    /// assert!(s.capacity() >= 10);
+    /// ```
+    ///
+    pub fn reserve(&mut self, additional: usize) {
+        self.vec.reserve(additional);
+    }
+
+    /// `reserve_exact` ensures the string has room for exactly `additional` more bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::new();
+    /// s.reserve_exact(10);
+    ///
+    /// assert_eq!(s.capacity(), 10);
+    /// ```
+    ///
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.vec.reserve_exact(additional);
+    }
+
+    /// `retain` removes every `char` for which the predicate `f` returns `false`, visiting each
+    /// `char` exactly once and in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("f_o_ob_ar");
+    /// s.retain(|c| c != '_');
+    ///
+    /// assert_eq!(s, "foobar");
+    /// ```
+    ///
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(char) -> bool,
+    {
+        // should `f` panic, the guard truncates the string to the prefix we've already compacted
+        // so that it remains valid UTF-8
+        //
+        struct Guard<'a, A: Allocator> {
+            s: &'a mut MiniString<A>,
+            idx: usize,
+            deleted: usize,
+        }
+
+        impl<A: Allocator> Drop for Guard<'_, A> {
+            fn drop(&mut self) {
+                unsafe { self.s.vec.set_len(self.idx - self.deleted) };
+            }
+        }
+
+        let len = self.len();
+        let mut guard = Guard {
+            s: self,
+            idx: 0,
+            deleted: 0,
+        };
+
+        while guard.idx < len {
+            let Some(ch) = unsafe { guard.s.get_unchecked(guard.idx..len) }
+                .chars()
+                .next()
+            else {
+                break;
+            };
+
+            let ch_len = ch.len_utf8();
+
+            if !f(ch) {
+                guard.deleted += ch_len;
+            } else if guard.deleted > 0 {
+                unsafe {
+                    let data = guard.s.vec.data();
+                    core::ptr::copy(
+                        data.add(guard.idx),
+                        data.add(guard.idx - guard.deleted),
+                        ch_len,
+                    );
+                }
+            }
+
+            guard.idx += ch_len;
+        }
+    }
+
+    /// `shrink_to_fit` reduces the string's capacity to its length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::with_capacity(100);
+    /// s.push_str("abc");
+    /// s.shrink_to_fit();
+    ///
+    /// assert_eq!(s.capacity(), 3);
+    /// ```
+    ///
+    pub fn shrink_to_fit(&mut self) {
+        self.vec.shrink_to_fit();
+    }
+
+    /// `split_off` splits the string into two at the byte offset `at`. `self` retains `[0, at)`
+    /// and the returned string contains `[at, len)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is larger than the string's length or doesn't lie on a `char` boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut hello = minivec::MiniString::from("Hello, World!");
+    /// let world = hello.split_off(7);
+    ///
+    /// assert_eq!(hello, "Hello, ");
+    /// assert_eq!(world, "World!");
+    /// ```
+    ///
+    #[must_use = "use `.truncate()` if you don't need the other half"]
+    pub fn split_off(&mut self, at: usize) -> MiniString<A>
+    where
+        A: Clone,
+    {
+        assert!(self.is_char_boundary(at), "index is not a char boundary");
+
+        MiniString {
+            vec: self.vec.split_off(at),
+        }
+    }
+
+    /// `truncate` shortens the string to `new_len` bytes. Has no effect if `new_len` is greater
+    /// than the string's current length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` doesn't lie on a `char` boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("hello");
+    /// s.truncate(2);
+    ///
+    /// assert_eq!(s, "he");
+    /// ```
+    ///
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len() {
+            assert!(
+                self.is_char_boundary(new_len),
+                "new_len is not a char boundary"
+            );
+            self.vec.truncate(new_len);
+        }
+    }
+
+    /// `try_push_str` is the fallible version of [`push_str`](MiniString::push_str).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](crate::TryReserveError) if the string could not be grown, in
+    /// which case the string is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::new();
+    /// s.try_push_str("abc").unwrap();
+    ///
+    /// assert_eq!(s, "abc");
+    /// ```
+    ///
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), TryReserveError> {
+        self.vec.try_extend_from_slice(string.as_bytes())
+    }
+
+    /// `try_reserve` is the fallible version of [`reserve`](MiniString::reserve).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](crate::TryReserveError) if the capacity overflows or the
+    /// allocator fails, in which case the string is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::new();
+    ///
+    /// assert!(s.try_reserve(16).is_ok());
+    /// assert!(s.try_reserve(usize::MAX).is_err());
+    /// ```
+    ///
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.vec.try_reserve(additional)
+    }
+
+    /// `with_capacity_in` constructs an empty `MiniString` with enough room for at least
+    /// `capacity` bytes, using `allocator` for all of its memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = minivec::MiniString::with_capacity_in(8, minivec::Global);
+    ///
+    /// assert!(s.capacity() >= 8);
+    /// ```
+    ///
+    pub fn with_capacity_in(capacity: usize, allocator: A) -> MiniString<A> {
+        MiniString {
+            vec: MiniVec::with_capacity_in(capacity, allocator),
+        }
+    }
+}
+
+impl<A: Allocator> FromUtf8Error<A> {
+    /// `as_bytes` returns the bytes that failed to convert.
+    ///
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// `into_bytes` returns the bytes that failed to convert, giving back the original
+    /// allocation.
+    ///
+    #[must_use]
+    pub fn into_bytes(self) -> MiniVec<u8, A> {
+        self.bytes
+    }
+
+    /// `utf8_error` returns the underlying `Utf8Error` describing where the conversion failed.
+    ///
+    #[must_use]
+    pub fn utf8_error(&self) -> core::str::Utf8Error {
+        self.error
+    }
+}
+
+impl<A: Allocator + Clone> Clone for FromUtf8Error<A> {
+    fn clone(&self) -> Self {
+        FromUtf8Error {
+            bytes: self.bytes.clone(),
+            error: self.error,
+        }
+    }
+}
+
+impl<A: Allocator> fmt::Debug for FromUtf8Error<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FromUtf8Error")
+            .field("bytes", &self.bytes)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<A: Allocator> fmt::Display for FromUtf8Error<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<A: Allocator> PartialEq for FromUtf8Error<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes && self.error == other.error
+    }
+}
+
+impl<A: Allocator> Eq for FromUtf8Error<A> {}
+
+#[cfg(feature = "std")]
+impl<A: Allocator> std::error::Error for FromUtf8Error<A> {}
+
+impl<A: Allocator> StringDrain<'_, A> {
+    /// `as_str` returns the part of the drained range that has not yet been yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut s = minivec::MiniString::from("abc");
+    ///
+    /// let mut drain = s.drain(..);
+    /// drain.next();
+    ///
+    /// assert_eq!(drain.as_str(), "bc");
+    /// ```
+    ///
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.iter.as_str()
+    }
+}
+
+impl<A: Allocator> Iterator for StringDrain<'_, A> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn last(mut self) -> Option<char> {
+        self.next_back()
+    }
+}
+
+impl<A: Allocator> DoubleEndedIterator for StringDrain<'_, A> {
+    fn next_back(&mut self) -> Option<char> {
+        self.iter.next_back()
+    }
+}
+
+impl<A: Allocator> core::iter::FusedIterator for StringDrain<'_, A> {}
+
+impl<A: Allocator> Drop for StringDrain<'_, A> {
+    fn drop(&mut self) {
+        let string = unsafe { &mut *self.string };
+        string.vec.drain(self.start..self.end);
+    }
+}
+
+impl<A: Allocator> fmt::Debug for StringDrain<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StringDrain").field(&self.as_str()).finish()
+    }
+}
+
+impl<A: Allocator> core::ops::Add<&str> for MiniString<A> {
+    type Output = MiniString<A>;
+
+    fn add(mut self, other: &str) -> MiniString<A> {
+        self.push_str(other);
+        self
+    }
+}
+
+impl<A: Allocator> core::ops::AddAssign<&str> for MiniString<A> {
+    fn add_assign(&mut self, other: &str) {
+        self.push_str(other);
+    }
+}
+
+impl<A: Allocator> AsMut<str> for MiniString<A> {
+    fn as_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<A: Allocator> AsRef<str> for MiniString<A> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A: Allocator> AsRef<[u8]> for MiniString<A> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<A: Allocator> core::borrow::Borrow<str> for MiniString<A> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A: Allocator> core::borrow::BorrowMut<str> for MiniString<A> {
+    fn borrow_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<A: Allocator + Clone> Clone for MiniString<A> {
+    fn clone(&self) -> Self {
+        MiniString {
+            vec: self.vec.clone(),
+        }
+    }
+}
+
+impl<A: Allocator> fmt::Debug for MiniString<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<A: Allocator + Default> Default for MiniString<A> {
+    fn default() -> Self {
+        MiniString::new_in(A::default())
+    }
+}
+
+impl<A: Allocator> core::ops::Deref for MiniString<A> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A: Allocator> core::ops::DerefMut for MiniString<A> {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<A: Allocator> fmt::Display for MiniString<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<A: Allocator> Eq for MiniString<A> {}
+
+impl<A: Allocator> Extend<char> for MiniString<A> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for ch in iter {
+            self.push(ch);
+        }
+    }
+}
+
+impl<'a, A: Allocator> Extend<&'a char> for MiniString<A> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a char>,
+    {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<'a, A: Allocator> Extend<&'a str> for MiniString<A> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        for s in iter {
+            self.push_str(s);
+        }
+    }
+}
+
+impl<A: Allocator> Extend<alloc::string::String> for MiniString<A> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = alloc::string::String>,
+    {
+        for s in iter {
+            self.push_str(&s);
+        }
+    }
+}
+
+impl<A: Allocator, B: Allocator> Extend<MiniString<B>> for MiniString<A> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = MiniString<B>>,
+    {
+        for s in iter {
+            self.push_str(&s);
+        }
+    }
+}
+
+impl From<&str> for MiniString {
+    fn from(s: &str) -> Self {
+        let mut string = MiniString::with_capacity(s.len());
+        string.push_str(s);
+        string
+    }
+}
+
+impl From<&mut str> for MiniString {
+    fn from(s: &mut str) -> Self {
+        MiniString::from(&*s)
+    }
+}
+
+impl From<char> for MiniString {
+    fn from(ch: char) -> Self {
+        let mut string = MiniString::new();
+        string.push(ch);
+        string
+    }
+}
+
+impl From<alloc::string::String> for MiniString {
+    fn from(s: alloc::string::String) -> Self {
+        MiniString {
+            vec: MiniVec::from(s),
+        }
+    }
+}
+
+impl<A: Allocator> From<MiniString<A>> for alloc::string::String {
+    fn from(s: MiniString<A>) -> Self {
+        unsafe { alloc::string::String::from_utf8_unchecked(s.vec.into()) }
+    }
+}
+
+impl<A: Allocator> From<MiniString<A>> for MiniVec<u8, A> {
+    fn from(s: MiniString<A>) -> Self {
+        s.vec
+    }
+}
+
+impl core::iter::FromIterator<char> for MiniString {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut s = MiniString::new();
+        s.extend(iter);
+        s
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a char> for MiniString {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a char>,
+    {
+        let mut s = MiniString::new();
+        s.extend(iter);
+        s
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a str> for MiniString {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut s = MiniString::new();
+        s.extend(iter);
+        s
+    }
+}
+
+impl core::iter::FromIterator<alloc::string::String> for MiniString {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = alloc::string::String>,
+    {
+        let mut s = MiniString::new();
+        s.extend(iter);
+        s
+    }
+}
+
+impl<A: Allocator> core::iter::FromIterator<MiniString<A>> for MiniString {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = MiniString<A>>,
+    {
+        let mut s = MiniString::new();
+        s.extend(iter);
+        s
+    }
+}
+
+impl core::str::FromStr for MiniString {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(MiniString::from(s))
+    }
+}
+
+impl<A: Allocator> core::hash::Hash for MiniString<A> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: core::hash::Hasher,
+    {
+        // must hash identically to `str` to uphold the contract of `Borrow<str>`
+        //
+        core::hash::Hash::hash(self.as_str(), state);
+    }
+}
+
+impl<A: Allocator> Ord for MiniString<A> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<A: Allocator, V> PartialEq<V> for MiniString<A>
+where
+    V: AsRef<str>,
+{
+    fn eq(&self, other: &V) -> bool {
+        self.as_str() == other.as_ref()
+    }
+}
+
+impl<A: Allocator, V> PartialOrd<V> for MiniString<A>
+where
+    V: AsRef<str>,
+{
+    fn partial_cmp(&self, other: &V) -> Option<core::cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_ref())
+    }
+}
+
+// the impls above only cover `MiniString` on the left-hand side so we provide the mirrored
+// comparisons for the common string types
+//
+impl<A: Allocator> PartialEq<MiniString<A>> for str {
+    fn eq(&self, other: &MiniString<A>) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl<A: Allocator> PartialEq<MiniString<A>> for &str {
+    fn eq(&self, other: &MiniString<A>) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl<A: Allocator> PartialEq<MiniString<A>> for alloc::string::String {
+    fn eq(&self, other: &MiniString<A>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<A: Allocator> fmt::Write for MiniString<A> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
+
+unsafe impl<A: Allocator + Send> Send for StringDrain<'_, A> {}
+unsafe impl<A: Allocator + Sync> Sync for StringDrain<'_, A> {}
@@ -0,0 +1,53 @@
+use crate::shared::{shared_slice, RawShared};
+use crate::{Allocator, FrozenSlice, Global, MiniVec};
+
+use core::sync::atomic::AtomicUsize;
+
+shared_slice! {
+    /// `MiniArc` is a thread-safe, reference-counted, single-pointer shared slice.
+    ///
+    /// Where an `Arc<[T]>` is two words wide and an `Arc<MiniVec<T>>` requires two allocations and
+    /// a double indirection, a `MiniArc<[T]>` stores an atomic reference count right next to the
+    /// length and capacity in the header of a single allocation.
+    ///
+    /// A `MiniArc` is created from a [`MiniVec`](crate::MiniVec), adopting its allocation and
+    /// growing it in place if the reference count doesn't fit in front of the elements yet. A
+    /// unique `MiniArc` can be turned back into a `MiniVec` with its original alignment, again
+    /// without copying, via [`try_unwrap`](MiniArc::try_unwrap).
+    ///
+    /// Like the stdlib's `Arc`, all of the functionality specific to `MiniArc` is exposed through
+    /// associated functions so as not to clash with the methods of the slice it dereferences to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use minivec::{mini_vec, MiniArc};
+    ///
+    /// let shared = MiniArc::from(mini_vec![1, 2, 3]);
+    /// assert_eq!(core::mem::size_of_val(&shared), core::mem::size_of::<usize>());
+    ///
+    /// let handle = {
+    ///     let shared = shared.clone();
+    ///     std::thread::spawn(move || shared.iter().sum::<i32>())
+    /// };
+    ///
+    /// assert_eq!(handle.join().unwrap(), 6);
+    /// assert_eq!(MiniArc::try_unwrap(shared).unwrap(), [1, 2, 3]);
+    /// ```
+    ///
+    MiniArc, AtomicUsize
+}
+
+unsafe impl<T, A> Send for MiniArc<[T], A>
+where
+    T: Send + Sync,
+    A: Allocator + Send + Sync,
+{
+}
+
+unsafe impl<T, A> Sync for MiniArc<[T], A>
+where
+    T: Send + Sync,
+    A: Allocator + Send + Sync,
+{
+}
@@ -0,0 +1,437 @@
+use crate::{Global, MiniVec};
+
+use core::ptr::NonNull;
+
+/// `TaggedMiniVec` is a [`MiniVec`](crate::MiniVec) which stores a small, user-defined tag in the
+/// otherwise unused low bits of its pointer, making it a building block for compact tagged unions.
+///
+/// Both the allocations of a `MiniVec` and the sentinel used by empty vectors are aligned to at
+/// least `align_of::<usize>()`, leaving 3 bits free on 64-bit targets and 2 on 32-bit ones. `BITS`
+/// picks how many of those are used for the tag, using more than are available is a compile-time
+/// error.
+///
+/// Slice operations are available directly through `Deref` and the common vector operations are
+/// forwarded. The full `MiniVec` API is reachable through [`as_vec`](TaggedMiniVec::as_vec) and
+/// [`as_vec_mut`](TaggedMiniVec::as_vec_mut), which mask off the tag and, for the latter, put it
+/// back once done.
+///
+/// Note: vectors of zero-sized types encode their length in the pointer so tagging one reduces
+/// its maximum length by a factor of `2^BITS`.
+///
+/// # Example
+///
+/// ```
+/// use minivec::{mini_vec, TaggedMiniVec};
+///
+/// let mut vec = TaggedMiniVec::<i32, 2>::from(mini_vec![1, 2, 3]);
+/// vec.set_tag(3);
+///
+/// vec.push(4);
+/// vec.as_vec_mut().reserve(128);
+///
+/// assert_eq!(vec.tag(), 3);
+/// assert_eq!(vec, [1, 2, 3, 4]);
+/// assert_eq!(core::mem::size_of_val(&vec), core::mem::size_of::<usize>());
+/// ```
+///
+pub struct TaggedMiniVec<T, const BITS: u32> {
+    buf: NonNull<u8>,
+    phantom: core::marker::PhantomData<T>,
+}
+
+/// `TaggedRef` is a read-only view of the [`MiniVec`](crate::MiniVec) inside a
+/// [`TaggedMiniVec`](crate::TaggedMiniVec), see [`as_vec`](crate::TaggedMiniVec::as_vec).
+///
+pub struct TaggedRef<'a, T, const BITS: u32> {
+    vec: core::mem::ManuallyDrop<MiniVec<T>>,
+    phantom: core::marker::PhantomData<&'a TaggedMiniVec<T, BITS>>,
+}
+
+/// `TaggedMut` is a mutable view of the [`MiniVec`](crate::MiniVec) inside a
+/// [`TaggedMiniVec`](crate::TaggedMiniVec) which re-applies the tag when dropped, see
+/// [`as_vec_mut`](crate::TaggedMiniVec::as_vec_mut).
+///
+pub struct TaggedMut<'a, T, const BITS: u32> {
+    vec: core::mem::ManuallyDrop<MiniVec<T>>,
+    tagged: &'a mut TaggedMiniVec<T, BITS>,
+}
+
+impl<T, const BITS: u32> TaggedMiniVec<T, BITS> {
+    const MASK: usize = {
+        assert!(
+            BITS <= core::mem::align_of::<usize>().trailing_zeros(),
+            "BITS exceeds the number of bits freed up by the alignment of the vector"
+        );
+
+        (1 << BITS) - 1
+    };
+
+    fn is_zst() -> bool {
+        core::mem::size_of::<T>() == 0
+    }
+
+    // the pointer of a vector of zero-sized types is nothing but its length so we make room for
+    // the tag by shifting it instead of masking
+    //
+    fn pack(buf: NonNull<u8>, tag: usize) -> NonNull<u8> {
+        debug_assert!(tag <= Self::MASK);
+
+        let p = if Self::is_zst() {
+            let addr = buf.as_ptr() as usize;
+            assert!(addr <= usize::MAX >> BITS, "capacity overflow");
+            (addr << BITS | tag) as *mut u8
+        } else {
+            // the tag bits are known to be clear so adding the tag sets them without losing the
+            // provenance of the pointer
+            //
+            buf.as_ptr().wrapping_add(tag)
+        };
+
+        unsafe { NonNull::new_unchecked(p) }
+    }
+
+    fn untagged(&self) -> NonNull<u8> {
+        let p = if Self::is_zst() {
+            (self.buf.as_ptr() as usize >> BITS) as *mut u8
+        } else {
+            self.buf.as_ptr().wrapping_sub(self.tag())
+        };
+
+        unsafe { NonNull::new_unchecked(p) }
+    }
+
+    // a bitwise copy of the underlying vector, which must never be dropped
+    //
+    fn inner(&self) -> core::mem::ManuallyDrop<MiniVec<T>> {
+        let mut vec = MiniVec::new();
+        vec.buf = self.untagged();
+        core::mem::ManuallyDrop::new(vec)
+    }
+
+    /// `as_vec` returns a read-only view of the underlying [`MiniVec`](crate::MiniVec) with the
+    /// tag masked off.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::TaggedMiniVec::<u8, 1>::with_tag(minivec::mini_vec![1, 2, 3], 1);
+    ///
+    /// assert_eq!(vec.as_vec().alignment(), core::mem::align_of::<usize>());
+    /// ```
+    ///
+    #[must_use]
+    pub fn as_vec(&self) -> TaggedRef<'_, T, BITS> {
+        TaggedRef {
+            vec: self.inner(),
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// `as_vec_mut` returns a mutable view of the underlying [`MiniVec`](crate::MiniVec) with the
+    /// tag masked off. The tag is applied to the possibly reallocated vector once the view is
+    /// dropped.
+    ///
+    /// Note: the `TaggedMiniVec` is left empty while the view is alive. Leaking the view with
+    /// `core::mem::forget` leaks the vector and leaves the `TaggedMiniVec` empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut vec = minivec::TaggedMiniVec::<i32, 3>::new();
+    /// vec.set_tag(5);
+    ///
+    /// vec.as_vec_mut().extend_from_slice(&[1, 2, 3]);
+    /// vec.as_vec_mut().retain(|&x| x != 2);
+    ///
+    /// assert_eq!(vec, [1, 3]);
+    /// assert_eq!(vec.tag(), 5);
+    /// ```
+    ///
+    pub fn as_vec_mut(&mut self) -> TaggedMut<'_, T, BITS> {
+        // the vector is moved into the view and an empty one is left behind until it's dropped,
+        // so leaking the view leaks the allocation instead of leaving a dangling pointer here
+        //
+        let vec = self.inner();
+        let empty = core::mem::ManuallyDrop::new(MiniVec::<T>::new());
+        self.buf = Self::pack(empty.buf, self.tag());
+
+        TaggedMut { vec, tagged: self }
+    }
+
+    /// `capacity` returns the capacity of the underlying vector.
+    ///
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.as_vec().capacity()
+    }
+
+    /// `clear` drops every element of the underlying vector, leaving the tag as-is.
+    ///
+    pub fn clear(&mut self) {
+        self.as_vec_mut().clear();
+    }
+
+    /// `into_inner` returns the underlying [`MiniVec`](crate::MiniVec), discarding the tag.
+    ///
+    #[must_use]
+    pub fn into_inner(self) -> MiniVec<T> {
+        let this = core::mem::ManuallyDrop::new(self);
+        core::mem::ManuallyDrop::into_inner(this.inner())
+    }
+
+    /// `is_empty` returns whether or not the underlying vector is empty.
+    ///
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `len` returns the length of the underlying vector.
+    ///
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.as_vec().len()
+    }
+
+    /// `new` constructs an empty `TaggedMiniVec` with a tag of 0.
+    ///
+    /// Note: does not allocate any memory.
+    ///
+    #[must_use]
+    pub fn new() -> TaggedMiniVec<T, BITS> {
+        TaggedMiniVec::with_tag(MiniVec::new(), 0)
+    }
+
+    /// `pop` removes the last element of the underlying vector and returns it, if there is one.
+    ///
+    pub fn pop(&mut self) -> Option<T> {
+        self.as_vec_mut().pop()
+    }
+
+    /// `push` appends `value` to the underlying vector.
+    ///
+    pub fn push(&mut self, value: T) {
+        self.as_vec_mut().push(value);
+    }
+
+    /// `set_tag` replaces the tag stored in the pointer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` doesn't fit in `BITS` bits.
+    ///
+    pub fn set_tag(&mut self, tag: usize) {
+        assert!(
+            tag <= Self::MASK,
+            "tag (is {}) should fit in {} bits",
+            tag,
+            BITS
+        );
+
+        self.buf = Self::pack(self.untagged(), tag);
+    }
+
+    /// `tag` returns the tag stored in the pointer.
+    ///
+    #[must_use]
+    pub fn tag(&self) -> usize {
+        self.buf.as_ptr() as usize & Self::MASK
+    }
+
+    /// `with_tag` takes ownership of `vec`, storing `tag` alongside it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` doesn't fit in `BITS` bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::TaggedMiniVec::<u64, 3>::with_tag(minivec::MiniVec::new(), 7);
+    ///
+    /// assert_eq!(vec.tag(), 7);
+    /// assert!(vec.is_empty());
+    /// ```
+    ///
+    #[must_use]
+    pub fn with_tag(vec: MiniVec<T>, tag: usize) -> TaggedMiniVec<T, BITS> {
+        assert!(
+            tag <= Self::MASK,
+            "tag (is {}) should fit in {} bits",
+            tag,
+            BITS
+        );
+
+        let mut vec = core::mem::ManuallyDrop::new(vec);
+        vec.unshare();
+
+        TaggedMiniVec {
+            buf: Self::pack(vec.buf, tag),
+            phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, const BITS: u32> Drop for TaggedMiniVec<T, BITS> {
+    fn drop(&mut self) {
+        drop(core::mem::ManuallyDrop::into_inner(self.inner()));
+    }
+}
+
+impl<T, const BITS: u32> core::ops::Deref for TaggedRef<'_, T, BITS> {
+    type Target = MiniVec<T>;
+
+    fn deref(&self) -> &MiniVec<T> {
+        &self.vec
+    }
+}
+
+impl<T, const BITS: u32> core::ops::Deref for TaggedMut<'_, T, BITS> {
+    type Target = MiniVec<T>;
+
+    fn deref(&self) -> &MiniVec<T> {
+        &self.vec
+    }
+}
+
+impl<T, const BITS: u32> core::ops::DerefMut for TaggedMut<'_, T, BITS> {
+    fn deref_mut(&mut self) -> &mut MiniVec<T> {
+        &mut self.vec
+    }
+}
+
+impl<T, const BITS: u32> Drop for TaggedMut<'_, T, BITS> {
+    fn drop(&mut self) {
+        let tag = self.tagged.tag();
+        self.tagged.buf = TaggedMiniVec::<T, BITS>::pack(self.vec.buf, tag);
+    }
+}
+
+impl<T, const BITS: u32> core::ops::Deref for TaggedMiniVec<T, BITS> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        let vec = self.inner();
+        unsafe { core::slice::from_raw_parts(vec.data(), vec.len()) }
+    }
+}
+
+impl<T, const BITS: u32> core::ops::DerefMut for TaggedMiniVec<T, BITS> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        let vec = self.inner();
+        unsafe { core::slice::from_raw_parts_mut(vec.data(), vec.len()) }
+    }
+}
+
+impl<T, const BITS: u32> AsRef<[T]> for TaggedMiniVec<T, BITS> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, const BITS: u32> AsMut<[T]> for TaggedMiniVec<T, BITS> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: Clone, const BITS: u32> Clone for TaggedMiniVec<T, BITS> {
+    fn clone(&self) -> Self {
+        let vec: MiniVec<T> = (*self.as_vec()).clone();
+        TaggedMiniVec::with_tag(vec, self.tag())
+    }
+}
+
+impl<T: core::fmt::Debug, const BITS: u32> core::fmt::Debug for TaggedMiniVec<T, BITS> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TaggedMiniVec")
+            .field("tag", &self.tag())
+            .field("vec", &&**self)
+            .finish()
+    }
+}
+
+impl<T, const BITS: u32> Default for TaggedMiniVec<T, BITS> {
+    fn default() -> Self {
+        TaggedMiniVec::new()
+    }
+}
+
+impl<T: core::hash::Hash, const BITS: u32> core::hash::Hash for TaggedMiniVec<T, BITS> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: core::hash::Hasher,
+    {
+        self.tag().hash(state);
+
+        let this: &[T] = self;
+        core::hash::Hash::hash(this, state);
+    }
+}
+
+impl<T: Eq, const BITS: u32> Eq for TaggedMiniVec<T, BITS> {}
+
+impl<T: PartialEq, const BITS: u32> PartialEq for TaggedMiniVec<T, BITS> {
+    fn eq(&self, other: &Self) -> bool {
+        let lhs: &[T] = self;
+        let rhs: &[T] = other;
+
+        self.tag() == other.tag() && lhs == rhs
+    }
+}
+
+impl<T: PartialEq, const BITS: u32> PartialEq<[T]> for TaggedMiniVec<T, BITS> {
+    fn eq(&self, other: &[T]) -> bool {
+        let lhs: &[T] = self;
+        lhs == other
+    }
+}
+
+impl<T: PartialEq, const BITS: u32, const N: usize> PartialEq<[T; N]> for TaggedMiniVec<T, BITS> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        let lhs: &[T] = self;
+        lhs == other
+    }
+}
+
+impl<T, const BITS: u32> From<MiniVec<T>> for TaggedMiniVec<T, BITS> {
+    fn from(vec: MiniVec<T>) -> Self {
+        TaggedMiniVec::with_tag(vec, 0)
+    }
+}
+
+impl<T, const BITS: u32> From<TaggedMiniVec<T, BITS>> for MiniVec<T> {
+    fn from(tagged: TaggedMiniVec<T, BITS>) -> Self {
+        tagged.into_inner()
+    }
+}
+
+impl<T, const BITS: u32> IntoIterator for TaggedMiniVec<T, BITS> {
+    type Item = T;
+    type IntoIter = crate::IntoIter<T, Global>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_inner().into_iter()
+    }
+}
+
+impl<'a, T, const BITS: u32> IntoIterator for &'a TaggedMiniVec<T, BITS> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const BITS: u32> IntoIterator for &'a mut TaggedMiniVec<T, BITS> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+unsafe impl<T: Send, const BITS: u32> Send for TaggedMiniVec<T, BITS> {}
+unsafe impl<T: Sync, const BITS: u32> Sync for TaggedMiniVec<T, BITS> {}
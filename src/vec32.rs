@@ -0,0 +1,934 @@
+use crate::r#impl::helpers::{next_aligned, next_capacity};
+#[cfg(feature = "alloc")]
+use crate::Global;
+use crate::{handle_reserve_error, zst_buf};
+use crate::{Allocator, MiniVec, TryReserveError};
+
+use core::alloc::Layout;
+use core::convert::TryFrom;
+use core::ptr::NonNull;
+
+#[repr(C)]
+struct Header32 {
+    len: u32,
+    cap: u32,
+}
+
+static EMPTY_HEADER32: Header32 = Header32 { len: 0, cap: 0 };
+
+fn empty_header32() -> NonNull<u8> {
+    NonNull::from(&EMPTY_HEADER32).cast::<u8>()
+}
+
+// `MiniVec32` never over-aligns so the alignment is implied by `T` and never stored
+//
+fn alignment32<T>() -> usize {
+    core::cmp::max(
+        core::mem::align_of::<T>(),
+        core::mem::align_of::<Header32>(),
+    )
+}
+
+fn data_offset32<T>() -> usize {
+    next_aligned(core::mem::size_of::<Header32>(), alignment32::<T>())
+}
+
+fn try_make_layout32<T>(capacity: usize) -> Result<Layout, TryReserveError> {
+    if capacity > u32::MAX as usize {
+        return Err(TryReserveError::CapacityOverflow);
+    }
+
+    capacity
+        .checked_mul(core::mem::size_of::<T>())
+        .and_then(|n| n.checked_add(data_offset32::<T>()))
+        .and_then(|n| Layout::from_size_align(n, alignment32::<T>()).ok())
+        .ok_or(TryReserveError::CapacityOverflow)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn to_u32(n: usize) -> u32 {
+    debug_assert!(u32::try_from(n).is_ok());
+    n as u32
+}
+
+/// `MiniVec32` is a compact variant of [`MiniVec`](crate::MiniVec) which stores its length and
+/// capacity as `u32`s, bringing the per-allocation overhead down to 8 bytes.
+///
+/// This makes it a good fit for large numbers of tiny vectors, such as adjacency lists, where a
+/// 24 byte header would often outweigh the payload.
+///
+/// A `MiniVec32` can hold at most `u32::MAX` elements. Growing past that point is reported as a
+/// [`TryReserveError::CapacityOverflow`](crate::TryReserveError::CapacityOverflow) by the fallible
+/// APIs and as a "capacity overflow" panic otherwise.
+///
+/// Note: `MiniVec32` does not support over-alignment. Its allocations are always aligned to
+/// `T` (and at least 4 bytes), which is what lets it leave the alignment out of the header.
+///
+/// # Example
+///
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// use minivec::MiniVec32;
+///
+/// let mut edges = MiniVec32::<u32>::new();
+/// edges.push(1);
+/// edges.push(7);
+///
+/// assert_eq!(edges, [1, 7]);
+/// assert_eq!(core::mem::size_of_val(&edges), core::mem::size_of::<usize>());
+/// ```
+///
+pub struct MiniVec32<
+    T,
+    #[cfg(feature = "alloc")] A: Allocator = Global,
+    #[cfg(not(feature = "alloc"))] A: Allocator,
+> {
+    buf: NonNull<u8>,
+    allocator: A,
+    phantom: core::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> MiniVec32<T> {
+    /// `new` constructs an empty `MiniVec32`.
+    ///
+    /// Note: does not allocate any memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniVec32::<i32>::new();
+    ///
+    /// assert_eq!(vec.len(), 0);
+    /// assert_eq!(vec.capacity(), 0);
+    /// ```
+    ///
+    #[must_use]
+    pub fn new() -> MiniVec32<T> {
+        MiniVec32::new_in(Global)
+    }
+
+    /// `try_with_capacity` is the fallible counterpart to
+    /// [`with_capacity`](MiniVec32::with_capacity).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](crate::TryReserveError) if `capacity` exceeds `u32::MAX` or
+    /// the allocation fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniVec32::<u8>::try_with_capacity(16).unwrap();
+    /// assert_eq!(vec.capacity(), 16);
+    ///
+    /// assert_eq!(
+    ///     minivec::MiniVec32::<u8>::try_with_capacity(u32::MAX as usize + 1).unwrap_err(),
+    ///     minivec::TryReserveError::CapacityOverflow
+    /// );
+    /// ```
+    ///
+    pub fn try_with_capacity(capacity: usize) -> Result<MiniVec32<T>, TryReserveError> {
+        let mut v = MiniVec32::new();
+        v.try_grow(capacity)?;
+        Ok(v)
+    }
+
+    /// `with_capacity` constructs an empty `MiniVec32` with room for at least `capacity` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics with "capacity overflow" if `capacity` exceeds `u32::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let vec = minivec::MiniVec32::<i32>::with_capacity(8);
+    ///
+    /// assert_eq!(vec.len(), 0);
+    /// assert_eq!(vec.capacity(), 8);
+    /// ```
+    ///
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> MiniVec32<T> {
+        MiniVec32::with_capacity_in(capacity, Global)
+    }
+}
+
+impl<T, A: Allocator> MiniVec32<T, A> {
+    fn is_allocated(&self) -> bool {
+        core::mem::size_of::<T>() > 0 && self.buf != empty_header32()
+    }
+
+    fn header(&self) -> &Header32 {
+        debug_assert!(core::mem::size_of::<T>() > 0);
+
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            self.buf.cast::<Header32>().as_ref()
+        }
+    }
+
+    fn data(&self) -> *mut T {
+        if !self.is_allocated() {
+            return NonNull::dangling().as_ptr();
+        }
+
+        unsafe { self.buf.as_ptr().add(data_offset32::<T>()).cast::<T>() }
+    }
+
+    fn try_grow(&mut self, capacity: usize) -> Result<(), TryReserveError> {
+        debug_assert!(capacity >= self.len());
+
+        if core::mem::size_of::<T>() == 0 {
+            return if capacity > u32::MAX as usize {
+                Err(TryReserveError::CapacityOverflow)
+            } else {
+                Ok(())
+            };
+        }
+
+        let old_capacity = self.capacity();
+        if capacity == old_capacity {
+            return Ok(());
+        }
+
+        let new_layout = try_make_layout32::<T>(capacity)?;
+
+        let len = self.len();
+
+        let new_buf = if self.is_allocated() {
+            let old_layout = try_make_layout32::<T>(old_capacity)?;
+
+            if new_layout.size() >= old_layout.size() {
+                unsafe { self.allocator.grow(self.buf, old_layout, new_layout) }
+            } else {
+                unsafe { self.allocator.shrink(self.buf, old_layout, new_layout) }
+            }
+        } else {
+            self.allocator.allocate(new_layout)
+        };
+
+        let new_buf = match new_buf {
+            Ok(p) => p.cast::<u8>(),
+            Err(_) => return Err(TryReserveError::AllocError { layout: new_layout }),
+        };
+
+        let header = Header32 {
+            len: to_u32(len),
+            cap: to_u32(capacity),
+        };
+
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+            core::ptr::write(new_buf.cast::<Header32>().as_ptr(), header);
+        }
+
+        self.buf = new_buf;
+        Ok(())
+    }
+
+    /// `allocator` returns a reference to the allocator backing the vector.
+    ///
+    #[must_use]
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+
+    /// `as_mut_slice` obtains a mutable slice over the vector's elements.
+    ///
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    /// `as_ptr` obtains a pointer to the vector's first element.
+    ///
+    /// Note: the pointer is dangling, but suitably aligned, if the vector has not allocated.
+    ///
+    #[must_use]
+    pub fn as_ptr(&self) -> *const T {
+        self.data()
+    }
+
+    /// `as_slice` obtains a slice over the vector's elements.
+    ///
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    /// `capacity` returns the number of elements the vector can hold without reallocating.
+    ///
+    /// Note: vectors of zero-sized types never allocate and report a capacity of `u32::MAX`.
+    ///
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        if core::mem::size_of::<T>() == 0 {
+            u32::MAX as usize
+        } else {
+            self.header().cap as usize
+        }
+    }
+
+    /// `clear` drops every element in the vector, retaining its capacity.
+    ///
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// `insert` places `element` at position `index`, shifting every element after it to the
+    /// right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len()` or if the vector would exceed `u32::MAX` elements.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec: minivec::MiniVec32<i32> = [1, 3].iter().copied().collect();
+    /// vec.insert(1, 2);
+    ///
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    ///
+    pub fn insert(&mut self, index: usize, element: T) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+
+        self.reserve(1);
+
+        unsafe {
+            let p = self.data().add(index);
+            core::ptr::copy(p, p.add(1), len - index);
+            core::ptr::write(p, element);
+            self.set_len(len + 1);
+        }
+    }
+
+    /// `is_empty` returns whether or not the vector has a length of zero.
+    ///
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `len` returns the number of elements in the vector.
+    ///
+    #[must_use]
+    pub fn len(&self) -> usize {
+        if core::mem::size_of::<T>() == 0 {
+            self.buf.as_ptr() as usize - 1
+        } else {
+            self.header().len as usize
+        }
+    }
+
+    /// `new_in` constructs an empty `MiniVec32` which will use `allocator` for all of its memory.
+    ///
+    /// Note: does not allocate any memory.
+    ///
+    pub fn new_in(allocator: A) -> MiniVec32<T, A> {
+        let buf = if core::mem::size_of::<T>() == 0 {
+            zst_buf(0)
+        } else {
+            empty_header32()
+        };
+
+        MiniVec32 {
+            buf,
+            allocator,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// `pop` removes the last element from the vector, should it exist.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec: minivec::MiniVec32<i32> = [1, 2].iter().copied().collect();
+    ///
+    /// assert_eq!(vec.pop(), Some(2));
+    /// assert_eq!(vec.pop(), Some(1));
+    /// assert_eq!(vec.pop(), None);
+    /// ```
+    ///
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+
+        unsafe {
+            self.set_len(len - 1);
+            Some(core::ptr::read(self.data().add(len - 1)))
+        }
+    }
+
+    /// `push` appends `value` to the end of the vector, reallocating if required.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector would exceed `u32::MAX` elements.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::MiniVec32::new();
+    /// vec.push(1_u16);
+    /// vec.push(2);
+    ///
+    /// assert_eq!(vec, [1, 2]);
+    /// ```
+    ///
+    pub fn push(&mut self, value: T) {
+        if let Err(err) = self.try_push(value) {
+            handle_reserve_error(err);
+        }
+    }
+
+    /// `remove` removes the element at `index`, shifting every element after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    ///
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "removal index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+
+        unsafe {
+            let p = self.data().add(index);
+            let x = core::ptr::read(p);
+            core::ptr::copy(p.add(1), p, len - index - 1);
+            self.set_len(len - 1);
+            x
+        }
+    }
+
+    /// `reserve` ensures there is room for at least `additional` more elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity would exceed `u32::MAX`.
+    ///
+    pub fn reserve(&mut self, additional: usize) {
+        if let Err(err) = self.try_reserve(additional) {
+            handle_reserve_error(err);
+        }
+    }
+
+    /// `reserve_exact` ensures there is room for exactly `additional` more elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity would exceed `u32::MAX`.
+    ///
+    pub fn reserve_exact(&mut self, additional: usize) {
+        if let Err(err) = self.try_reserve_exact(additional) {
+            handle_reserve_error(err);
+        }
+    }
+
+    /// `retain` removes every element for which `f` returns `false`.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec: minivec::MiniVec32<i32> = (0..10).collect();
+    /// vec.retain(|&x| x % 3 == 0);
+    ///
+    /// assert_eq!(vec, [0, 3, 6, 9]);
+    /// ```
+    ///
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        let data = self.data();
+
+        // as in `MiniVec::retain`, the length is zeroed up-front so a panicking predicate can
+        // only ever leak elements
+        //
+        unsafe { self.set_len(0) };
+
+        let mut kept = 0;
+        for idx in 0..len {
+            unsafe {
+                let p = data.add(idx);
+                if f(&*p) {
+                    core::ptr::copy(p, data.add(kept), 1);
+                    kept += 1;
+                } else {
+                    core::ptr::drop_in_place(p);
+                }
+            }
+        }
+
+        unsafe { self.set_len(kept) };
+    }
+
+    /// `set_len` reassigns the length of the vector without touching any of its elements.
+    ///
+    /// # Safety
+    ///
+    /// `len` must not exceed [`capacity()`](MiniVec32::capacity) and every element up to `len`
+    /// must be initialized.
+    ///
+    pub unsafe fn set_len(&mut self, len: usize) {
+        if core::mem::size_of::<T>() == 0 {
+            self.buf = zst_buf(len);
+            return;
+        }
+
+        if !self.is_allocated() {
+            debug_assert_eq!(len, 0);
+            return;
+        }
+
+        debug_assert!(len <= self.capacity());
+
+        #[allow(clippy::cast_ptr_alignment)]
+        {
+            (*self.buf.cast::<Header32>().as_ptr()).len = to_u32(len);
+        }
+    }
+
+    /// `shrink_to_fit` reduces the vector's capacity to its length.
+    ///
+    pub fn shrink_to_fit(&mut self) {
+        if !self.is_allocated() || self.len() == self.capacity() {
+            return;
+        }
+
+        if let Err(err) = self.try_grow(self.len()) {
+            handle_reserve_error(err);
+        }
+    }
+
+    /// `swap_remove` removes the element at `index`, replacing it with the last element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len()`.
+    ///
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "swap_remove index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+
+        self.swap(index, len - 1);
+        self.pop().unwrap()
+    }
+
+    /// `truncate` drops every element past `len`. Has no effect if `len` is greater than the
+    /// vector's current length.
+    ///
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+
+        unsafe {
+            self.set_len(len);
+
+            let tail = core::ptr::slice_from_raw_parts_mut(self.data().add(len), old_len - len);
+            core::ptr::drop_in_place(tail);
+        }
+    }
+
+    /// `try_push` is the fallible counterpart to [`push`](MiniVec32::push).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](crate::TryReserveError) if the vector is at its maximum
+    /// capacity of `u32::MAX` elements or the allocation fails. The vector is left unchanged.
+    ///
+    pub fn try_push(&mut self, value: T) -> Result<(), TryReserveError> {
+        self.try_reserve(1)?;
+
+        let len = self.len();
+        unsafe {
+            core::ptr::write(self.data().add(len), value);
+            self.set_len(len + 1);
+        }
+
+        Ok(())
+    }
+
+    /// `try_reserve` is the fallible counterpart to [`reserve`](MiniVec32::reserve).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](crate::TryReserveError) if the new capacity would exceed
+    /// `u32::MAX` or the allocation fails. The vector is left unchanged.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::MiniVec32::<u8>::new();
+    ///
+    /// assert!(vec.try_reserve(10).is_ok());
+    /// assert_eq!(
+    ///     vec.try_reserve(u32::MAX as usize + 1),
+    ///     Err(minivec::TryReserveError::CapacityOverflow)
+    /// );
+    /// ```
+    ///
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let capacity = self.capacity();
+        let total_required = self
+            .len()
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+
+        if total_required <= capacity {
+            return Ok(());
+        }
+
+        if total_required > u32::MAX as usize {
+            return Err(TryReserveError::CapacityOverflow);
+        }
+
+        let mut new_capacity = next_capacity::<T>(capacity);
+        while new_capacity < total_required {
+            new_capacity = new_capacity.saturating_mul(2);
+        }
+
+        self.try_grow(core::cmp::min(new_capacity, u32::MAX as usize))
+    }
+
+    /// `try_reserve_exact` is the fallible counterpart to
+    /// [`reserve_exact`](MiniVec32::reserve_exact).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TryReserveError`](crate::TryReserveError) if the new capacity would exceed
+    /// `u32::MAX` or the allocation fails. The vector is left unchanged.
+    ///
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let total_required = self
+            .len()
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+
+        if total_required <= self.capacity() {
+            return Ok(());
+        }
+
+        self.try_grow(total_required)
+    }
+
+    /// `with_capacity_in` constructs an empty `MiniVec32` with room for at least `capacity`
+    /// elements, using `allocator` for all of its memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics with "capacity overflow" if `capacity` exceeds `u32::MAX`.
+    ///
+    pub fn with_capacity_in(capacity: usize, allocator: A) -> MiniVec32<T, A> {
+        let mut v = MiniVec32::new_in(allocator);
+        if let Err(err) = v.try_grow(capacity) {
+            handle_reserve_error(err);
+        }
+
+        v
+    }
+}
+
+impl<T: Clone, A: Allocator> MiniVec32<T, A> {
+    /// `extend_from_slice` clones every element of `elems` onto the end of the vector.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// let mut vec = minivec::MiniVec32::new();
+    /// vec.extend_from_slice(&[1, 2, 3]);
+    ///
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    ///
+    pub fn extend_from_slice(&mut self, elems: &[T]) {
+        self.reserve(elems.len());
+
+        for x in elems {
+            self.push(x.clone());
+        }
+    }
+}
+
+impl<T, A: Allocator> Drop for MiniVec32<T, A> {
+    fn drop(&mut self) {
+        unsafe { core::ptr::drop_in_place(self.as_mut_slice()) };
+
+        if !self.is_allocated() {
+            return;
+        }
+
+        let layout = try_make_layout32::<T>(self.capacity()).unwrap();
+        unsafe { self.allocator.deallocate(self.buf, layout) };
+    }
+}
+
+impl<T, A: Allocator> core::ops::Deref for MiniVec32<T, A> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.data(), self.len()) }
+    }
+}
+
+impl<T, A: Allocator> core::ops::DerefMut for MiniVec32<T, A> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { core::slice::from_raw_parts_mut(self.data(), self.len()) }
+    }
+}
+
+impl<T, A: Allocator> AsRef<[T]> for MiniVec32<T, A> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, A: Allocator> AsMut<[T]> for MiniVec32<T, A> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, A: Allocator> core::borrow::Borrow<[T]> for MiniVec32<T, A> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, A: Allocator> core::borrow::BorrowMut<[T]> for MiniVec32<T, A> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for MiniVec32<T, A> {
+    fn clone(&self) -> Self {
+        let mut copy = MiniVec32::with_capacity_in(self.len(), self.allocator.clone());
+        copy.extend_from_slice(self);
+        copy
+    }
+}
+
+impl<T: core::fmt::Debug, A: Allocator> core::fmt::Debug for MiniVec32<T, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let this: &[T] = self;
+
+        this.fmt(f)
+    }
+}
+
+impl<T, A: Allocator + Default> Default for MiniVec32<T, A> {
+    fn default() -> Self {
+        MiniVec32::new_in(A::default())
+    }
+}
+
+impl<T: core::hash::Hash, A: Allocator> core::hash::Hash for MiniVec32<T, A> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: core::hash::Hasher,
+    {
+        let this: &[T] = self;
+        core::hash::Hash::hash(this, state);
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for MiniVec32<T, A> {}
+
+impl<T: Ord, A: Allocator> Ord for MiniVec32<T, A> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let x: &[T] = self;
+        let y: &[T] = other;
+
+        x.cmp(y)
+    }
+}
+
+impl<T, A: Allocator, V> PartialEq<V> for MiniVec32<T, A>
+where
+    V: AsRef<[T]>,
+    T: PartialEq,
+{
+    fn eq(&self, other: &V) -> bool {
+        let lhs: &[T] = self;
+        let rhs: &[T] = other.as_ref();
+
+        lhs == rhs
+    }
+}
+
+impl<T, A: Allocator, V> PartialOrd<V> for MiniVec32<T, A>
+where
+    V: AsRef<[T]>,
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &V) -> Option<core::cmp::Ordering> {
+        let x: &[T] = self;
+        let y: &[T] = other.as_ref();
+
+        x.partial_cmp(y)
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for MiniVec32<T, A> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy, A: Allocator> Extend<&'a T> for MiniVec32<T, A> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::iter::FromIterator<T> for MiniVec32<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut v = MiniVec32::new();
+        v.extend(iter);
+        v
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> From<&[T]> for MiniVec32<T> {
+    fn from(elems: &[T]) -> Self {
+        let mut v = MiniVec32::with_capacity(elems.len());
+        v.extend_from_slice(elems);
+        v
+    }
+}
+
+impl<T, A: Allocator> From<MiniVec32<T, A>> for MiniVec<T, A> {
+    fn from(v: MiniVec32<T, A>) -> Self {
+        let len = v.len();
+        let src = v.data();
+
+        let v = core::mem::ManuallyDrop::new(v);
+        let allocator = unsafe { core::ptr::read(core::ptr::addr_of!(v.allocator)) };
+
+        let mut vec = MiniVec::with_capacity_in(len, allocator);
+        unsafe {
+            core::ptr::copy_nonoverlapping(src, vec.data(), len);
+            vec.set_len(len);
+        }
+
+        if v.is_allocated() {
+            let layout = try_make_layout32::<T>(v.capacity()).unwrap();
+            unsafe { vec.allocator.deallocate(v.buf, layout) };
+        }
+
+        vec
+    }
+}
+
+impl<T, A: Allocator> TryFrom<MiniVec<T, A>> for MiniVec32<T, A> {
+    type Error = MiniVec<T, A>;
+
+    /// Moves the elements of a `MiniVec` into a new `MiniVec32`, failing with the original vector
+    /// if it holds more than `u32::MAX` elements or the allocation fails.
+    ///
+    fn try_from(mut v: MiniVec<T, A>) -> Result<Self, Self::Error> {
+        let len = v.len();
+        let allocator = unsafe { core::ptr::read(core::ptr::addr_of!(v.allocator)) };
+
+        let mut vec = MiniVec32::new_in(allocator);
+        if vec.try_grow(len).is_err() {
+            core::mem::forget(vec);
+            return Err(v);
+        }
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(v.data(), vec.data(), len);
+            v.forget_elements();
+            vec.set_len(len);
+        }
+
+        // the allocator was duplicated above so hand the vector's copy back without running its
+        // destructor, after releasing the now empty allocation
+        //
+        let v = core::mem::ManuallyDrop::new(v);
+        if v.is_allocated() {
+            let layout =
+                crate::r#impl::helpers::make_layout::<T, ()>(v.capacity(), v.layout_alignment());
+            unsafe { vec.allocator.deallocate(v.buf, layout) };
+        }
+
+        Ok(vec)
+    }
+}
+
+impl<T, A: Allocator> IntoIterator for MiniVec32<T, A> {
+    type Item = T;
+    type IntoIter = crate::IntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MiniVec::from(self).into_iter()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a MiniVec32<T, A> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut MiniVec32<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+unsafe impl<T: Send, A: Allocator + Send> Send for MiniVec32<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for MiniVec32<T, A> {}
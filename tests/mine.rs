@@ -1,3 +1,5 @@
+#![cfg(feature = "alloc")]
+
 extern crate minivec;
 
 use minivec::{mini_vec, MiniVec};
@@ -371,7 +373,7 @@ fn minivec_extend() {
     let mut v = mini_vec![String::from("1"), String::from("2"), String::from("3")];
     let other = vec![String::from("4"), String::from("5"), String::from("6")];
 
-    v.extend(other.into_iter());
+    v.extend(other);
 
     assert_eq!(
         v,
@@ -937,3 +939,1831 @@ fn minivec_page_aligned() {
     let mut vec = minivec::MiniVec::<i32>::with_alignment(capacity, alignment).unwrap();
     assert_eq!(vec.as_mut_ptr() as usize % alignment, 0);
 }
+
+#[test]
+fn minivec_try_reserve() {
+    let mut v = mini_vec![1, 2, 3];
+
+    v.try_reserve(64).unwrap();
+    assert!(v.capacity() >= 67);
+
+    let cap = v.capacity();
+    assert_eq!(
+        v.try_reserve(usize::MAX),
+        Err(minivec::TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(
+        v.try_reserve_exact(usize::MAX - 1),
+        Err(minivec::TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(v.capacity(), cap);
+
+    let mut v = MiniVec::<u8>::new();
+    assert_eq!(
+        v.try_reserve_exact(isize::MAX as usize),
+        Err(minivec::TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(v.capacity(), 0);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+#[cfg_attr(miri, ignore)]
+fn minivec_try_reserve_alloc_error() {
+    let mut v = mini_vec![1_u8, 2, 3];
+    let cap = v.capacity();
+
+    match v.try_reserve_exact(isize::MAX as usize / 2) {
+        Err(minivec::TryReserveError::AllocError { layout }) => {
+            assert!(layout.size() >= isize::MAX as usize / 2);
+        }
+        other => panic!("expected an allocation failure, got {:?}", other),
+    }
+
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(v.capacity(), cap);
+}
+
+#[test]
+fn minivec_try_push_insert() {
+    let mut v = MiniVec::<String>::new();
+    for i in 0..32 {
+        v.try_push(i.to_string()).unwrap();
+    }
+    assert_eq!(v.len(), 32);
+
+    v.try_insert(0, String::from("first")).unwrap();
+    assert_eq!(v[0], "first");
+    assert_eq!(v[1], "0");
+
+    v.try_extend_from_slice(&[String::from("a"), String::from("b")])
+        .unwrap();
+    assert_eq!(v.len(), 35);
+    assert_eq!(v[34], "b");
+}
+
+#[test]
+fn minivec_try_with_capacity() {
+    let v = MiniVec::<i32>::try_with_capacity(128).unwrap();
+    assert_eq!(v.capacity(), 128);
+    assert!(v.is_empty());
+
+    assert_eq!(
+        MiniVec::<i32>::try_with_capacity(usize::MAX).unwrap_err(),
+        minivec::TryReserveError::CapacityOverflow
+    );
+
+    let v = MiniVec::<i32>::try_with_alignment(128, 64).unwrap();
+    assert_eq!(v.capacity(), 128);
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+
+    assert_eq!(
+        MiniVec::<i32>::try_with_alignment(128, 4).unwrap_err(),
+        minivec::TryReserveError::InvalidAlignment(minivec::LayoutErr::AlignmentTooSmall)
+    );
+}
+
+#[derive(Clone, Default)]
+struct CountingAllocator {
+    live: std::rc::Rc<std::cell::Cell<isize>>,
+    total: std::rc::Rc<std::cell::Cell<usize>>,
+}
+
+unsafe impl minivec::Allocator for CountingAllocator {
+    fn allocate(
+        &self,
+        layout: std::alloc::Layout,
+    ) -> Result<std::ptr::NonNull<[u8]>, minivec::AllocError> {
+        self.live.set(self.live.get() + 1);
+        self.total.set(self.total.get() + 1);
+        minivec::Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: std::ptr::NonNull<u8>, layout: std::alloc::Layout) {
+        self.live.set(self.live.get() - 1);
+        minivec::Global.deallocate(ptr, layout);
+    }
+}
+
+#[test]
+fn minivec_custom_allocator() {
+    let alloc = CountingAllocator::default();
+
+    {
+        let mut v = MiniVec::<String, _>::new_in(alloc.clone());
+        assert_eq!(alloc.total.get(), 0);
+
+        for i in 0..64 {
+            v.push(i.to_string());
+        }
+        assert_eq!(alloc.live.get(), 1);
+        assert!(alloc.total.get() > 1);
+
+        let w = v.clone();
+        assert_eq!(w, v);
+        assert_eq!(alloc.live.get(), 2);
+
+        let tail = v.split_off(32);
+        assert_eq!(tail.len(), 32);
+        assert_eq!(tail[0], "32");
+        assert_eq!(alloc.live.get(), 3);
+
+        let drained: Vec<_> = v.drain(0..4).collect();
+        assert_eq!(drained, ["0", "1", "2", "3"]);
+
+        let spliced: Vec<_> = v
+            .splice(0..2, (0..8).map(|i| format!("new{}", i)))
+            .collect();
+        assert_eq!(spliced, ["4", "5"]);
+        assert_eq!(v[0], "new0");
+        assert_eq!(v.len(), 34);
+
+        let mut iter = tail.into_iter();
+        assert_eq!(iter.next().unwrap(), "32");
+        assert_eq!(iter.clone().count(), 31);
+        drop(iter);
+
+        assert_eq!(alloc.live.get(), 2);
+    }
+
+    assert_eq!(alloc.live.get(), 0);
+
+    let v = MiniVec::<i32, _>::with_alignment_in(16, 64, alloc.clone()).unwrap();
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+    assert_eq!(alloc.live.get(), 1);
+    drop(v);
+
+    let mut v = MiniVec::<i32, _>::with_capacity_in(16, alloc.clone());
+    v.push(1337);
+    assert_eq!(alloc.live.get(), 1);
+
+    let (ptr, len, cap, a) = v.into_raw_parts_with_alloc();
+    let v = unsafe { MiniVec::from_raw_parts_in(ptr, len, cap, a) };
+    drop(v);
+    assert_eq!(alloc.live.get(), 0);
+}
+
+#[cfg(feature = "allocator-api2")]
+#[test]
+fn minivec_allocator_api2() {
+    use allocator_api2::alloc::{AllocError, Allocator};
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::ptr::NonNull;
+
+    #[derive(Default)]
+    struct Counting(std::cell::Cell<usize>);
+
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            let p = NonNull::new(unsafe { System.alloc(layout) }).ok_or(AllocError)?;
+            Ok(NonNull::slice_from_raw_parts(p, layout.size()))
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            System.dealloc(ptr.as_ptr(), layout);
+        }
+    }
+
+    let counting = Counting::default();
+    let mut v = MiniVec::<String, _>::new_in(minivec::Api2(&counting));
+    for i in 0..64 {
+        v.push(i.to_string());
+    }
+
+    assert_eq!(v.len(), 64);
+    assert_eq!(v[63], "63");
+    assert!(counting.0.get() > 1);
+
+    // the feature is additive: references to the crate's own allocators still qualify
+    //
+    let global = minivec::Global;
+    let mut w = MiniVec::<i32, _>::new_in(&global);
+    w.push(1);
+
+    let alloc = CountingAllocator::default();
+    let mut x = MiniVec::<i32, _>::new_in(&alloc);
+    x.extend_from_slice(&w);
+    assert_eq!(x, [1]);
+    assert_eq!(alloc.live.get(), 1);
+}
+
+#[test]
+fn minivec_single_pointer_with_global() {
+    assert_eq!(
+        std::mem::size_of::<MiniVec<i32>>(),
+        std::mem::size_of::<*const ()>()
+    );
+    assert_eq!(
+        std::mem::size_of::<MiniVec<i32, minivec::Global>>(),
+        std::mem::size_of::<*const ()>()
+    );
+}
+
+#[test]
+fn minivec_option_niche() {
+    assert_eq!(
+        std::mem::size_of::<Option<MiniVec<i32>>>(),
+        std::mem::size_of::<usize>()
+    );
+    assert_eq!(
+        std::mem::size_of::<Option<MiniVec<()>>>(),
+        std::mem::size_of::<usize>()
+    );
+    assert_eq!(
+        std::mem::size_of::<Result<MiniVec<String>, ()>>(),
+        std::mem::size_of::<usize>()
+    );
+
+    let mut empty = MiniVec::<i32>::new();
+    assert_eq!(empty.len(), 0);
+    assert_eq!(empty.capacity(), 0);
+    assert!(empty.as_ptr().is_null());
+    assert_eq!(empty.as_slice(), &[]);
+    assert_eq!(empty.drain(..).count(), 0);
+    empty.truncate(0);
+    empty.clear();
+    empty.retain(|_| true);
+    unsafe { empty.set_len(0) };
+
+    let mut v = Some(empty);
+    v.as_mut().unwrap().push(1);
+    assert_eq!(v.unwrap(), [1]);
+}
+
+#[test]
+fn minivec_alignment_preserved() {
+    let is_aligned = |p: *const f32, alignment: usize| p as usize % alignment == 0;
+
+    let mut v = MiniVec::<f32>::with_alignment(16, 64).unwrap();
+    v.extend((0..16).map(|x| x as f32));
+
+    let w = v.clone();
+    assert_eq!(w, v);
+    assert!(is_aligned(w.as_ptr(), 64));
+
+    let tail = v.split_off(8);
+    assert_eq!(tail.len(), 8);
+    assert!(is_aligned(tail.as_ptr(), 64));
+
+    let mut iter = w.into_iter();
+    iter.next();
+    let iter = iter.clone();
+    assert!(is_aligned(iter.as_slice().as_ptr(), 64));
+    assert_eq!(iter.count(), 15);
+
+    // a vector without any capacity must still remember its alignment
+    //
+    let mut empty = MiniVec::<f32>::with_alignment(0, 128).unwrap();
+    assert_eq!(empty.capacity(), 0);
+    assert!(is_aligned(empty.clone().as_ptr(), 128));
+
+    empty.push(1.0);
+    assert!(is_aligned(empty.as_ptr(), 128));
+    empty.shrink_to_fit();
+    assert!(is_aligned(empty.as_ptr(), 128));
+
+    let v = MiniVec::from_slice_aligned(&[1.0_f32, 2.0, 3.0], 32).unwrap();
+    assert_eq!(v, [1.0, 2.0, 3.0]);
+    assert!(is_aligned(v.as_ptr(), 32));
+
+    let v =
+        MiniVec::collect_aligned((0..100).map(|x| x as f32).filter(|x| *x > 50.0), 256).unwrap();
+    assert_eq!(v.len(), 49);
+    assert!(is_aligned(v.as_ptr(), 256));
+
+    let v = v.clone_with_alignment(512).unwrap();
+    assert_eq!(v.len(), 49);
+    assert!(is_aligned(v.as_ptr(), 512));
+
+    assert_eq!(
+        v.clone_with_alignment(4),
+        Err(minivec::LayoutErr::AlignmentTooSmall)
+    );
+    assert_eq!(
+        MiniVec::from_slice_aligned(&[1_u8], 9),
+        Err(minivec::LayoutErr::AlignmentNotPowerOfTwo)
+    );
+    assert!(MiniVec::collect_aligned(0..4, 1).is_err());
+}
+
+#[test]
+fn minivec_realign() {
+    let mut v: MiniVec<String> = (0..32).map(|x| x.to_string()).collect();
+    let default_alignment = v.alignment();
+    let capacity = v.capacity();
+
+    v.realign(default_alignment).unwrap();
+    assert_eq!(v.alignment(), default_alignment);
+
+    v.realign(256).unwrap();
+    assert_eq!(v.alignment(), 256);
+    assert_eq!(v.as_ptr() as usize % 256, 0);
+    assert_eq!(v.capacity(), capacity);
+    assert_eq!(v.len(), 32);
+    assert_eq!(v[31], "31");
+
+    // the current allocation already satisfies smaller alignments so nothing moves
+    //
+    let p = v.as_ptr();
+    v.realign(64).unwrap();
+    assert_eq!(v.as_ptr(), p);
+    assert_eq!(v.alignment(), 256);
+
+    // growing the vector must keep the new alignment
+    //
+    v.extend((32..1024).map(|x| x.to_string()));
+    assert_eq!(v.as_ptr() as usize % 256, 0);
+    assert_eq!(v[1023], "1023");
+
+    assert_eq!(v.realign(1), Err(minivec::LayoutErr::AlignmentTooSmall));
+    assert_eq!(
+        v.try_realign(257),
+        Err(minivec::TryReserveError::InvalidAlignment(
+            minivec::LayoutErr::AlignmentNotPowerOfTwo
+        ))
+    );
+
+    let mut empty = MiniVec::<u8>::new();
+    empty.realign(128).unwrap();
+    assert_eq!(empty.alignment(), 128);
+    assert_eq!(empty.capacity(), 0);
+    empty.push(1);
+    assert_eq!(empty.as_ptr() as usize % 128, 0);
+
+    let mut zst = mini_vec![(); 4];
+    zst.realign(64).unwrap();
+    assert_eq!(zst.len(), 4);
+    assert_eq!(zst.alignment(), MiniVec::<()>::new().alignment());
+
+    // data that already starts on a 64 byte boundary stays where it is, only the reported
+    // alignment changes
+    //
+    let mut vecs: Vec<MiniVec<u64>> = Vec::new();
+    let mut v = loop {
+        let mut v = MiniVec::<u64>::with_alignment(8, 16).unwrap();
+        v.extend_from_slice(&[1, 2, 3]);
+        if v.as_ptr() as usize % 64 == 0 {
+            break v;
+        }
+        vecs.push(v);
+    };
+
+    let p = v.as_ptr();
+    v.realign(64).unwrap();
+    assert_eq!(v.as_ptr(), p);
+    assert_eq!(v.alignment(), 64);
+    assert_eq!(v.capacity(), 8);
+
+    let mut v = unsafe { MiniVec::from_raw(v.into_raw()) };
+    assert_eq!(v.alignment(), 64);
+    assert_eq!(v, [1, 2, 3]);
+
+    v.extend(4..100);
+    assert_eq!(v.alignment(), 64);
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+    assert_eq!(v, (1..100).collect::<Vec<_>>());
+
+    v.shrink_to_fit();
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+    assert_eq!(v.clone(), v);
+
+    // an invalid alignment is rejected before a static vector is copied
+    //
+    let mut nums: MiniVec<u64> = minivec::static_mini_vec![1, 2, 3];
+    let p = nums.as_ptr();
+    assert!(nums.realign(3).is_err());
+    assert_eq!(nums.as_ptr(), p);
+    nums.realign(256).unwrap();
+    assert_ne!(nums.as_ptr(), p);
+    assert_eq!(nums.as_ptr() as usize % 256, 0);
+    assert_eq!(nums, [1, 2, 3]);
+}
+
+#[test]
+fn minivec_raw_round_trip_over_aligned() {
+    for &alignment in &[8, 16, 32, 64, 128, 4096] {
+        let mut v = MiniVec::<u16>::with_alignment(4, alignment).unwrap();
+        v.extend_from_slice(&[1, 2, 3, 4, 5]);
+
+        let ptr = v.as_mut_ptr();
+        std::mem::forget(v);
+
+        let mut v = unsafe { MiniVec::from_raw_part(ptr) };
+        assert_eq!(v, [1, 2, 3, 4, 5]);
+        assert_eq!(v.alignment(), alignment);
+
+        v.push(6);
+
+        let (ptr, len, cap) = v.into_raw_parts();
+        let v = unsafe { MiniVec::from_raw_parts(ptr, len, cap) };
+        assert_eq!(v, [1, 2, 3, 4, 5, 6]);
+
+        let ptr = v.into_raw();
+        assert_eq!(ptr as usize % alignment, 0);
+
+        let mut v = unsafe { MiniVec::from_raw(ptr) };
+        assert_eq!(v, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(v.alignment(), alignment);
+
+        v.realign(alignment * 2).unwrap();
+        let v = unsafe { MiniVec::from_raw(v.into_raw()) };
+        assert_eq!(v.alignment(), alignment * 2);
+        assert_eq!(v, [1, 2, 3, 4, 5, 6]);
+    }
+
+    let empty = MiniVec::<String>::new();
+    let ptr = empty.into_raw();
+    assert!(ptr.is_null());
+    let mut empty = unsafe { MiniVec::from_raw(ptr) };
+    assert!(empty.is_empty());
+    empty.push(String::from("hello"));
+
+    let (ptr, len, cap) = MiniVec::<String>::new().into_raw_parts();
+    let empty = unsafe { MiniVec::from_raw_parts(ptr, len, cap) };
+    assert_eq!(empty.capacity(), 0);
+
+    let zst = mini_vec![(); 5];
+    let zst = unsafe { MiniVec::<()>::from_raw(zst.into_raw()) };
+    assert_eq!(zst.len(), 5);
+}
+
+#[test]
+fn minivec_std_conversions() {
+    use std::convert::TryFrom;
+
+    let v: MiniVec<String> = MiniVec::from(vec![String::from("a"), String::from("b")]);
+    assert_eq!(v.as_slice(), ["a", "b"]);
+
+    let w: Vec<String> = Vec::from(v.clone());
+    assert_eq!(w, v);
+    assert_eq!(v, w);
+
+    let b: Box<[String]> = v.clone().into_boxed_slice();
+    assert_eq!(&*b, ["a", "b"]);
+
+    let v2 = MiniVec::from(b);
+    assert_eq!(v2, v);
+
+    let v = MiniVec::from([1, 2, 3, 4]);
+    assert_eq!(v, [1, 2, 3, 4]);
+    assert_eq!([1, 2, 3, 4], v);
+    assert_eq!(&[1, 2, 3, 4][..], v);
+    assert_eq!(vec![1, 2, 3, 4], v);
+
+    let arr: [i32; 4] = <[i32; 4]>::try_from(v).unwrap();
+    assert_eq!(arr, [1, 2, 3, 4]);
+
+    let v = mini_vec![String::from("x"), String::from("y")];
+    let v = <[String; 3]>::try_from(v).unwrap_err();
+    assert_eq!(v.as_slice(), ["x", "y"]);
+    let [x, y] = <[String; 2]>::try_from(v).unwrap();
+    assert_eq!((x.as_str(), y.as_str()), ("x", "y"));
+
+    let empty: MiniVec<i32> = MiniVec::from(Vec::new());
+    assert!(empty.is_empty());
+    assert!(Vec::from(empty).is_empty());
+    assert!(MiniVec::<i32>::new().into_boxed_slice().is_empty());
+    assert_eq!(MiniVec::<u8>::from([]).len(), 0);
+    let _: [u8; 0] = <[u8; 0]>::try_from(MiniVec::<u8>::new()).unwrap();
+
+    let bytes = MiniVec::from(String::from("hello"));
+    assert_eq!(bytes, b"hello");
+    assert!(MiniVec::from("").is_empty());
+
+    let v = MiniVec::<f32>::with_alignment(4, 64).unwrap();
+    let v: Vec<f32> = v.into();
+    assert!(v.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn minivec_std_io() {
+    use std::io::{BufRead, IoSlice, Read, Write};
+
+    let mut v: MiniVec<u8> = MiniVec::new();
+    let (n, s) = (1, "two");
+    write!(v, "{n}-{s}").unwrap();
+    assert_eq!(v, b"1-two");
+
+    let n = v
+        .write_vectored(&[IoSlice::new(b" three"), IoSlice::new(b" four")])
+        .unwrap();
+    assert_eq!(n, 11);
+    assert_eq!(v, b"1-two three four");
+    v.flush().unwrap();
+
+    let data: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+    let mut out = mini_vec![1_u8, 2, 3];
+    let n = minivec::read_to_end_into(&mut std::io::Cursor::new(&data), &mut out).unwrap();
+    assert_eq!(n, data.len());
+    assert_eq!(&out[..3], [1, 2, 3]);
+    assert_eq!(&out[3..], data.as_slice());
+
+    let mut iter = mini_vec![b'a', b'b', b'c', b'\n', b'd'].into_iter();
+    let mut line = String::new();
+    iter.read_line(&mut line).unwrap();
+    assert_eq!(line, "abc\n");
+    assert_eq!(iter.next(), Some(b'd'));
+    assert_eq!(iter.read(&mut [0; 4]).unwrap(), 0);
+
+    let mut v = mini_vec![b'x'; 8];
+    let mut buf = [0_u8; 3];
+    {
+        let mut drain = v.drain(2..);
+        assert_eq!(drain.read(&mut buf).unwrap(), 3);
+        assert_eq!(drain.fill_buf().unwrap(), b"xxx");
+        drain.consume(1);
+        assert_eq!(drain.len(), 2);
+    }
+    assert_eq!(buf, *b"xxx");
+    assert_eq!(v.len(), 2);
+
+    let err: Box<dyn std::error::Error> = Box::new(minivec::TryReserveError::CapacityOverflow);
+    assert!(!err.to_string().is_empty());
+}
+
+#[test]
+fn minivec_string() {
+    use minivec::{mini_format, MiniString};
+    use std::collections::HashSet;
+    use std::fmt::Write;
+
+    assert_eq!(
+        std::mem::size_of::<MiniString>(),
+        std::mem::size_of::<usize>()
+    );
+    assert_eq!(
+        std::mem::size_of::<Option<MiniString>>(),
+        std::mem::size_of::<usize>()
+    );
+
+    let mut s = MiniString::new();
+    s.push_str("héllo");
+    s.push(' ');
+    s.push('🌍');
+    assert_eq!(s, "héllo 🌍");
+    assert_eq!(s.len(), 11);
+    assert_eq!(s.chars().count(), 7);
+    assert!(s.starts_with("hé"));
+
+    write!(s, " {}", 42).unwrap();
+    assert_eq!(s.as_str(), "héllo 🌍 42");
+
+    assert_eq!(s.pop(), Some('2'));
+    assert_eq!(s.pop(), Some('4'));
+    assert_eq!(s.pop(), Some(' '));
+    assert_eq!(s.pop(), Some('🌍'));
+
+    s.insert(0, '¡');
+    s.insert_str(s.len(), "!!");
+    assert_eq!(s, "¡héllo !!");
+    assert_eq!(s.remove(0), '¡');
+
+    let drained: MiniString = s.drain(1..3).collect();
+    assert_eq!(drained, "é");
+    assert_eq!(s, "hllo !!");
+
+    {
+        let mut drain = s.drain(4..);
+        assert_eq!(drain.next_back(), Some('!'));
+        assert_eq!(drain.as_str(), " !");
+    }
+    assert_eq!(s, "hllo");
+
+    s.retain(|c| c != 'l');
+    assert_eq!(s, "ho");
+
+    let tail = s.split_off(1);
+    assert_eq!((s.as_str(), tail.as_str()), ("h", "o"));
+
+    let s: MiniString = "a-b-c".split('-').collect();
+    assert_eq!(s, "abc");
+    let s: MiniString = ['x', 'y'].iter().collect();
+    assert_eq!(s, "xy");
+    let s = s + "z";
+    assert_eq!(s, String::from("xyz"));
+    assert_eq!(String::from("xyz"), s);
+    assert_eq!("xyz", s);
+    assert!(s < "xz");
+    assert_eq!(format!("{s} {s:?}"), "xyz \"xyz\"");
+
+    let mut set = HashSet::new();
+    set.insert(MiniString::from("key"));
+    assert!(set.contains("key"));
+
+    let s = mini_format!("{}-{:03}", "id", 7);
+    assert_eq!(s, "id-007");
+
+    let bytes = s.clone().into_bytes();
+    assert_eq!(bytes, b"id-007");
+    let s = MiniString::from_utf8(bytes).unwrap();
+    assert_eq!(String::from(s), "id-007");
+
+    let err = MiniString::from_utf8(mini_vec![b'a', 0xff]).unwrap_err();
+    assert_eq!(err.utf8_error().valid_up_to(), 1);
+    assert_eq!(err.as_bytes(), [b'a', 0xff]);
+    assert!(!err.to_string().is_empty());
+    assert_eq!(MiniString::from_utf8_lossy(b"a\xffb"), "a\u{FFFD}b");
+
+    let s: MiniString = "parse".parse().unwrap();
+    assert_eq!(s, "parse");
+    assert_eq!(MiniString::from(String::new()), "");
+    let empty: MiniString = Default::default();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn minivec_frozen() {
+    use minivec::{MiniBox, MiniString};
+
+    assert_eq!(
+        std::mem::size_of::<Option<MiniBox<[i32]>>>(),
+        std::mem::size_of::<usize>()
+    );
+    assert_eq!(
+        std::mem::size_of::<MiniBox<str>>(),
+        std::mem::size_of::<usize>()
+    );
+
+    let mut vec: MiniVec<String> = MiniVec::with_capacity(64);
+    vec.push(String::from("a"));
+    vec.push(String::from("b"));
+
+    let mut frozen = vec.into_frozen();
+    assert_eq!(frozen.len(), 2);
+    assert_eq!(frozen[..], ["a", "b"]);
+    frozen[0].push('!');
+
+    let copy = frozen.clone();
+    assert_eq!(copy, frozen);
+    assert_eq!(format!("{copy:?}"), r#"["a!", "b"]"#);
+
+    let mut vec = frozen.into_vec();
+    assert_eq!(vec.capacity(), 2);
+    vec.push(String::from("c"));
+    assert_eq!(vec.as_slice(), ["a!", "b", "c"]);
+
+    // over-aligned element types keep their alignment in both directions
+    //
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(align(64))]
+    struct Aligned(u8);
+
+    let frozen: MiniBox<[Aligned]> = (0..5).map(Aligned).collect();
+    assert_eq!(frozen.as_ptr() as usize % 64, 0);
+    assert_eq!(frozen[4], Aligned(4));
+    let vec = frozen.into_vec();
+    assert_eq!(vec.as_ptr() as usize % 64, 0);
+    assert_eq!(vec.len(), 5);
+
+    // over-aligned vectors are copied into a default-aligned box
+    //
+    let mut vec = MiniVec::<u16>::with_alignment(8, 256).unwrap();
+    vec.extend_from_slice(&[1, 2, 3]);
+    let frozen = vec.into_frozen();
+    assert_eq!(frozen, [1, 2, 3]);
+    assert!(frozen < [1, 2, 4]);
+
+    let empty: MiniBox<[u8]> = MiniVec::with_capacity(10).into_frozen();
+    assert!(empty.is_empty());
+    assert!(empty.into_vec().is_empty());
+    assert!(MiniBox::<[i32]>::default().is_empty());
+
+    let zst = mini_vec![(); 10].into_frozen();
+    assert_eq!(zst.len(), 10);
+    assert_eq!(zst.into_vec().len(), 10);
+
+    let s = MiniString::from("héllo").into_frozen();
+    assert_eq!(s, "héllo");
+    assert_eq!(s.to_string(), "héllo");
+    assert_eq!(MiniBox::<str>::from("héllo"), s);
+
+    let mut set = std::collections::HashSet::new();
+    set.insert(s.clone());
+    assert!(set.contains("héllo"));
+
+    let mut s = s.into_string();
+    s.push_str(" world");
+    assert_eq!(s, "héllo world");
+}
+
+#[test]
+fn minivec_shared() {
+    use minivec::{MiniArc, MiniRc};
+
+    assert_eq!(
+        std::mem::size_of::<Option<MiniArc<[String]>>>(),
+        std::mem::size_of::<usize>()
+    );
+    assert_eq!(
+        std::mem::size_of::<MiniRc<[u8]>>(),
+        std::mem::size_of::<usize>()
+    );
+
+    // a sufficiently aligned vector is adopted and handed back without copying
+    //
+    let mut vec = MiniVec::<u64>::with_alignment(16, 32).unwrap();
+    vec.extend_from_slice(&[1, 2, 3]);
+    let ptr = vec.as_ptr();
+
+    let shared = MiniArc::from(vec);
+    assert_eq!(shared.as_ptr(), ptr);
+    let vec = MiniArc::try_unwrap(shared).unwrap();
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), 16);
+    assert_eq!(vec.alignment(), 32);
+
+    let mut shared = MiniArc::from(vec);
+    let other = shared.clone();
+    assert_eq!(MiniArc::strong_count(&shared), 2);
+    assert!(MiniArc::get_mut(&mut shared).is_none());
+
+    MiniArc::make_mut(&mut shared)[0] = 10;
+    assert_eq!(shared, [10, 2, 3]);
+    assert_eq!(other, [1, 2, 3]);
+    assert!(!MiniArc::ptr_eq(&shared, &other));
+    assert_eq!(shared.as_ptr() as usize % 32, 0);
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let other = other.clone();
+            std::thread::spawn(move || other.iter().sum::<u64>())
+        })
+        .collect();
+    for t in threads {
+        assert_eq!(t.join().unwrap(), 6);
+    }
+    assert_eq!(MiniArc::strong_count(&other), 1);
+
+    let vec = MiniArc::unwrap_or_clone(other);
+    assert_eq!(vec, [1, 2, 3]);
+
+    // default-aligned vectors and the non-atomic variant
+    //
+    let rc: MiniRc<[String]> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+    let rc2 = rc.clone();
+    assert_eq!(rc2.len(), 2);
+    assert_eq!(format!("{rc:?}"), r#"["a", "b"]"#);
+    let rc = MiniRc::try_unwrap(rc).unwrap_err();
+    drop(rc2);
+    let mut vec = MiniRc::try_unwrap(rc).unwrap();
+    vec.push(String::from("c"));
+    assert_eq!(vec.len(), 3);
+
+    // default-aligned vectors keep their allocation and alignment as well, making room for the
+    // reference count in place
+    //
+    let vec = mini_vec![1_u32, 2, 3];
+    let alignment = vec.alignment();
+    let shared = MiniArc::from(vec);
+    let vec = MiniArc::try_unwrap(shared).unwrap();
+    assert_eq!(vec, [1, 2, 3]);
+    assert_eq!(vec.alignment(), alignment);
+
+    let mut buf = [std::mem::MaybeUninit::<u8>::uninit(); 128];
+    let mut vec = MiniVec::<u32, _>::from_buffer(&mut buf);
+    vec.extend_from_slice(&[1, 2, 3]);
+    vec.shrink_to(4);
+    let shared = MiniRc::from(vec);
+    assert_eq!(shared, [1, 2, 3]);
+    let mut vec = MiniRc::try_unwrap(shared).unwrap();
+    assert_eq!(vec, [1, 2, 3]);
+    assert_eq!(vec.capacity(), 4);
+    vec.push(4);
+    assert_eq!(vec, [1, 2, 3, 4]);
+
+    let empty = MiniRc::<[i32]>::default();
+    assert!(empty.is_empty());
+    assert!(MiniRc::try_unwrap(empty).unwrap().is_empty());
+
+    // zero-sized elements are only dropped once no matter how many references there are
+    //
+    thread_local!(static DROPS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) });
+
+    #[derive(Clone)]
+    struct Marker;
+
+    impl Drop for Marker {
+        fn drop(&mut self) {
+            DROPS.with(|d| d.set(d.get() + 1));
+        }
+    }
+
+    let rc = MiniRc::from(mini_vec![Marker; 3]);
+    DROPS.with(|d| d.set(0));
+    let rc2 = rc.clone();
+    drop(rc);
+    assert_eq!(DROPS.with(std::cell::Cell::get), 0);
+    assert_eq!(rc2.len(), 3);
+    drop(rc2);
+    assert_eq!(DROPS.with(std::cell::Cell::get), 3);
+}
+
+#[test]
+fn minivec_compact_header() {
+    use minivec::{MiniVec32, TryReserveError};
+    use std::convert::TryFrom;
+
+    #[derive(Clone, Default)]
+    struct SizeRecorder(std::rc::Rc<std::cell::Cell<usize>>);
+
+    unsafe impl minivec::Allocator for SizeRecorder {
+        fn allocate(
+            &self,
+            layout: std::alloc::Layout,
+        ) -> Result<std::ptr::NonNull<[u8]>, minivec::AllocError> {
+            self.0.set(layout.size());
+            minivec::Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: std::ptr::NonNull<u8>, layout: std::alloc::Layout) {
+            minivec::Global.deallocate(ptr, layout);
+        }
+    }
+
+    let alloc = SizeRecorder::default();
+    let mut v = MiniVec32::<u32, _>::with_capacity_in(2, alloc.clone());
+    assert_eq!(alloc.0.get(), 8 + 2 * 4);
+    v.push(1);
+    v.push(2);
+    v.insert(0, 0);
+    assert_eq!(v, [0, 1, 2]);
+    assert!(v.capacity() >= 3);
+
+    let mut v: MiniVec32<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(v.remove(1), "b");
+    assert_eq!(v.swap_remove(0), "a");
+    assert_eq!(v.as_slice(), ["d", "c"]);
+    v.retain(|s| s == "c");
+    assert_eq!(v.pop().as_deref(), Some("c"));
+    assert!(v.is_empty());
+
+    let v: MiniVec32<i32> = (0..100).collect();
+    let w = v.clone();
+    assert_eq!(v, w);
+    assert_eq!(v.iter().sum::<i32>(), 4950);
+    assert_eq!(v.clone().into_iter().next_back(), Some(99));
+
+    let vec: MiniVec<i32> = MiniVec::from(v);
+    assert_eq!(vec.len(), 100);
+    let v = MiniVec32::try_from(vec).unwrap();
+    assert_eq!(v[99], 99);
+
+    let mut v = MiniVec32::<u8>::new();
+    assert_eq!(
+        v.try_reserve(u32::MAX as usize + 1),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(
+        v.try_reserve_exact(usize::MAX),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(v.capacity(), 0);
+
+    let mut z = MiniVec32::<()>::new();
+    assert_eq!(z.capacity(), u32::MAX as usize);
+    unsafe { z.set_len(u32::MAX as usize) };
+    assert_eq!(z.try_push(()), Err(TryReserveError::CapacityOverflow));
+    unsafe { z.set_len(0) };
+
+    let result = std::panic::catch_unwind(|| {
+        let mut v = MiniVec32::<u8>::new();
+        v.reserve(u32::MAX as usize + 1);
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn minivec_small_inline() {
+    use minivec::MiniSmallVec;
+
+    let word = std::mem::size_of::<usize>();
+    assert_eq!(std::mem::size_of::<MiniSmallVec<u8>>(), word);
+    assert_eq!(MiniSmallVec::<u8>::inline_capacity(), word - 1);
+    assert_eq!(MiniSmallVec::<u16>::inline_capacity(), (word - 2) / 2);
+    assert_eq!(MiniSmallVec::<u32>::inline_capacity(), (word - 4) / 4);
+    assert_eq!(MiniSmallVec::<u64>::inline_capacity(), 0);
+    assert_eq!(MiniSmallVec::<String>::inline_capacity(), 0);
+
+    let mut v = MiniSmallVec::<u8>::new();
+    for i in 0..word - 1 {
+        v.push(i as u8);
+        assert!(!v.spilled());
+    }
+    v.insert(0, 42);
+    assert!(v.spilled());
+    assert_eq!(v.remove(0), 42);
+    v.shrink_to_fit();
+    assert!(!v.spilled());
+    assert_eq!(v.len(), word - 1);
+    assert_eq!(v.pop(), Some(word as u8 - 2));
+
+    let tail = v.split_off(1);
+    assert_eq!(v, [0]);
+    assert_eq!(tail.len(), word - 3);
+    assert!(!tail.spilled());
+
+    let vec: MiniVec<u8> = MiniSmallVec::from(&b"abc"[..]).into();
+    assert_eq!(vec, b"abc");
+    let v = MiniSmallVec::from(vec);
+    assert!(v.spilled());
+    assert_eq!(v.into_iter().rev().collect::<Vec<_>>(), b"cba");
+
+    // elements that need dropping are dropped exactly once whether inline or spilled
+    //
+    let rc = std::rc::Rc::new(());
+    let mut v: MiniSmallVec<std::rc::Rc<()>> = (0..8).map(|_| rc.clone()).collect();
+    assert_eq!(std::rc::Rc::strong_count(&rc), 9);
+    v.retain(|_| false);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+    v.push(rc.clone());
+    drop(v);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+
+    let mut v: MiniSmallVec<()> = MiniSmallVec::new();
+    v.resize(100, ());
+    assert_eq!(v.len(), 100);
+    assert_eq!(v.drain(..50).count(), 50);
+    assert_eq!(v.len(), 50);
+
+    let mut v = MiniSmallVec::<u16>::new();
+    v.extend([1_u16, 1, 2].iter());
+    v.dedup();
+    assert_eq!(v, [1, 2]);
+    assert_eq!(v.clone(), v);
+    assert_eq!(format!("{:?}", v), "[1, 2]");
+}
+
+#[test]
+fn minivec_small_vec_api() {
+    use minivec::{LayoutErr, MiniSmallVec, TryReserveError};
+
+    let mut v = MiniSmallVec::from(&[1_u8, 2, 3][..]);
+    v.extend_from_within(1..);
+    assert!(!v.spilled());
+    assert_eq!(v, [1, 2, 3, 2, 3]);
+    v.extend_from_within(..);
+    assert!(v.spilled());
+    assert_eq!(v, [1, 2, 3, 2, 3, 1, 2, 3, 2, 3]);
+
+    let mut v = MiniSmallVec::from(&[1_u8, 2, 3, 4][..]);
+    assert_eq!(v.splice(1..2, [9, 9]).collect::<Vec<_>>(), [2]);
+    assert_eq!(v, [1, 9, 9, 3, 4]);
+    assert_eq!(v.drain_filter(|x| *x == 9).count(), 2);
+    assert_eq!(v, [1, 3, 4]);
+
+    // a panicking clone keeps the elements cloned before it, inline and spilled alike
+    //
+    #[derive(Debug, PartialEq)]
+    struct Bomb(u8);
+
+    impl Clone for Bomb {
+        fn clone(&self) -> Self {
+            assert!(self.0 != 0);
+            Bomb(self.0)
+        }
+    }
+
+    for &extra in &[0_usize, 64] {
+        let mut v = MiniSmallVec::from(&[Bomb(1)][..]);
+        v.reserve(extra);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            v.extend_from_slice(&[Bomb(2), Bomb(0), Bomb(3)]);
+        }));
+        assert!(result.is_err());
+        assert_eq!(v, [Bomb(1), Bomb(2)]);
+
+        v.extend_from_within(..);
+        assert_eq!(v, [Bomb(1), Bomb(2), Bomb(1), Bomb(2)]);
+    }
+
+    // shrinking moves the elements back inline only when both they and the capacity fit
+    //
+    let mut v = MiniSmallVec::<u8>::try_with_capacity(100).unwrap();
+    v.extend_from_slice(&[1, 2]);
+    v.shrink_to(50);
+    assert!(v.spilled());
+    assert_eq!(v.capacity(), 50);
+    v.shrink_to(2);
+    assert!(!v.spilled());
+    assert_eq!(v, [1, 2]);
+    assert!(!MiniSmallVec::<u8>::try_with_capacity(1).unwrap().spilled());
+    assert!(MiniSmallVec::<u8>::try_with_capacity(usize::MAX).is_err());
+
+    let result = std::panic::catch_unwind(|| {
+        let mut v = MiniSmallVec::<u8>::new();
+        v.shrink_to(100);
+    });
+    assert!(result.is_err());
+
+    // alignment
+    //
+    let mut v = MiniSmallVec::from(&[1_u8, 2][..]);
+    assert_eq!(v.alignment(), MiniVec::<u8>::new().alignment());
+    assert_eq!(
+        v.try_realign(24),
+        Err(TryReserveError::InvalidAlignment(
+            LayoutErr::AlignmentNotPowerOfTwo
+        ))
+    );
+    assert!(!v.spilled());
+    v.realign(128).unwrap();
+    assert!(v.spilled());
+    assert_eq!(v.alignment(), 128);
+    assert_eq!(v.as_ptr() as usize % 128, 0);
+    assert_eq!(v, [1, 2]);
+
+    let v = MiniSmallVec::<u16>::with_alignment(0, 64).unwrap();
+    assert_eq!(v.alignment(), 64);
+    assert!(MiniSmallVec::<u16>::try_with_alignment(0, 1).is_err());
+
+    // raw parts and leaking always go through the heap
+    //
+    let (ptr, len, cap) = MiniSmallVec::from(&[5_u8][..]).into_raw_parts();
+    let vec = unsafe { MiniVec::from_raw_parts(ptr, len, cap) };
+    assert_eq!(vec, [5]);
+
+    let leaked = MiniSmallVec::leak(MiniSmallVec::from(&[6_u8, 7][..]));
+    assert_eq!(leaked, [6, 7]);
+    drop(unsafe { MiniVec::from_raw_parts(leaked.as_mut_ptr(), 2, 2) });
+}
+
+#[test]
+fn minivec_header_ext() {
+    use minivec::Global;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Shape {
+        rows: usize,
+        cols: usize,
+    }
+
+    let mut v = MiniVec::<u8, Global, Shape>::default();
+    assert_eq!(v.ext(), None);
+    assert_eq!(v.capacity(), 0);
+
+    assert_eq!(v.set_ext(Shape { rows: 1, cols: 3 }), None);
+    assert_eq!(v.capacity(), 0);
+    assert_eq!(v.ext(), Some(&Shape { rows: 1, cols: 3 }));
+
+    // the extension survives reallocation
+    //
+    for i in 0..100 {
+        v.push(i);
+    }
+    v.shrink_to_fit();
+    assert_eq!(v.ext(), Some(&Shape { rows: 1, cols: 3 }));
+    assert_eq!(v.len(), 100);
+    assert_eq!(v[99], 99);
+
+    v.ext_mut().unwrap().rows = 2;
+
+    let w = v.clone();
+    assert_eq!(w.ext(), v.ext());
+    assert_eq!(w, v);
+
+    let tail = v.split_off(50);
+    assert_eq!(tail.ext(), Some(&Shape { rows: 2, cols: 3 }));
+    assert_eq!(tail[0], 50);
+
+    v.realign(64).unwrap();
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+    assert_eq!(v.ext(), Some(&Shape { rows: 2, cols: 3 }));
+    assert_eq!(v.iter().map(|&x| x as usize).sum::<usize>(), 49 * 50 / 2);
+
+    v.extend(50..200);
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+    assert_eq!(v.ext(), Some(&Shape { rows: 2, cols: 3 }));
+    v.truncate(50);
+
+    assert_eq!(v.take_ext(), Some(Shape { rows: 2, cols: 3 }));
+    assert_eq!(v.ext(), None);
+
+    // over-aligned extensions and extensions that need dropping
+    //
+    #[derive(Clone)]
+    #[repr(align(32))]
+    struct Aligned(#[allow(dead_code)] std::rc::Rc<()>);
+
+    let rc = std::rc::Rc::new(());
+    let mut v = MiniVec::<u16, Global, Aligned>::with_ext(Aligned(rc.clone()));
+    assert_eq!(v.alignment(), 32);
+    v.extend_from_slice(&[1, 2, 3]);
+    let w = v.clone();
+    assert_eq!(std::rc::Rc::strong_count(&rc), 3);
+    assert_eq!(w.into_iter().sum::<u16>(), 6);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 2);
+    assert_eq!(v.drain(..).count(), 3);
+    drop(v);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+
+    let result = std::panic::catch_unwind(|| {
+        let _ = MiniVec::<(), Global, u32>::with_ext(1);
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn minivec_tagged() {
+    use minivec::TaggedMiniVec;
+
+    let mut vec = TaggedMiniVec::<String, 2>::new();
+    assert_eq!(vec.tag(), 0);
+
+    vec.set_tag(3);
+    assert!(vec.is_empty());
+    assert_eq!(vec.tag(), 3);
+
+    for i in 0..64 {
+        vec.push(i.to_string());
+        assert_eq!(vec.tag(), 3);
+    }
+
+    assert_eq!(vec.len(), 64);
+    assert_eq!(vec[63], "63");
+
+    vec.set_tag(1);
+    assert_eq!(vec.pop().as_deref(), Some("63"));
+    vec.as_vec_mut().truncate(2);
+    assert_eq!(vec, ["0".to_string(), "1".to_string()]);
+
+    let copy = vec.clone();
+    assert_eq!(copy.tag(), 1);
+    assert_eq!(copy, vec);
+
+    let inner = MiniVec::from(copy);
+    assert_eq!(inner.len(), 2);
+    assert_eq!(inner[1], "1");
+
+    let mut zst = TaggedMiniVec::<(), 3>::with_tag(mini_vec![(); 5], 7);
+    zst.push(());
+    assert_eq!(zst.len(), 6);
+    assert_eq!(zst.tag(), 7);
+    assert_eq!(zst.into_inner().len(), 6);
+
+    // leaking a view after it reallocated leaves an empty vector behind, never a dangling one
+    //
+    let mut vec = TaggedMiniVec::<u64, 2>::with_tag(mini_vec![1, 2, 3], 2);
+    let mut view = vec.as_vec_mut();
+    view.reserve(100_000);
+    core::mem::forget(view);
+
+    assert!(vec.is_empty());
+    assert_eq!(vec.tag(), 2);
+    vec.push(4);
+    assert_eq!(vec, [4]);
+
+    let mut zst = TaggedMiniVec::<(), 3>::with_tag(mini_vec![(); 5], 5);
+    core::mem::forget(zst.as_vec_mut());
+    assert!(zst.is_empty());
+    assert_eq!(zst.tag(), 5);
+}
+
+#[test]
+fn minivec_atomic() {
+    use minivec::AtomicMiniVec;
+    use std::sync::Arc;
+
+    let slot = Arc::new(AtomicMiniVec::<String>::default());
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let slot = slot.clone();
+            std::thread::spawn(move || {
+                let mut taken = 0;
+                for j in 0..100 {
+                    let old = slot.swap(mini_vec![i.to_string(); j % 4 + 1]);
+                    taken += old.len();
+
+                    if j % 10 == 0 {
+                        taken += slot.load_take().len();
+                    }
+                }
+                taken
+            })
+        })
+        .collect();
+
+    let taken: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    let rest = Arc::try_unwrap(slot).unwrap().into_inner();
+
+    assert_eq!(taken + rest.len(), 8 * (25 * (1 + 2 + 3 + 4)));
+    assert!(rest.iter().all(|s| s == &rest[0]));
+
+    let slot = AtomicMiniVec::new(mini_vec![1, 2, 3]);
+    let seen = slot.load();
+    assert_eq!(seen, slot.load());
+
+    slot.store(mini_vec![5]);
+    let new = slot.compare_exchange(seen, mini_vec![4]).unwrap_err();
+    assert_eq!(new, [4]);
+
+    slot.store(MiniVec::new());
+    let empty = slot.load();
+    assert!(slot.compare_exchange(empty, new).is_ok());
+    assert_eq!(slot.compare_exchange(empty, mini_vec![6]).unwrap_err(), [6]);
+    assert_eq!(slot.load_take(), [4]);
+
+    // racing updates built from the same token only ever let one of them through
+    //
+    let slot = AtomicMiniVec::new(mini_vec![0_usize]);
+    let seen = slot.load();
+    let published: usize = std::thread::scope(|s| {
+        let handles: Vec<_> = (1..=8)
+            .map(|i| {
+                let slot = &slot;
+                s.spawn(move || usize::from(slot.compare_exchange(seen, mini_vec![i]).is_ok()))
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    });
+    assert_eq!(published, 1);
+    assert_ne!(slot.into_inner(), [0]);
+}
+
+#[test]
+fn minivec_concurrent() {
+    use minivec::ConcurrentMiniVec;
+
+    let mut vec = MiniVec::with_capacity(1000);
+    vec.push(String::from("init"));
+
+    let vec = vec.into_concurrent();
+    assert_eq!(vec.len(), 1);
+
+    let pushed = std::thread::scope(|s| {
+        let reader = s.spawn(|| {
+            let mut last = 0;
+            while last < 1000 {
+                let prefix: &[String] = &vec;
+                assert!(prefix.len() >= last);
+                assert_eq!(prefix[0], "init");
+                assert!(prefix.iter().all(|s| !s.is_empty()));
+                last = prefix.len();
+            }
+        });
+
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let vec = &vec;
+                s.spawn(move || {
+                    let mut pushed = 0;
+                    while vec.push_within_capacity(i.to_string()).is_ok() {
+                        pushed += 1;
+                    }
+                    pushed
+                })
+            })
+            .collect();
+
+        let pushed: usize = writers.into_iter().map(|h| h.join().unwrap()).sum();
+        reader.join().unwrap();
+        pushed
+    });
+
+    assert_eq!(pushed, 999);
+    assert_eq!(vec.len(), 1000);
+
+    let vec: MiniVec<String> = vec.into_inner();
+    assert_eq!(vec.len(), 1000);
+
+    let zst = ConcurrentMiniVec::<()>::default();
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..100 {
+                    zst.push_within_capacity(()).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(zst.len(), 400);
+
+    let empty = ConcurrentMiniVec::<i32>::default();
+    assert_eq!(empty.push_within_capacity(1), Err(1));
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn minivec_fixed_buffer() {
+    use core::mem::MaybeUninit;
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); 256];
+    let mut vec = MiniVec::<String, _>::from_buffer(&mut buf);
+
+    let capacity = vec.capacity();
+    assert!(capacity > 0);
+
+    for i in 0..capacity {
+        vec.try_push(i.to_string()).unwrap();
+    }
+    assert!(vec.try_push(String::from("overflow")).is_err());
+    assert!(vec.try_reserve(1).is_err());
+    assert_eq!(vec.capacity(), capacity);
+
+    vec.retain(|s| s.parse::<usize>().unwrap() % 2 == 0);
+    let drained: Vec<_> = vec.drain(..1).collect();
+    assert_eq!(drained, ["0"]);
+
+    let removed: Vec<_> = vec
+        .splice(..1, ["a".to_string(), "a".to_string()])
+        .collect();
+    assert_eq!(removed, ["2"]);
+    vec.dedup();
+    assert_eq!(vec[0], "a");
+
+    let len = vec.len();
+    vec.shrink_to_fit();
+    assert_eq!(vec.capacity(), len);
+    vec.try_reserve_exact(capacity - len).unwrap();
+    assert_eq!(vec.capacity(), capacity);
+
+    // growth is clamped to the end of the buffer and running out of room is reported as a
+    // capacity overflow
+    //
+    #[repr(align(8))]
+    struct Aligned([MaybeUninit<u8>; 152]);
+
+    let mut buf = Aligned([MaybeUninit::uninit(); 152]);
+    let mut vec = MiniVec::<u64, _>::from_buffer(&mut buf.0);
+    assert_eq!(vec.capacity(), 15);
+
+    vec.shrink_to(10);
+    assert_eq!(vec.capacity(), 10);
+    vec.extend(0..10);
+    vec.push(10);
+    assert_eq!(vec.capacity(), 15);
+    vec.extend(11..15);
+
+    let overflow = Err(minivec::TryReserveError::CapacityOverflow);
+    assert_eq!(vec.try_push(15), overflow);
+    assert_eq!(vec.try_reserve(1), overflow);
+    assert_eq!(vec.try_reserve_exact(1), overflow);
+    assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| vec.push(15))).is_err());
+    assert_eq!(vec, (0..15).collect::<Vec<_>>());
+
+    vec.truncate(2);
+    vec.shrink_to_fit();
+    assert_eq!(vec.capacity(), 2);
+    vec.try_reserve(12).unwrap();
+    assert_eq!(vec.capacity(), 15);
+    drop(vec);
+
+    let mut tiny = [MaybeUninit::<u8>::uninit(); 4];
+    let mut vec = MiniVec::<u8, _>::from_buffer(&mut tiny);
+    assert_eq!(vec.capacity(), 0);
+    assert_eq!(
+        vec.try_push(1),
+        Err(minivec::TryReserveError::CapacityOverflow)
+    );
+    drop(vec);
+
+    let mut vec = MiniVec::<(), _>::from_buffer(&mut tiny);
+    vec.push(());
+    assert_eq!(vec.len(), 1);
+
+    // the buffer can't be swapped for another so realigning only works when the data is already
+    // suitably aligned
+    //
+    #[repr(align(64))]
+    struct Line([MaybeUninit<u8>; 256]);
+
+    let mut buf = Line([MaybeUninit::uninit(); 256]);
+    let mut vec = MiniVec::<u64, _>::from_buffer(&mut buf.0);
+    vec.extend_from_slice(&[1, 2]);
+    assert_ne!(vec.as_ptr() as usize % 64, 0);
+    assert!(matches!(
+        vec.try_realign(64),
+        Err(minivec::TryReserveError::AllocError { .. })
+    ));
+    assert_eq!(vec.alignment(), 8);
+    drop(vec);
+
+    let mut vec = MiniVec::<u64, _>::from_buffer(&mut buf.0[32..]);
+    vec.extend_from_slice(&[1, 2]);
+    let p = vec.as_ptr();
+    assert_eq!(p as usize % 64, 0);
+    vec.realign(64).unwrap();
+    assert_eq!(vec.alignment(), 64);
+    assert_eq!(vec.as_ptr(), p);
+    assert_eq!(vec, [1, 2]);
+}
+
+#[test]
+fn minivec_static() {
+    use minivec::static_mini_vec;
+    use std::convert::TryFrom;
+
+    static NUMS: MiniVec<u64> = static_mini_vec![1, 2, 3, 4];
+    static WORDS: MiniVec<&str> = static_mini_vec!["a", "b"];
+    static UNITS: MiniVec<()> = static_mini_vec![(), ()];
+    const EMPTY: MiniVec<String> = MiniVec::new();
+
+    std::thread_local! {
+        static LOCAL: std::cell::RefCell<MiniVec<u64>> = const { std::cell::RefCell::new(MiniVec::new()) };
+    }
+
+    assert_eq!(NUMS, [1, 2, 3, 4]);
+    assert_eq!(NUMS.capacity(), 4);
+    assert_eq!(NUMS.alignment(), std::mem::align_of::<usize>());
+    assert_eq!(WORDS.iter().copied().collect::<String>(), "ab");
+    assert_eq!(UNITS.len(), 2);
+    assert!(EMPTY.is_empty());
+
+    LOCAL.with(|v| v.borrow_mut().push(1));
+    LOCAL.with(|v| assert_eq!(*v.borrow(), [1]));
+
+    let mut v = NUMS.clone();
+    v.push(5);
+    assert_eq!(v, [1, 2, 3, 4, 5]);
+
+    let mut v = static_mini_vec![1u64, 2, 3, 4];
+    v[0] = 10;
+    assert_eq!(v, [10, 2, 3, 4]);
+    assert!(v.capacity() >= 4);
+
+    let mut v: MiniVec<u64> = static_mini_vec![1, 2, 3, 4];
+    assert_eq!(v.pop(), Some(4));
+    v.truncate(1);
+    assert_eq!(v, [1]);
+
+    let mut v: MiniVec<u64> = static_mini_vec![1, 2, 3, 4];
+    assert_eq!(v.drain(1..3).collect::<Vec<_>>(), [2, 3]);
+    assert_eq!(v, [1, 4]);
+
+    let mut v: MiniVec<u64> = static_mini_vec![1, 2, 3, 4];
+    assert_eq!(v.splice(1..2, [9, 9]).collect::<Vec<_>>(), [2]);
+    assert_eq!(v, [1, 9, 9, 3, 4]);
+
+    let mut v: MiniVec<u64> = static_mini_vec![1, 2, 3, 4];
+    assert_eq!(v.splice(1..2, [9]).collect::<Vec<_>>(), [2]);
+    assert_eq!(v, [1, 9, 3, 4]);
+
+    let mut v: MiniVec<u64> = static_mini_vec![1, 2, 3, 4];
+    let tail = v.split_off(2);
+    assert_eq!((v.as_slice(), tail.as_slice()), (&[1, 2][..], &[3, 4][..]));
+
+    let mut v: MiniVec<u64> = static_mini_vec![4, 3, 2, 1];
+    v.sort_unstable();
+    v.shrink_to_fit();
+    assert_eq!(v, [1, 2, 3, 4]);
+
+    let v: MiniVec<u64> = static_mini_vec![1, 2, 3];
+    assert_eq!(v.into_iter().sum::<u64>(), 6);
+
+    let frozen = NUMS.clone().into_frozen();
+    assert_eq!(frozen, [1, 2, 3, 4]);
+
+    let shared = minivec::MiniArc::from(static_mini_vec![1u64, 2]);
+    assert_eq!(minivec::MiniArc::try_unwrap(shared).unwrap(), [1, 2]);
+
+    let mut units = static_mini_vec![(), (), ()];
+    units.push(());
+    assert_eq!(units.len(), 4);
+
+    let mut v: MiniVec<u64> = static_mini_vec![1, 2];
+    v.push(3);
+    assert_eq!(v, [1, 2, 3]);
+
+    // moving the elements out leaves the static storage alone
+    //
+    assert_eq!(Vec::from(static_mini_vec![1u64, 2]), [1, 2]);
+    assert_eq!(
+        <[u64; 2]>::try_from(static_mini_vec![1u64, 2]).unwrap(),
+        [1, 2]
+    );
+    assert_eq!(
+        minivec::MiniVec32::try_from(static_mini_vec![1u64, 2]).unwrap(),
+        [1, 2]
+    );
+
+    let mut other: MiniVec<u64> = static_mini_vec![3, 4];
+    v.append(&mut other);
+    assert_eq!(v, [1, 2, 3, 3, 4]);
+    assert!(other.is_empty());
+
+    let mut s = minivec::MiniString::from_utf8(static_mini_vec![b'h', b'i']).unwrap();
+    assert_eq!(s.pop(), Some('i'));
+    assert_eq!(s, "h");
+
+    assert_eq!(NUMS, [1, 2, 3, 4]);
+}
+
+#[test]
+fn minivec_macro_align() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let v = mini_vec![align = 64; 1u8; 100];
+    assert_eq!(v.alignment(), 64);
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+    assert!(v.iter().all(|&x| x == 1));
+
+    let alignment = 1 << 7;
+    let v = mini_vec![align = alignment; 2u16, 3, 4];
+    assert_eq!(v.alignment(), 128);
+    assert_eq!(v, [2, 3, 4]);
+
+    let v: MiniVec<u32> = mini_vec![align = 16;];
+    assert!(v.is_empty());
+
+    let evaluated = Cell::new(0);
+    let make = || {
+        evaluated.set(evaluated.get() + 1);
+        Rc::new(5)
+    };
+
+    let v = mini_vec![make(); 4];
+    assert_eq!(evaluated.get(), 1);
+    assert_eq!(Rc::strong_count(&v[0]), 4);
+
+    let v = mini_vec![align = 32; make(); 0];
+    assert!(v.is_empty());
+    assert_eq!(evaluated.get(), 2);
+
+    let result = std::panic::catch_unwind(|| mini_vec![align = alignment * 3; 0u8; 1]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn minivec_capacity_overflow() {
+    use minivec::TryReserveError;
+
+    let max = MiniVec::<u32>::max_capacity();
+    let header = 3 * std::mem::size_of::<usize>();
+    assert_eq!(
+        max,
+        ((isize::MAX as usize - header) & !(header / 3 - 1)) / 4
+    );
+
+    let mut vec: MiniVec<u32> = mini_vec![1, 2, 3];
+    assert_eq!(
+        vec.try_reserve(usize::MAX),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(
+        vec.try_reserve_exact(usize::MAX),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(vec.try_reserve(max), Err(TryReserveError::CapacityOverflow));
+    assert_eq!(vec, [1, 2, 3]);
+
+    let err = std::panic::catch_unwind(|| MiniVec::<u32>::new().reserve(usize::MAX)).unwrap_err();
+    assert_eq!(err.downcast_ref::<&str>(), Some(&"capacity overflow"));
+
+    let err = std::panic::catch_unwind(|| MiniVec::<u32>::with_capacity(max + 1)).unwrap_err();
+    assert_eq!(err.downcast_ref::<&str>(), Some(&"capacity overflow"));
+
+    let err =
+        std::panic::catch_unwind(|| MiniVec::<u64>::with_capacity(usize::MAX / 4)).unwrap_err();
+    assert_eq!(err.downcast_ref::<&str>(), Some(&"capacity overflow"));
+
+    let err = std::panic::catch_unwind(|| {
+        let mut vec = MiniVec::<u16>::new();
+        vec.resize(isize::MAX as usize / 2 + 1, 0);
+    })
+    .unwrap_err();
+    assert_eq!(err.downcast_ref::<&str>(), Some(&"capacity overflow"));
+
+    let mut units = MiniVec::<()>::new();
+    assert_eq!(MiniVec::<()>::max_capacity(), usize::MAX - 1);
+    assert!(units.try_reserve(usize::MAX).is_ok());
+}
+
+#[test]
+fn minivec_layout_err() {
+    use minivec::LayoutErr;
+
+    // an alignment that isn't a power of two is reported as such, however small it is
+    //
+    for alignment in [0, 3, 6, 12, 24, 48, 96, 100] {
+        assert_eq!(
+            MiniVec::<u8>::with_alignment(16, alignment),
+            Err(LayoutErr::AlignmentNotPowerOfTwo)
+        );
+    }
+
+    assert_eq!(
+        MiniVec::<u8>::with_alignment(16, 4),
+        Err(LayoutErr::AlignmentTooSmall)
+    );
+    assert_eq!(
+        MiniVec::<u64>::with_alignment(1, 1 << (usize::BITS - 1)),
+        Err(LayoutErr::AlignmentTooLarge)
+    );
+    assert_eq!(
+        MiniVec::<u64>::with_alignment(usize::MAX / 16, 64),
+        Err(LayoutErr::SizeOverflow)
+    );
+
+    let mut vec: MiniVec<u64> = mini_vec![1, 2, 3];
+    assert_eq!(vec.realign(24), Err(LayoutErr::AlignmentNotPowerOfTwo));
+    assert_eq!(
+        vec.clone_with_alignment(1 << (usize::BITS - 1)),
+        Err(LayoutErr::AlignmentTooLarge)
+    );
+    assert_eq!(vec, [1, 2, 3]);
+
+    let err = LayoutErr::SizeOverflow;
+    assert_eq!(err.clone(), err);
+    assert_eq!(
+        LayoutErr::AlignmentNotPowerOfTwo.to_string(),
+        "alignment is not a power of two"
+    );
+    assert_eq!(
+        minivec::TryReserveError::InvalidAlignment(LayoutErr::AlignmentTooSmall).to_string(),
+        "invalid alignment: alignment is smaller than the minimum required alignment"
+    );
+}
+
+#[test]
+fn minivec_bulk_copy() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let bytes: Vec<u8> = (0..=255).cycle().take(4096).collect();
+
+    let mut v = MiniVec::<u8>::new();
+    v.extend_from_slice(&bytes[..100]);
+    v.extend(&bytes[100..]);
+    assert_eq!(v, bytes.as_slice());
+    assert_eq!(MiniVec::from(bytes.as_slice()), v);
+    assert_eq!(v.clone(), v);
+    assert_eq!(mini_vec![7u8; 300], [7u8; 300]);
+
+    let mut v = mini_vec![1, 2, 3];
+    v.extend([4, 5].iter().chain(&[6]).filter(|&&x| x != 5));
+    assert_eq!(v, [1, 2, 3, 4, 6]);
+
+    // clone_from reuses the allocation it already has
+    //
+    let mut dst: MiniVec<String> = (0..64).map(|x| x.to_string()).collect();
+    let ptr = dst.as_ptr();
+    let src: MiniVec<String> = (0..8).map(|x| (x * 2).to_string()).collect();
+    dst.clone_from(&src);
+    assert_eq!(dst, src);
+    assert_eq!(dst.as_ptr(), ptr);
+
+    let src: MiniVec<String> = (0..32).map(|x| x.to_string()).collect();
+    dst.clone_from(&src);
+    assert_eq!(dst, src);
+    assert_eq!(dst.as_ptr(), ptr);
+
+    let aligned = MiniVec::<u8>::with_alignment(4, 256).unwrap();
+    let mut dst = mini_vec![1u8, 2, 3];
+    dst.clone_from(&aligned);
+    assert!(dst.is_empty());
+    assert_eq!(dst.alignment(), 256);
+
+    // a panicking clone must still drop the elements cloned so far
+    //
+    struct Bomb(Rc<Cell<usize>>, bool);
+
+    impl Clone for Bomb {
+        fn clone(&self) -> Self {
+            assert!(!self.1, "boom");
+            self.0.set(self.0.get() + 1);
+            Bomb(self.0.clone(), false)
+        }
+    }
+
+    impl Drop for Bomb {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() - 1);
+        }
+    }
+
+    let live = Rc::new(Cell::new(0));
+    let src: MiniVec<Bomb> = (0..8)
+        .map(|x| {
+            live.set(live.get() + 1);
+            Bomb(live.clone(), x == 5)
+        })
+        .collect();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| src.clone()));
+    assert!(result.is_err());
+    assert_eq!(live.get(), 8);
+
+    drop(src);
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn minivec_extend_size_hint() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    // an exact-size iterator is collected into a single allocation
+    //
+    let v: MiniVec<u64> = (0..1000).collect();
+    assert_eq!(v.capacity(), 1000);
+    assert!(v.iter().copied().eq(0..1000));
+
+    // exact size hints, like those of `TrustedLen` iterators, are written in a single batch
+    //
+    let alloc = CountingAllocator::default();
+    let mut v = MiniVec::<u64, _>::new_in(alloc.clone());
+    v.extend((0..500).chain(500..1000));
+    v.extend([1000, 1001].iter());
+    assert!(v.iter().copied().eq(0..1002));
+    assert_eq!(alloc.total.get(), 1);
+
+    let mut v = mini_vec![0_u64; 3];
+    v.extend(3..100);
+    assert!(v.capacity() >= 100);
+    assert!(v.iter().copied().eq((0..3).map(|_| 0).chain(3..100)));
+
+    struct Liar<I> {
+        iter: I,
+        hint: (usize, Option<usize>),
+    }
+
+    impl<I: Iterator> Iterator for Liar<I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<I::Item> {
+            self.iter.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.hint
+        }
+    }
+
+    let v: MiniVec<String> = Liar {
+        iter: (0..100).map(|x| x.to_string()),
+        hint: (4, Some(4)),
+    }
+    .collect();
+    assert_eq!(v.len(), 100);
+    assert_eq!(v[99], "99");
+
+    let v: MiniVec<String> = Liar {
+        iter: (0..4).map(|x| x.to_string()),
+        hint: (100, Some(100)),
+    }
+    .collect();
+    assert!(v.iter().map(String::as_str).eq(["0", "1", "2", "3"]));
+    assert_eq!(v.capacity(), 100);
+
+    // batches are split where the data reaches a cache line boundary, running out on either side
+    // of it leaves every element in place
+    //
+    for n in 0..80_u8 {
+        let v: MiniVec<u8> = Liar {
+            iter: 0..n,
+            hint: (80, Some(80)),
+        }
+        .collect();
+        assert!(v.iter().copied().eq(0..n));
+        assert_eq!(mini_vec![7_u8; usize::from(n)], vec![7; usize::from(n)]);
+    }
+
+    let v: MiniVec<()> = Liar {
+        iter: std::iter::repeat_n((), 10),
+        hint: (0, None),
+    }
+    .collect();
+    assert_eq!(v.len(), 10);
+
+    // elements written before the iterator panics are still dropped
+    //
+    struct Counted(Rc<Cell<usize>>);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() - 1);
+        }
+    }
+
+    let live = Rc::new(Cell::new(0));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        (0..10)
+            .map(|x| {
+                assert!(x != 6, "boom");
+                live.set(live.get() + 1);
+                Counted(live.clone())
+            })
+            .collect::<MiniVec<_>>()
+    }));
+    assert!(result.is_err());
+    assert_eq!(live.get(), 0);
+
+    let mut v: MiniVec<Counted> = MiniVec::new();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        v.extend((0..10).map(|x| {
+            assert!(x != 6, "boom");
+            live.set(live.get() + 1);
+            Counted(live.clone())
+        }));
+    }));
+    assert!(result.is_err());
+    assert_eq!(v.len(), 6);
+    assert_eq!(live.get(), 6);
+
+    drop(v);
+    assert_eq!(live.get(), 0);
+}
@@ -0,0 +1,76 @@
+#![cfg(not(feature = "alloc"))]
+
+extern crate minivec;
+
+use core::mem::MaybeUninit;
+use minivec::{FixedStorage, MiniVec, TryReserveError};
+
+fn from_buffer<T>(buf: &mut [MaybeUninit<u8>]) -> MiniVec<T, FixedStorage<'_>> {
+    MiniVec::from_buffer(buf)
+}
+
+#[test]
+fn minivec_no_alloc() {
+    let mut buf = [MaybeUninit::<u8>::uninit(); 256];
+    let mut vec = from_buffer::<u32>(&mut buf);
+
+    let capacity = vec.capacity();
+    assert!(capacity >= 32);
+
+    vec.extend(0..8);
+    vec.retain(|x| x % 2 == 0);
+    assert_eq!(vec, [0, 2, 4, 6]);
+
+    assert_eq!(vec.drain(1..3).sum::<u32>(), 6);
+    assert_eq!(vec, [0, 6]);
+
+    while vec.try_push(1).is_ok() {}
+    assert_eq!(vec.len(), capacity);
+    assert_eq!(vec.try_push(1), Err(TryReserveError::CapacityOverflow));
+
+    vec.truncate(2);
+    vec.shrink_to_fit();
+    assert_eq!(vec.capacity(), 2);
+}
+
+#[test]
+fn minivec_no_alloc_splice() {
+    let mut buf = [MaybeUninit::<u8>::uninit(); 256];
+    let mut vec = from_buffer::<u32>(&mut buf);
+    vec.extend([1, 2, 3, 4]);
+
+    // iterators that under-report their length move the tail more than once
+    //
+    let removed: u32 = vec.splice(1..2, (10..20).filter(|x| x % 2 == 0)).sum();
+    assert_eq!(removed, 2);
+    assert_eq!(vec, [1, 10, 12, 14, 16, 18, 3, 4]);
+
+    // and ones that over-report it leave a gap that's closed again
+    //
+    struct Overeager<I>(I);
+
+    impl<I: Iterator> Iterator for Overeager<I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<I::Item> {
+            self.0.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (10, None)
+        }
+    }
+
+    let removed: u32 = vec
+        .splice(..1, Overeager(IntoIterator::into_iter([1, 2])))
+        .sum();
+    assert_eq!(removed, 1);
+    assert_eq!(vec, [1, 2, 10, 12, 14, 16, 18, 3, 4]);
+
+    vec.splice(2.., [7, 8, 9].iter().copied().filter(|_| true))
+        .for_each(drop);
+    assert_eq!(vec, [1, 2, 7, 8, 9]);
+
+    vec.splice(.., core::iter::empty()).for_each(drop);
+    assert!(vec.is_empty());
+}
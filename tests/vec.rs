@@ -2,7 +2,8 @@
 #![allow(clippy::op_ref)]
 #![allow(clippy::verbose_bit_mask)]
 #![allow(clippy::reversed_empty_ranges)]
-#![allow(clippy::vtable_address_comparisons)]
+#![allow(ambiguous_wide_pointer_comparisons)]
+#![cfg(feature = "alloc")]
 
 extern crate minivec;
 
@@ -401,6 +402,7 @@ fn test_zip_unzip() {
 #[test]
 fn test_vec_truncate_drop() {
     static mut DROPS: u32 = 0;
+    #[allow(dead_code)]
     struct Elem(i32);
     impl Drop for Elem {
         fn drop(&mut self) {
@@ -452,35 +454,35 @@ fn test_index_out_of_bounds() {
 #[should_panic]
 fn test_slice_out_of_bounds_1() {
     let x = mini_vec![1, 2, 3, 4, 5];
-    &x[!0..];
+    let _ = &x[!0..];
 }
 
 #[test]
 #[should_panic]
 fn test_slice_out_of_bounds_2() {
     let x = mini_vec![1, 2, 3, 4, 5];
-    &x[..6];
+    let _ = &x[..6];
 }
 
 #[test]
 #[should_panic]
 fn test_slice_out_of_bounds_3() {
     let x = mini_vec![1, 2, 3, 4, 5];
-    &x[!0..4];
+    let _ = &x[!0..4];
 }
 
 #[test]
 #[should_panic]
 fn test_slice_out_of_bounds_4() {
     let x = mini_vec![1, 2, 3, 4, 5];
-    &x[1..6];
+    let _ = &x[1..6];
 }
 
 #[test]
 #[should_panic]
 fn test_slice_out_of_bounds_5() {
     let x = mini_vec![1, 2, 3, 4, 5];
-    &x[3..2];
+    let _ = &x[3..2];
 }
 
 #[test]
@@ -1062,7 +1064,7 @@ fn drain_filter_consumed_panic() {
     struct Check {
         index: usize,
         drop_counts: Rc<Mutex<MiniVec<usize>>>,
-    };
+    }
 
     impl Drop for Check {
         fn drop(&mut self) {
@@ -1121,7 +1123,7 @@ fn drain_filter_unconsumed_panic() {
     struct Check {
         index: usize,
         drop_counts: Rc<Mutex<MiniVec<usize>>>,
-    };
+    }
 
     impl Drop for Check {
         fn drop(&mut self) {
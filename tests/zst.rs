@@ -0,0 +1,318 @@
+#![cfg(feature = "alloc")]
+
+extern crate minivec;
+
+use minivec::{mini_vec, MiniVec};
+
+use std::cell::Cell;
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn reset_drops() {
+    DROPS.with(|d| d.set(0));
+}
+
+fn num_drops() -> usize {
+    DROPS.with(Cell::get)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Marker;
+
+impl Drop for Marker {
+    fn drop(&mut self) {
+        DROPS.with(|d| d.set(d.get() + 1));
+    }
+}
+
+// an allocator that refuses to allocate so we can prove that ZSTs never touch the heap
+//
+#[derive(Clone, Copy, Default)]
+struct NoAlloc;
+
+unsafe impl minivec::Allocator for NoAlloc {
+    fn allocate(
+        &self,
+        _layout: std::alloc::Layout,
+    ) -> Result<std::ptr::NonNull<[u8]>, minivec::AllocError> {
+        panic!("zero-sized types should never allocate");
+    }
+
+    unsafe fn deallocate(&self, _ptr: std::ptr::NonNull<u8>, _layout: std::alloc::Layout) {
+        panic!("zero-sized types should never deallocate");
+    }
+}
+
+#[test]
+fn zst_never_allocates() {
+    let mut v = MiniVec::<(), NoAlloc>::with_capacity_in(1024, NoAlloc);
+    assert_eq!(v.capacity(), usize::MAX);
+
+    for _ in 0..1000 {
+        v.push(());
+    }
+
+    v.reserve(1_000_000);
+    v.reserve_exact(1_000_000);
+    v.shrink_to_fit();
+    v.insert(10, ());
+
+    assert_eq!(v.len(), 1001);
+    assert_eq!(v.capacity(), usize::MAX);
+
+    let w = v.clone();
+    assert_eq!(w.len(), 1001);
+
+    let x = v.split_off(1);
+    assert_eq!(v.len(), 1);
+    assert_eq!(x.len(), 1000);
+
+    let v = MiniVec::<Marker, NoAlloc>::with_alignment_in(16, 64, NoAlloc).unwrap();
+    assert_eq!(v.capacity(), usize::MAX);
+}
+
+#[test]
+fn zst_push_pop() {
+    let mut v = MiniVec::<()>::new();
+    assert_eq!(v.len(), 0);
+    assert_eq!(v.capacity(), usize::MAX);
+    assert_eq!(v.pop(), None);
+
+    v.push(());
+    v.push(());
+    assert_eq!(v.len(), 2);
+    assert_eq!(v.as_slice(), [(), ()]);
+    assert_eq!(v.as_ptr() as usize % std::mem::align_of::<()>(), 0);
+    assert!(!v.as_ptr().is_null());
+
+    assert_eq!(v.pop(), Some(()));
+    assert_eq!(v.pop(), Some(()));
+    assert_eq!(v.pop(), None);
+    assert!(v.is_empty());
+
+    let v = mini_vec![(); 1234];
+    assert_eq!(v.len(), 1234);
+    assert_eq!(v.iter().count(), 1234);
+}
+
+#[test]
+fn zst_over_aligned_pointer() {
+    #[repr(align(64))]
+    struct Aligned;
+
+    let mut v = MiniVec::<Aligned>::new();
+    v.push(Aligned);
+    v.push(Aligned);
+
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+    assert_eq!(v.as_mut_slice().len(), 2);
+}
+
+#[test]
+fn zst_drops() {
+    reset_drops();
+    {
+        let mut v = MiniVec::new();
+        for _ in 0..10 {
+            v.push(Marker);
+        }
+
+        v.truncate(7);
+        assert_eq!(num_drops(), 3);
+
+        drop(v.remove(0));
+        drop(v.swap_remove(0));
+        assert_eq!(num_drops(), 5);
+        assert_eq!(v.len(), 5);
+    }
+    assert_eq!(num_drops(), 10);
+
+    let mut v = mini_vec![Marker; 4];
+    reset_drops();
+    v.clear();
+    assert_eq!(num_drops(), 4);
+    assert_eq!(v.len(), 0);
+}
+
+#[test]
+fn zst_into_iter() {
+    reset_drops();
+
+    let v = mini_vec![Marker; 8];
+    reset_drops();
+
+    let mut iter = v.into_iter();
+    assert_eq!(iter.len(), 8);
+    assert_eq!(iter.size_hint(), (8, Some(8)));
+
+    assert!(iter.next().is_some());
+    assert!(iter.next_back().is_some());
+    assert_eq!(num_drops(), 2);
+    assert_eq!(iter.as_slice().len(), 6);
+
+    let copy = iter.clone();
+    assert_eq!(copy.count(), 6);
+    assert_eq!(num_drops(), 8);
+
+    drop(iter);
+    assert_eq!(num_drops(), 14);
+
+    let v: MiniVec<()> = MiniVec::new();
+    assert_eq!(v.into_iter().next(), None);
+
+    let v = mini_vec![(); 100];
+    assert_eq!(v.into_iter().rev().count(), 100);
+}
+
+#[test]
+fn zst_drain() {
+    let mut v = mini_vec![(); 10];
+
+    {
+        let mut drain = v.drain(2..6);
+        assert_eq!(drain.size_hint(), (4, Some(4)));
+        assert_eq!(drain.next(), Some(()));
+        assert_eq!(drain.next_back(), Some(()));
+        assert_eq!(drain.len(), 2);
+    }
+    assert_eq!(v.len(), 6);
+
+    assert_eq!(v.drain(..).count(), 6);
+    assert!(v.is_empty());
+
+    reset_drops();
+    let mut v = mini_vec![Marker; 10];
+    reset_drops();
+
+    drop(v.drain(..5));
+    assert_eq!(num_drops(), 5);
+    assert_eq!(v.len(), 5);
+}
+
+#[test]
+fn zst_drain_filter() {
+    let mut v = mini_vec![(); 10];
+
+    let mut toggle = false;
+    let drained = v
+        .drain_filter(|_| {
+            toggle = !toggle;
+            toggle
+        })
+        .count();
+
+    assert_eq!(drained, 5);
+    assert_eq!(v.len(), 5);
+}
+
+#[test]
+fn zst_splice() {
+    let mut v = mini_vec![(); 10];
+
+    let removed = v.splice(2..4, std::iter::repeat_n((), 5)).count();
+    assert_eq!(removed, 2);
+    assert_eq!(v.len(), 13);
+
+    let removed = v.splice(..10, std::iter::once(())).count();
+    assert_eq!(removed, 10);
+    assert_eq!(v.len(), 4);
+
+    let mut splice = v.splice(.., std::iter::empty());
+    assert_eq!(splice.size_hint(), (4, Some(4)));
+    assert_eq!(splice.next_back(), Some(()));
+    drop(splice);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn zst_dedup_retain() {
+    let mut v = mini_vec![(); 10];
+    v.dedup();
+    assert_eq!(v.len(), 1);
+
+    let mut v = mini_vec![(); 10];
+    let mut count = 0;
+    v.retain(|_| {
+        count += 1;
+        count % 3 == 0
+    });
+    assert_eq!(count, 10);
+    assert_eq!(v.len(), 3);
+
+    let mut v = mini_vec![(); 10];
+    let mut count = 0;
+    v.dedup_by(|_, _| {
+        count += 1;
+        count % 2 == 0
+    });
+    assert_eq!(count, 9);
+    assert_eq!(v.len(), 6);
+}
+
+#[test]
+fn zst_raw_parts() {
+    let v = mini_vec![(); 42];
+
+    let (ptr, len, cap) = v.into_raw_parts();
+    assert!(!ptr.is_null());
+    assert_eq!(len, 42);
+    assert_eq!(cap, usize::MAX);
+
+    let v = unsafe { MiniVec::from_raw_parts(ptr, len, cap) };
+    assert_eq!(v.len(), 42);
+
+    let leaked = MiniVec::leak(v);
+    assert_eq!(leaked.len(), 42);
+}
+
+#[test]
+fn zst_capacity_overflow() {
+    let mut v = MiniVec::<()>::new();
+    unsafe { v.set_len(usize::MAX - 1) };
+    assert_eq!(v.len(), usize::MAX - 1);
+
+    assert_eq!(
+        v.try_reserve(2),
+        Err(minivec::TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(
+        v.try_reserve_exact(2),
+        Err(minivec::TryReserveError::CapacityOverflow)
+    );
+
+    unsafe { v.set_len(0) };
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn zst_max_len() {
+    let mut v = MiniVec::<()>::new();
+    unsafe { v.set_len(usize::MAX - 1) };
+    v.push(());
+}
+
+#[test]
+fn zst_spare_capacity_and_misc() {
+    let mut v = MiniVec::<()>::new();
+    assert_eq!(v.spare_capacity_mut().len(), usize::MAX);
+
+    v.push(());
+    assert_eq!(v.spare_capacity_mut().len(), usize::MAX - 1);
+
+    let mut w = mini_vec![(); 3];
+    v.append(&mut w);
+    assert_eq!(v.len(), 4);
+    assert!(w.is_empty());
+
+    v.resize(10, ());
+    assert_eq!(v.len(), 10);
+    v.resize_with(2, || ());
+    assert_eq!(v.len(), 2);
+
+    let v: MiniVec<()> = std::iter::repeat_n((), 17).collect();
+    assert_eq!(v.len(), 17);
+    assert_eq!(v, vec![(); 17].as_slice());
+    assert_eq!(format!("{:?}", mini_vec![(); 2]), "[(), ()]");
+}